@@ -1,6 +1,20 @@
+use application::usecases::jobs::outbox_job::DEFAULT_BATCH_SIZE;
+use application::usecases::jobs::outbox_job::DEFAULT_LEASE;
+use application::usecases::jobs::outbox_job::OutboxRetryPolicy;
+use domain::model::kernel::coordinate_system::CoordinateSystem;
+use domain::model::kernel::coordinate_system::DistanceFormula;
+use domain::model::kernel::coordinate_system::GeoArea;
+use domain::model::kernel::coordinate_system::GeoSettings;
+use domain::model::kernel::grid::GridBounds;
 use envy::Error;
 use envy::from_env;
+use in_kafka::baskets_events_consumer::DEFAULT_MAX_IN_FLIGHT;
+use in_kafka::retry::ConsumerRetryPolicy;
 use serde::Deserialize;
+use std::time::Duration;
+
+use crate::cron::CronSettings;
+use crate::outbox_relay::OutboxRelaySettings;
 
 fn default_server_address() -> String {
     String::from("0.0.0.0")
@@ -27,9 +41,100 @@ fn default_kafka_group() -> String {
 fn default_kafka_confirmed_topic() -> String {
     String::from("basket.confirmed")
 }
+fn default_kafka_cancelled_topic() -> String {
+    String::from("basket.cancelled")
+}
 fn default_kafka_changed_topic() -> String {
     String::from("order.status.changed")
 }
+fn default_kafka_dlq_topic() -> String {
+    String::from("basket.dlq")
+}
+fn default_kafka_consumer_max_attempts() -> u32 {
+    ConsumerRetryPolicy::DEFAULT.max_attempts()
+}
+fn default_kafka_consumer_retry_base_ms() -> u64 {
+    500
+}
+fn default_kafka_consumer_retry_max_ms() -> u64 {
+    30_000
+}
+fn default_kafka_consumer_max_in_flight() -> usize {
+    DEFAULT_MAX_IN_FLIGHT
+}
+
+fn default_dispatch_strategy() -> String {
+    String::from("nearest")
+}
+fn default_dispatch_weight() -> f64 {
+    1.0
+}
+
+fn default_grid_width() -> u16 {
+    GridBounds::DEFAULT.width()
+}
+fn default_grid_height() -> u16 {
+    GridBounds::DEFAULT.height()
+}
+
+fn default_movement_model() -> String {
+    String::from("axis")
+}
+
+fn default_location_mode() -> String {
+    String::from("grid")
+}
+fn default_geo_distance_formula() -> String {
+    String::from("haversine")
+}
+fn default_geo_tick_seconds() -> u32 {
+    GeoSettings::DEFAULT.tick_seconds()
+}
+fn default_outbox_max_attempts() -> u32 {
+    OutboxRetryPolicy::DEFAULT.max_attempts()
+}
+fn default_outbox_retry_base_ms() -> u64 {
+    1_000
+}
+fn default_outbox_retry_max_ms() -> u64 {
+    60_000
+}
+fn default_outbox_relays() -> usize {
+    1
+}
+fn default_outbox_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+fn default_outbox_lease_ms() -> u64 {
+    DEFAULT_LEASE.as_millis() as u64
+}
+fn default_outbox_poll_interval_ms() -> u64 {
+    1_000
+}
+fn default_outbox_retention_hours() -> u64 {
+    168
+}
+fn default_shutdown_drain_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Courier selection used by the assign orders job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchStrategy {
+    Nearest,
+    LeastLoaded,
+    RoundRobin,
+    BestVolumeFit,
+    WeightedScore,
+}
+
+/// How couriers move across the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementModelKind {
+    Axis,
+    Manhattan,
+    AStar,
+}
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -53,12 +158,265 @@ pub struct Config {
     pub kafka_consumer_group: String,
     #[serde(default = "default_kafka_confirmed_topic")]
     pub kafka_basket_confirmed_topic: String,
+    #[serde(default = "default_kafka_cancelled_topic")]
+    pub kafka_basket_cancelled_topic: String,
     #[serde(default = "default_kafka_changed_topic")]
     pub kafka_order_changed_topic: String,
+    #[serde(default = "default_kafka_dlq_topic")]
+    pub kafka_basket_dlq_topic: String,
+    #[serde(default = "default_kafka_consumer_max_attempts")]
+    pub kafka_consumer_max_attempts: u32,
+    #[serde(default = "default_kafka_consumer_retry_base_ms")]
+    pub kafka_consumer_retry_base_ms: u64,
+    #[serde(default = "default_kafka_consumer_retry_max_ms")]
+    pub kafka_consumer_retry_max_ms: u64,
+    #[serde(default = "default_kafka_consumer_max_in_flight")]
+    pub kafka_consumer_max_in_flight: usize,
+    #[serde(default)]
+    pub kafka_properties: Vec<String>,
+    #[serde(default)]
+    pub kafka_consumer_properties: Vec<String>,
+    #[serde(default)]
+    pub kafka_producer_properties: Vec<String>,
+    pub pickup_street: Option<String>,
+    #[serde(default = "default_dispatch_strategy")]
+    pub dispatch_strategy: String,
+    #[serde(default = "default_dispatch_weight")]
+    pub dispatch_distance_weight: f64,
+    #[serde(default = "default_dispatch_weight")]
+    pub dispatch_load_weight: f64,
+    #[serde(default = "default_dispatch_weight")]
+    pub dispatch_fit_weight: f64,
+    #[serde(default)]
+    pub assign_batch: bool,
+    #[serde(default = "default_grid_width")]
+    pub grid_width: u16,
+    #[serde(default = "default_grid_height")]
+    pub grid_height: u16,
+    #[serde(default = "default_movement_model")]
+    pub movement_model: String,
+    #[serde(default)]
+    pub grid_obstacles: Vec<String>,
+    #[serde(default = "default_location_mode")]
+    pub location_mode: String,
+    #[serde(default = "default_geo_distance_formula")]
+    pub geo_distance_formula: String,
+    #[serde(default)]
+    pub geo_area: Vec<String>,
+    #[serde(default = "default_geo_tick_seconds")]
+    pub geo_tick_seconds: u32,
+    #[serde(default = "default_outbox_max_attempts")]
+    pub outbox_max_attempts: u32,
+    #[serde(default = "default_outbox_retry_base_ms")]
+    pub outbox_retry_base_ms: u64,
+    #[serde(default = "default_outbox_retry_max_ms")]
+    pub outbox_retry_max_ms: u64,
+    #[serde(default = "default_outbox_relays")]
+    pub outbox_relays: usize,
+    #[serde(default = "default_outbox_batch_size")]
+    pub outbox_batch_size: usize,
+    #[serde(default = "default_outbox_lease_ms")]
+    pub outbox_lease_ms: u64,
+    #[serde(default = "default_outbox_poll_interval_ms")]
+    pub outbox_poll_interval_ms: u64,
+    #[serde(default = "default_outbox_retention_hours")]
+    pub outbox_retention_hours: u64,
+    #[serde(default = "default_shutdown_drain_timeout_ms")]
+    pub shutdown_drain_timeout_ms: u64,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self, Error> {
-        from_env::<Config>()
+        let config = from_env::<Config>()?;
+        config.kafka_consumer_client_properties()?;
+        config.kafka_producer_client_properties()?;
+        config.dispatch_strategy()?;
+        config.grid_bounds()?;
+        config.movement_model()?;
+        config.grid_obstacles()?;
+        config.coordinate_system()?;
+        Ok(config)
+    }
+
+    pub fn grid_bounds(&self) -> Result<GridBounds, Error> {
+        GridBounds::new(self.grid_width, self.grid_height)
+            .map_err(|e| Error::Custom(format!("invalid grid bounds: {}", e)))
+    }
+
+    pub fn movement_model(&self) -> Result<MovementModelKind, Error> {
+        match self.movement_model.trim().to_lowercase().as_str() {
+            "axis" => Ok(MovementModelKind::Axis),
+            "manhattan" => Ok(MovementModelKind::Manhattan),
+            "astar" => Ok(MovementModelKind::AStar),
+            other => Err(Error::Custom(format!(
+                "unknown movement model {:?}, expected one of axis, manhattan, astar",
+                other
+            ))),
+        }
+    }
+
+    /// Entries come as comma separated `x:y` cells, e.g.
+    /// `GRID_OBSTACLES=5:1,5:2,5:3`.
+    pub fn grid_obstacles(&self) -> Result<Vec<(u16, u16)>, Error> {
+        self.grid_obstacles
+            .iter()
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                entry
+                    .split_once(':')
+                    .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+                    .ok_or_else(|| {
+                        Error::Custom(format!("invalid grid obstacle {:?}, expected x:y", entry))
+                    })
+            })
+            .collect()
+    }
+
+    pub fn coordinate_system(&self) -> Result<CoordinateSystem, Error> {
+        match self.location_mode.trim().to_lowercase().as_str() {
+            "grid" => self.grid_bounds().map(CoordinateSystem::Grid),
+            "geographic" => {
+                let formula = match self.geo_distance_formula.trim().to_lowercase().as_str() {
+                    "haversine" => DistanceFormula::Haversine,
+                    "equirectangular" => DistanceFormula::Equirectangular,
+                    other => {
+                        return Err(Error::Custom(format!(
+                            "unknown geo distance formula {:?}, expected haversine or \
+                             equirectangular",
+                            other
+                        )));
+                    }
+                };
+                let bounds = self
+                    .geo_area
+                    .iter()
+                    .filter(|entry| !entry.trim().is_empty())
+                    .map(|entry| {
+                        entry.trim().parse::<f64>().map_err(|_| {
+                            Error::Custom(format!("invalid geo area bound {:?}", entry))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let area = match bounds.as_slice() {
+                    [] => Ok(GeoArea::WORLD),
+                    &[south, west, north, east] => GeoArea::new(south, west, north, east),
+                    _ => {
+                        return Err(Error::Custom(
+                            "geo area expects south,west,north,east".to_string(),
+                        ));
+                    }
+                };
+
+                area.and_then(|area| GeoSettings::new(area, formula, self.geo_tick_seconds))
+                    .map(CoordinateSystem::Geographic)
+                    .map_err(|e| Error::Custom(format!("invalid geo settings: {}", e)))
+            }
+            other => Err(Error::Custom(format!(
+                "unknown location mode {:?}, expected grid or geographic",
+                other
+            ))),
+        }
+    }
+
+    /// Transient consumer failures back off exponentially from
+    /// `KAFKA_CONSUMER_RETRY_BASE_MS` up to `KAFKA_CONSUMER_RETRY_MAX_MS`; the
+    /// message is dead-lettered after `KAFKA_CONSUMER_MAX_ATTEMPTS`.
+    pub fn consumer_retry_policy(&self) -> ConsumerRetryPolicy {
+        ConsumerRetryPolicy::new(
+            self.kafka_consumer_max_attempts,
+            Duration::from_millis(self.kafka_consumer_retry_base_ms),
+            Duration::from_millis(self.kafka_consumer_retry_max_ms),
+        )
+    }
+
+    /// Failed deliveries back off exponentially from `OUTBOX_RETRY_BASE_MS` up to
+    /// `OUTBOX_RETRY_MAX_MS`; the message is dead-lettered after `OUTBOX_MAX_ATTEMPTS`.
+    pub fn outbox_retry_policy(&self) -> OutboxRetryPolicy {
+        OutboxRetryPolicy::new(
+            self.outbox_max_attempts,
+            Duration::from_millis(self.outbox_retry_base_ms),
+            Duration::from_millis(self.outbox_retry_max_ms),
+        )
+    }
+
+    pub fn outbox_relay_settings(&self) -> OutboxRelaySettings {
+        OutboxRelaySettings {
+            relays: self.outbox_relays,
+            batch_size: self.outbox_batch_size,
+            lease: Duration::from_millis(self.outbox_lease_ms),
+            poll_interval: Duration::from_millis(self.outbox_poll_interval_ms),
+            retry_policy: self.outbox_retry_policy(),
+        }
+    }
+
+    pub fn cron_settings(&self) -> Result<CronSettings, Error> {
+        Ok(CronSettings {
+            dispatch_strategy: self.dispatch_strategy()?,
+            distance_weight: self.dispatch_distance_weight,
+            load_weight: self.dispatch_load_weight,
+            fit_weight: self.dispatch_fit_weight,
+            batch_assignment: self.assign_batch,
+            outbox_retention: self.outbox_retention(),
+        })
+    }
+
+    /// How long each shutdown stage may take before its components are aborted.
+    pub fn shutdown_drain_timeout(&self) -> Duration {
+        Duration::from_millis(self.shutdown_drain_timeout_ms)
+    }
+
+    /// Published outbox messages older than this are purged.
+    pub fn outbox_retention(&self) -> Duration {
+        Duration::from_secs(self.outbox_retention_hours.saturating_mul(60 * 60))
     }
+
+    pub fn dispatch_strategy(&self) -> Result<DispatchStrategy, Error> {
+        match self.dispatch_strategy.trim().to_lowercase().as_str() {
+            "nearest" => Ok(DispatchStrategy::Nearest),
+            "least_loaded" => Ok(DispatchStrategy::LeastLoaded),
+            "round_robin" => Ok(DispatchStrategy::RoundRobin),
+            "best_volume_fit" => Ok(DispatchStrategy::BestVolumeFit),
+            "weighted_score" => Ok(DispatchStrategy::WeightedScore),
+            other => Err(Error::Custom(format!(
+                "unknown dispatch strategy {:?}, expected one of nearest, least_loaded, \
+                 round_robin, best_volume_fit, weighted_score",
+                other
+            ))),
+        }
+    }
+
+    pub fn kafka_consumer_client_properties(&self) -> Result<Vec<(String, String)>, Error> {
+        parse_kafka_properties(
+            self.kafka_properties
+                .iter()
+                .chain(self.kafka_consumer_properties.iter()),
+        )
+    }
+
+    pub fn kafka_producer_client_properties(&self) -> Result<Vec<(String, String)>, Error> {
+        parse_kafka_properties(
+            self.kafka_properties
+                .iter()
+                .chain(self.kafka_producer_properties.iter()),
+        )
+    }
+}
+
+/// Entries come as comma separated `key=value` pairs, e.g.
+/// `KAFKA_PROPERTIES=security.protocol=SASL_SSL,sasl.mechanisms=PLAIN`.
+fn parse_kafka_properties<'a>(
+    entries: impl Iterator<Item = &'a String>,
+) -> Result<Vec<(String, String)>, Error> {
+    entries
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(Error::Custom(format!(
+                "invalid kafka property {:?}, expected key=value",
+                entry
+            ))),
+        })
+        .collect()
 }
@@ -1,17 +1,28 @@
 use application::usecases::CommandHandler;
 use application::usecases::JobHandler;
+use application::usecases::commands::apply_shift_schedules_command::ApplyShiftSchedulesCommand;
+use application::usecases::commands::apply_shift_schedules_handler::ApplyShiftSchedulesHandler;
 use application::usecases::commands::assign_order_command::AssignOrderCommand;
 use application::usecases::commands::assign_order_handler::AssignOrderHandler;
+use application::usecases::commands::assign_orders_batch_command::AssignOrdersBatchCommand;
+use application::usecases::commands::assign_orders_batch_handler::AssignOrdersBatchHandler;
 use application::usecases::commands::move_couriers_command::MoveCouriersCommand;
 use application::usecases::commands::move_couriers_handler::MoveCouriersHandler;
-use application::usecases::events::event_bus::EventBus;
-use application::usecases::jobs::outbox_job::OutboxJob;
-use out_kafka::orders_events_producer::OrdersEventsProducer;
+use application::usecases::jobs::purge_outbox_job::PurgeOutboxJob;
+use domain::model::courier::city_map::CityMap;
+use domain::model::services::dispatch_strategies::BestVolumeFitDispatcher;
+use domain::model::services::dispatch_strategies::LeastLoadedDispatcher;
+use domain::model::services::dispatch_strategies::RoundRobinDispatcher;
+use domain::model::services::dispatch_strategies::WeightedScoreDispatcher;
+use domain::model::services::order_dispatcher::OrderDispatcher;
+use domain::model::services::order_dispatcher::OrderDispatcherService;
 use out_postgres::ConnectionManager;
 use out_postgres::PgConnection;
 use out_postgres::Pool;
+use out_postgres::courier::courier_repository::CourierRepository;
 use out_postgres::outbox::outbox_repository::OutboxRepository;
 use out_postgres::unit_of_work::UnitOfWork;
+use std::fmt::Debug;
 use std::panic;
 use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
@@ -21,167 +32,116 @@ use tokio::runtime::Handle;
 use tokio::task;
 use tokio_cron_scheduler::Job;
 use tokio_cron_scheduler::JobScheduler;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::DispatchStrategy;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CronSettings {
+    pub dispatch_strategy: DispatchStrategy,
+    pub distance_weight: f64,
+    pub load_weight: f64,
+    pub fit_weight: f64,
+    pub batch_assignment: bool,
+    pub outbox_retention: Duration,
+}
+
+/// Runs the cron jobs until `shutdown` is cancelled, then stops scheduling them.
+pub async fn run_crons(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    settings: CronSettings,
+    map: CityMap,
+    shutdown: CancellationToken,
+) {
+    let batch = settings.batch_assignment;
+    let retention = settings.outbox_retention;
+    let mut scheduler = match settings.dispatch_strategy {
+        DispatchStrategy::Nearest => {
+            start_crons(pool, OrderDispatcherService, map, batch, retention).await
+        }
+        DispatchStrategy::LeastLoaded => {
+            start_crons(pool, LeastLoadedDispatcher, map, batch, retention).await
+        }
+        DispatchStrategy::RoundRobin => {
+            start_crons(pool, RoundRobinDispatcher::new(), map, batch, retention).await
+        }
+        DispatchStrategy::BestVolumeFit => {
+            start_crons(pool, BestVolumeFitDispatcher, map, batch, retention).await
+        }
+        DispatchStrategy::WeightedScore => {
+            let dispatcher = WeightedScoreDispatcher::new(
+                settings.distance_weight,
+                settings.load_weight,
+                settings.fit_weight,
+            );
+            start_crons(pool, dispatcher, map, batch, retention).await
+        }
+    };
+
+    shutdown.cancelled().await;
+
+    if let Err(error) = scheduler.shutdown().await {
+        tracing::error!(?error, "failed to shutdown cron scheduler");
+    }
+}
 
 pub async fn start_crons(
     pool: Pool<ConnectionManager<PgConnection>>,
-    event_bus: impl EventBus + 'static,
-    brokers: &str,
-    group_id: &str,
+    dispatcher: impl OrderDispatcher + Send + 'static,
+    map: CityMap,
+    batch_assignment: bool,
+    outbox_retention: Duration,
 ) -> JobScheduler {
     let scheduler = JobScheduler::new()
         .await
         .expect("failed to initialize cron scheduler");
 
-    let move_couriers_handler = Arc::new(Mutex::new(MoveCouriersHandler::new(
-        UnitOfWork::new(pool.clone()),
-        event_bus,
-    )));
-    let move_couriers_handler_job = Arc::clone(&move_couriers_handler);
-    let runtime_handle = Handle::current();
-    let move_job_handle = runtime_handle.clone();
-    match Job::new_repeated_async(Duration::from_secs(1), move |_uuid, _l| {
-        let handler = Arc::clone(&move_couriers_handler_job);
-        let handle = move_job_handle.clone();
-        Box::pin(async move {
-            let join_result = task::spawn_blocking(move || {
-                let run_result = panic::catch_unwind(AssertUnwindSafe(|| {
-                    let mut handler = match handler.lock() {
-                        Ok(handler) => handler,
-                        Err(err) => {
-                            tracing::error!(
-                                error = %err,
-                                "move couriers handler mutex poisoned"
-                            );
-                            err.into_inner()
-                        }
-                    };
-
-                    match MoveCouriersCommand::new() {
-                        Ok(command) => {
-                            if let Err(err) = handle.block_on(handler.execute(command)) {
-                                tracing::warn!(?err, "move couriers job failed");
-                            }
-                        }
-                        Err(err) => tracing::error!(?err, "failed to create move couriers command"),
-                    }
-                }));
-
-                if let Err(err) = run_result {
-                    tracing::error!(?err, "move couriers job panicked");
-                }
-            })
-            .await;
-
-            if let Err(join_err) = join_result {
-                tracing::error!(?join_err, "move couriers job task panicked");
-            }
-        })
-    }) {
-        Ok(job) => {
-            if let Err(error) = scheduler.add(job).await {
-                tracing::error!(?error, "failed to register move_couriers job");
-            }
-        }
-        Err(error) => tracing::error!(?error, "failed to register move_couriers job"),
-    }
+    schedule(
+        &scheduler,
+        "move_couriers",
+        Duration::from_secs(1),
+        MoveCouriersHandler::new(UnitOfWork::new(pool.clone()), map.clone()),
+        |handler, handle| handle.block_on(handler.execute(MoveCouriersCommand::new()?)),
+    )
+    .await;
 
-    let assign_order_handler = Arc::new(Mutex::new(AssignOrderHandler::new(UnitOfWork::new(
-        pool.clone(),
-    ))));
-    let assign_order_handler_job = Arc::clone(&assign_order_handler);
-    let assign_job_handle = runtime_handle.clone();
-    match Job::new_repeated_async(Duration::from_secs(1), move |_uuid, _l| {
-        let handler = Arc::clone(&assign_order_handler_job);
-        let handle = assign_job_handle.clone();
-        Box::pin(async move {
-            let join_result = task::spawn_blocking(move || {
-                let run_result = panic::catch_unwind(AssertUnwindSafe(|| {
-                    let mut handler = match handler.lock() {
-                        Ok(handler) => handler,
-                        Err(err) => {
-                            tracing::error!(
-                                error = %err,
-                                "assign order handler mutex poisoned"
-                            );
-                            err.into_inner()
-                        }
-                    };
-
-                    match AssignOrderCommand::new() {
-                        Ok(command) => {
-                            if let Err(err) = handle.block_on(handler.execute(command)) {
-                                tracing::warn!(?err, "assign orders job failed");
-                            }
-                        }
-                        Err(err) => tracing::error!(?err, "failed to create assign order command"),
-                    }
-                }));
-
-                if let Err(err) = run_result {
-                    tracing::error!(?err, "assign orders job panicked");
-                }
-            })
-            .await;
-
-            if let Err(join_err) = join_result {
-                tracing::error!(?join_err, "assign orders job task panicked");
-            }
-        })
-    }) {
-        Ok(job) => {
-            if let Err(error) = scheduler.add(job).await {
-                tracing::error!(?error, "failed to register assign_orders job");
-            }
-        }
-        Err(error) => tracing::error!(?error, "failed to register assign_orders job"),
+    if batch_assignment {
+        schedule(
+            &scheduler,
+            "assign_orders_batch",
+            Duration::from_secs(1),
+            AssignOrdersBatchHandler::new(UnitOfWork::new(pool.clone()), map),
+            |handler, handle| handle.block_on(handler.execute(AssignOrdersBatchCommand::new()?)),
+        )
+        .await;
+    } else {
+        schedule(
+            &scheduler,
+            "assign_orders",
+            Duration::from_secs(1),
+            AssignOrderHandler::new(UnitOfWork::new(pool.clone()), dispatcher, map),
+            |handler, handle| handle.block_on(handler.execute(AssignOrderCommand::new()?)),
+        )
+        .await;
     }
 
-    let outbox_job = Arc::new(Mutex::new(OutboxJob::new(
-        OutboxRepository::new(pool),
-        OrdersEventsProducer::new(brokers, group_id),
-    )));
-    let outbox_handler_job = Arc::clone(&outbox_job);
-    let outbox_job_handle = runtime_handle.clone();
-    match Job::new_repeated_async(Duration::from_secs(10), move |_uuid, _l| {
-        let handler = Arc::clone(&outbox_handler_job);
-        let handle = outbox_job_handle.clone();
-        Box::pin(async move {
-            let join_result = task::spawn_blocking(move || {
-                let run_result = panic::catch_unwind(AssertUnwindSafe(|| {
-                    let mut handler = match handler.lock() {
-                        Ok(handler) => handler,
-                        Err(err) => {
-                            tracing::error!(
-                                error = %err,
-                                "outbox job mutex poisoned"
-                            );
-                            err.into_inner()
-                        }
-                    };
-
-                    if let Err(err) = handle.block_on(handler.execute()) {
-                        tracing::warn!(?err, "outbox job failed");
-                    }
-                }));
-
-                if let Err(err) = run_result {
-                    tracing::error!(?err, "outbox job panicked");
-                }
-            })
-            .await;
-
-            if let Err(join_err) = join_result {
-                tracing::error!(?join_err, "outbox job task panicked");
-            }
-        })
-    }) {
-        Ok(job) => {
-            if let Err(error) = scheduler.add(job).await {
-                tracing::error!(?error, "failed to register outbox job");
-            }
-        }
-        Err(error) => tracing::error!(?error, "failed to register outbox job"),
-    }
+    schedule(
+        &scheduler,
+        "shift_schedules",
+        Duration::from_secs(60),
+        ApplyShiftSchedulesHandler::new(CourierRepository::new(pool.clone())),
+        |handler, handle| handle.block_on(handler.execute(ApplyShiftSchedulesCommand::new()?)),
+    )
+    .await;
+
+    schedule(
+        &scheduler,
+        "purge_outbox",
+        Duration::from_secs(600),
+        PurgeOutboxJob::new(OutboxRepository::new(pool), outbox_retention),
+        |job, handle| handle.block_on(job.execute()),
+    )
+    .await;
 
     scheduler.start().await.unwrap_or_else(|error| {
         tracing::error!(?error, "failed to launch cron scheduler");
@@ -189,3 +149,65 @@ pub async fn start_crons(
 
     scheduler
 }
+
+/// Registers `run` to be called on `job` every `period`, one tick at a time
+/// through `run_job`.
+async fn schedule<J, E>(
+    scheduler: &JobScheduler,
+    name: &'static str,
+    period: Duration,
+    job: J,
+    run: fn(&mut J, &Handle) -> Result<(), E>,
+) where
+    J: Send + 'static,
+    E: Debug + 'static,
+{
+    let job = Arc::new(Mutex::new(job));
+    let handle = Handle::current();
+    let registered = Job::new_repeated_async(period, move |_uuid, _l| {
+        Box::pin(run_job(name, Arc::clone(&job), handle.clone(), run))
+    });
+
+    match registered {
+        Ok(registered) => {
+            if let Err(error) = scheduler.add(registered).await {
+                tracing::error!(?error, job = name, "failed to register job");
+            }
+        }
+        Err(error) => tracing::error!(?error, job = name, "failed to register job"),
+    }
+}
+
+/// Runs one tick of `job` on a blocking thread. Failures and panics are
+/// logged instead of reaching the scheduler, and a mutex poisoned by a
+/// panicked tick is taken over, so the next tick runs as usual.
+pub(crate) async fn run_job<J, E>(
+    name: &'static str,
+    job: Arc<Mutex<J>>,
+    handle: Handle,
+    run: impl FnOnce(&mut J, &Handle) -> Result<(), E> + Send + 'static,
+) where
+    J: Send + 'static,
+    E: Debug,
+{
+    let join_result = task::spawn_blocking(move || {
+        let run_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut job = job.lock().unwrap_or_else(|err| {
+                tracing::error!(error = %err, job = name, "job mutex poisoned");
+                err.into_inner()
+            });
+            run(&mut job, &handle)
+        }));
+
+        match run_result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => tracing::warn!(?err, job = name, "job failed"),
+            Err(err) => tracing::error!(?err, job = name, "job panicked"),
+        }
+    })
+    .await;
+
+    if let Err(join_err) = join_result {
+        tracing::error!(?join_err, job = name, "job task panicked");
+    }
+}
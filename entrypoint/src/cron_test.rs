@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use tokio::runtime::Handle;
+
+use crate::cron::run_job;
+
+#[tokio::test]
+async fn keeps_running_after_a_tick_panics() {
+    let ticks = Arc::new(Mutex::new(0));
+
+    run_job(
+        "panicking",
+        Arc::clone(&ticks),
+        Handle::current(),
+        |ticks, _| -> Result<(), String> {
+            *ticks += 1;
+            panic!("tick failed")
+        },
+    )
+    .await;
+    assert!(ticks.is_poisoned());
+
+    run_job(
+        "panicking",
+        Arc::clone(&ticks),
+        Handle::current(),
+        |ticks, _| {
+            *ticks += 1;
+            Ok::<(), String>(())
+        },
+    )
+    .await;
+
+    assert_eq!(*ticks.lock().unwrap_or_else(PoisonError::into_inner), 2);
+}
+
+#[tokio::test]
+async fn runs_the_tick_on_the_given_runtime() {
+    let ticks = Arc::new(Mutex::new(0));
+
+    run_job(
+        "async",
+        Arc::clone(&ticks),
+        Handle::current(),
+        |ticks, handle| {
+            *ticks = handle.block_on(async { 7 });
+            Err("tick failed")
+        },
+    )
+    .await;
+
+    assert_eq!(*ticks.lock().unwrap(), 7);
+}
@@ -1,33 +1,55 @@
 mod config;
 mod cron;
+#[cfg(test)]
+mod cron_test;
+mod outbox_relay;
+mod supervisor;
+#[cfg(test)]
+mod supervisor_test;
 
-use application::usecases::events::event_bus::EventBus;
-use application::usecases::events::event_bus::EventBusImpl;
-use application::usecases::events::order_completed_event_handler::OrderCompletedEventHandler;
-use application::usecases::events::order_created_event_handler::OrderCreatedEventHandler;
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::movement::AxisStepping;
+use domain::model::courier::movement::ManhattanStepping;
+use domain::model::courier::movement::MovementModel;
+use domain::model::courier::pathfinding::AStarMovement;
 use in_http::server::start_server;
 use in_http::state::AppState;
+use in_kafka::basket_topics::BasketTopicHandler;
+use in_kafka::basket_topics::BasketTopics;
 use in_kafka::baskets_events_consumer::BasketEventsConsumer;
-use in_kafka::shared::Shared;
+use in_kafka::consumer_options::KafkaConsumerOptions;
+use in_kafka::dead_letter::DeadLetterProducer;
 use out_grpc_geo::geo_service::GeoService;
 use out_kafka::orders_events_producer::OrdersEventsProducer;
+use out_kafka::producer_options::KafkaProducerOptions;
+use out_postgres::blocked_cell::blocked_cell_repository::BlockedCellRepository;
 use out_postgres::connection::PgConnectionOptions;
 use out_postgres::connection::establish_connection;
 use out_postgres::courier::courier_repository::CourierRepository;
 use out_postgres::order::order_repository::OrderRepository;
-use out_postgres::outbox::outbox_repository::OutboxRepository;
 use out_postgres::unit_of_work::UnitOfWork;
+use ports::blocked_cell_repository_port::BlockedCellRepositoryPort;
+use std::process::ExitCode;
+use std::sync::Arc;
 
 use crate::config::Config;
-use crate::cron::start_crons;
+use crate::config::MovementModelKind;
+use crate::cron::run_crons;
+use crate::outbox_relay::run_outbox_relays;
+use crate::supervisor::OnPanic;
+use crate::supervisor::Stage;
+use crate::supervisor::Supervisor;
+use crate::supervisor::shutdown_signal;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::DEBUG)
         .init();
 
     let config = Config::from_env().expect("missing env variables");
+    let grid_bounds = config.grid_bounds().expect("invalid grid bounds");
+    let coordinate_system = config.coordinate_system().expect("invalid location mode");
     tracing::event!(
         tracing::Level::INFO,
         "Start server: {}:{}",
@@ -35,9 +57,12 @@ async fn main() {
         config.server_port
     );
 
-    let geo_service = GeoService::new(format!("{}:{}", config.geo_address, config.geo_port))
-        .await
-        .expect("could not connect to geo service");
+    let geo_service = GeoService::new(
+        format!("{}:{}", config.geo_address, config.geo_port),
+        coordinate_system,
+    )
+    .await
+    .expect("could not connect to geo service");
     tracing::event!(tracing::Level::INFO, "Succesfull connect to geo");
 
     let pool = establish_connection(PgConnectionOptions::new(
@@ -48,54 +73,146 @@ async fn main() {
         config.db_name.clone(),
     ));
 
-    let outbox_repo = OutboxRepository::new(pool.clone());
     let courier_repo = CourierRepository::new(pool.clone());
     let order_repo = OrderRepository::new(pool.clone());
     let uow = UnitOfWork::new(pool.clone());
 
-    let mut event_bus = EventBusImpl::new();
-    let _orders_created_producer =
-        OrdersEventsProducer::new(&config.kafka_host, &config.kafka_consumer_group);
-    let _orders_completed_producer =
-        OrdersEventsProducer::new(&config.kafka_host, &config.kafka_consumer_group);
-    event_bus.register_order_created(OrderCreatedEventHandler::new(outbox_repo.clone()));
-    event_bus.register_order_completed(OrderCompletedEventHandler::new(outbox_repo.clone()));
+    let movement_model: Arc<dyn MovementModel> =
+        match config.movement_model().expect("invalid movement model") {
+            MovementModelKind::Axis => Arc::new(AxisStepping),
+            MovementModelKind::Manhattan => Arc::new(ManhattanStepping),
+            MovementModelKind::AStar => {
+                let mut blocked = config.grid_obstacles().expect("invalid grid obstacles");
+                blocked.extend(
+                    BlockedCellRepository::new(pool.clone())
+                        .get_all()
+                        .expect("could not load blocked cells"),
+                );
+                tracing::event!(tracing::Level::INFO, "Blocked cells: {}", blocked.len());
+                Arc::new(AStarMovement::new(grid_bounds, blocked))
+            }
+        };
+    let city_map = CityMap::new(coordinate_system, movement_model);
 
     let app_state = AppState::new(
         courier_repo,
         order_repo,
         uow,
         geo_service.clone(),
-        event_bus.clone(),
+        coordinate_system,
     );
 
-    let mut scheduler = start_crons(
-        pool.clone(),
-        event_bus.clone(),
-        &config.kafka_host,
-        &config.kafka_consumer_group,
-    )
-    .await;
+    let producer_options = KafkaProducerOptions::new(
+        config.kafka_host.clone(),
+        config
+            .kafka_producer_client_properties()
+            .expect("invalid kafka producer properties"),
+    );
+    let orders_events_producer =
+        OrdersEventsProducer::new(&producer_options, &config.kafka_order_changed_topic);
+
+    let cron_settings = config.cron_settings().expect("invalid dispatch strategy");
+    tracing::event!(
+        tracing::Level::INFO,
+        "Dispatch strategy: {:?}, batch assignment: {}",
+        cron_settings.dispatch_strategy,
+        cron_settings.batch_assignment
+    );
 
-    let consumer_order_repo = Shared::new(OrderRepository::new(pool.clone()));
-    let consumer = BasketEventsConsumer::new(
-        &config.kafka_host,
-        &config.kafka_consumer_group,
-        consumer_order_repo,
-        geo_service,
-        event_bus,
+    let consumer_options = KafkaConsumerOptions::new(
+        config.kafka_host.clone(),
+        config.kafka_consumer_group.clone(),
+        config
+            .kafka_consumer_client_properties()
+            .expect("invalid kafka consumer properties"),
+    );
+    let basket_topics = BasketTopics::new()
+        .subscribe(
+            &config.kafka_basket_confirmed_topic,
+            BasketTopicHandler::Confirmed,
+        )
+        .subscribe(
+            &config.kafka_basket_cancelled_topic,
+            BasketTopicHandler::Cancelled,
+        );
+    let dead_letter = DeadLetterProducer::new(
+        &producer_options.client_config(),
+        &config.kafka_basket_dlq_topic,
     );
-    let _consumer_handle = tokio::spawn(async move {
-        consumer.consume().await;
-    });
+    let pickup_street = config
+        .pickup_street
+        .clone()
+        .filter(|street| !street.is_empty());
+    let consumer_retry_policy = config.consumer_retry_policy();
+    let consumer_max_in_flight = config.kafka_consumer_max_in_flight;
 
-    start_server(
-        &format!("{}:{}", config.server_address, config.server_port),
-        app_state,
-    )
-    .await;
+    let server_address = format!("{}:{}", config.server_address, config.server_port);
+    let outbox_relay_settings = config.outbox_relay_settings();
+    assert!(
+        outbox_relay_settings.pooled_connections() < pool.max_size() as usize,
+        "{} outbox relays may hold {} pooled connections, the pool of {} needs room for the rest",
+        outbox_relay_settings.relays,
+        outbox_relay_settings.pooled_connections(),
+        pool.max_size()
+    );
+    let consumer_pool = pool.clone();
+    let crons_pool = pool.clone();
+    let relays_pool = pool;
 
-    if let Err(error) = scheduler.shutdown().await {
-        tracing::error!(?error, "failed to shutdown cron scheduler");
-    }
+    Supervisor::new(config.shutdown_drain_timeout())
+        .add("http", Stage::Intake, OnPanic::Exit, move |shutdown| {
+            let address = server_address.clone();
+            let state = app_state.clone();
+            Box::pin(async move { start_server(&address, state, shutdown.cancelled_owned()).await })
+        })
+        .add(
+            "basket-consumer",
+            Stage::Intake,
+            OnPanic::Restart,
+            move |shutdown| {
+                let options = consumer_options.clone();
+                let topics = basket_topics.clone();
+                let uow = UnitOfWork::new(consumer_pool.clone());
+                let geo_service = geo_service.clone();
+                let pickup_street = pickup_street.clone();
+                let dead_letter = dead_letter.clone();
+                Box::pin(async move {
+                    BasketEventsConsumer::new(
+                        &options,
+                        topics,
+                        uow,
+                        geo_service,
+                        pickup_street,
+                        dead_letter,
+                    )
+                    .with_retry_policy(consumer_retry_policy)
+                    .with_max_in_flight(consumer_max_in_flight)
+                    .consume(shutdown.cancelled_owned())
+                    .await
+                })
+            },
+        )
+        .add("crons", Stage::Jobs, OnPanic::Restart, move |shutdown| {
+            Box::pin(run_crons(
+                crons_pool.clone(),
+                cron_settings,
+                city_map.clone(),
+                shutdown,
+            ))
+        })
+        .add(
+            "outbox-relay",
+            Stage::Outbox,
+            OnPanic::Restart,
+            move |shutdown| {
+                Box::pin(run_outbox_relays(
+                    relays_pool.clone(),
+                    orders_events_producer.clone(),
+                    outbox_relay_settings,
+                    shutdown,
+                ))
+            },
+        )
+        .run(shutdown_signal())
+        .await
 }
@@ -0,0 +1,127 @@
+use application::usecases::jobs::outbox_job::OutboxJob;
+use application::usecases::jobs::outbox_job::OutboxRetryPolicy;
+use out_kafka::orders_events_producer::OrdersEventsProducer;
+use out_postgres::ConnectionManager;
+use out_postgres::PgConnection;
+use out_postgres::Pool;
+use out_postgres::outbox::outbox_listener::OutboxListener;
+use out_postgres::outbox::outbox_repository::OutboxRepository;
+use ports::outbox_repository::OutboxListenerPort;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::thread;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::task;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutboxRelaySettings {
+    pub relays: usize,
+    pub batch_size: usize,
+    pub lease: Duration,
+    /// Fallback wake-up for retries and expired leases, which raise no notification.
+    pub poll_interval: Duration,
+    pub retry_policy: OutboxRetryPolicy,
+}
+
+impl OutboxRelaySettings {
+    /// Pooled connections the relays may hold at once: each keeps one for its
+    /// listener and borrows another while relaying a batch.
+    pub fn pooled_connections(&self) -> usize {
+        self.relays.max(1) * 2
+    }
+}
+
+/// Runs `settings.relays` relays on blocking threads until `shutdown` is
+/// cancelled. Each drains the outbox while batches come back full, then sleeps
+/// until an insert is notified or the poll interval passes. Relays claim
+/// disjoint batches, so they can also run in other processes.
+pub async fn run_outbox_relays(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    producer: OrdersEventsProducer,
+    settings: OutboxRelaySettings,
+    shutdown: CancellationToken,
+) {
+    let runtime_handle = Handle::current();
+
+    let relays: Vec<_> = (0..settings.relays.max(1))
+        .map(|index| {
+            let pool = pool.clone();
+            let producer = producer.clone();
+            let handle = runtime_handle.clone();
+            let shutdown = shutdown.clone();
+
+            task::spawn_blocking(move || {
+                tracing::debug!(relay = index, "outbox relay started");
+                run_relay(pool, producer, settings, handle, shutdown);
+                tracing::debug!(relay = index, "outbox relay stopped");
+            })
+        })
+        .collect();
+
+    for relay in relays {
+        if let Err(err) = relay.await {
+            tracing::error!(?err, "outbox relay failed");
+        }
+    }
+}
+
+fn run_relay(
+    pool: Pool<ConnectionManager<PgConnection>>,
+    producer: OrdersEventsProducer,
+    settings: OutboxRelaySettings,
+    handle: Handle,
+    shutdown: CancellationToken,
+) {
+    let mut job = OutboxJob::new(OutboxRepository::new(pool.clone()), producer)
+        .with_retry_policy(settings.retry_policy)
+        .with_batch_size(settings.batch_size)
+        .with_lease(settings.lease);
+    let mut listener: Option<OutboxListener> = None;
+
+    while !shutdown.is_cancelled() {
+        if relay_batch(&mut job, &handle) {
+            continue;
+        }
+
+        if listener.is_none() {
+            listener = OutboxListener::new(&pool)
+                .inspect_err(|err| tracing::warn!(?err, "failed to listen for outbox inserts"))
+                .ok();
+        }
+
+        match listener.as_mut() {
+            Some(active) => {
+                if let Err(err) = active.wait_for_messages(settings.poll_interval) {
+                    tracing::warn!(?err, "outbox listener failed");
+                    listener = None;
+                }
+            }
+            None => thread::sleep(settings.poll_interval),
+        }
+    }
+
+    // Publishes what the stages stopped before the outbox wrote on their way out.
+    while relay_batch(&mut job, &handle) {}
+}
+
+/// Relays one batch and tells whether it came back full, i.e. more may be due.
+fn relay_batch(
+    job: &mut OutboxJob<OutboxRepository, OrdersEventsProducer>,
+    handle: &Handle,
+) -> bool {
+    let run_result = panic::catch_unwind(AssertUnwindSafe(|| handle.block_on(job.relay_batch())));
+
+    match run_result {
+        Ok(Ok(claimed)) => claimed >= job.batch_size(),
+        Ok(Err(err)) => {
+            tracing::warn!(?err, "outbox relay failed");
+            false
+        }
+        Err(err) => {
+            tracing::error!(?err, "outbox relay panicked");
+            false
+        }
+    }
+}
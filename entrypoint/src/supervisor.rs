@@ -0,0 +1,238 @@
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::signal;
+use tokio::task::AbortHandle;
+use tokio::task::Id;
+use tokio::task::JoinError;
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+/// Pause before a panicked component is started again, so a component that
+/// fails on start does not spin.
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Components stop stage by stage in this order: intake first, so nothing new
+/// arrives while jobs finish, and the outbox last, so it can still publish what
+/// the earlier stages wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Intake,
+    Jobs,
+    Outbox,
+}
+
+impl Stage {
+    const SHUTDOWN_ORDER: [Stage; 3] = [Stage::Intake, Stage::Jobs, Stage::Outbox];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnPanic {
+    Restart,
+    /// Shuts the whole process down with a failure exit code.
+    Exit,
+}
+
+type Start = Box<dyn Fn(CancellationToken) -> BoxFuture<'static, ()> + Send>;
+
+struct Component {
+    name: &'static str,
+    stage: Stage,
+    on_panic: OnPanic,
+    start: Start,
+}
+
+struct Running {
+    component: usize,
+    abort: AbortHandle,
+}
+
+/// Runs the long-lived parts of the service until a shutdown is requested or
+/// one of them fails for good, then stops them in [`Stage`] order.
+///
+/// A component runs until its token is cancelled, then finishes its in-flight
+/// work and returns. Returning before that counts as a failure.
+pub struct Supervisor {
+    drain_timeout: Duration,
+    components: Vec<Component>,
+    stages: HashMap<Stage, CancellationToken>,
+}
+
+impl Supervisor {
+    /// `drain_timeout` bounds how long each stage may take to stop before its
+    /// remaining tasks are aborted.
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            drain_timeout,
+            components: Vec::new(),
+            stages: Stage::SHUTDOWN_ORDER
+                .into_iter()
+                .map(|stage| (stage, CancellationToken::new()))
+                .collect(),
+        }
+    }
+
+    pub fn add<F>(mut self, name: &'static str, stage: Stage, on_panic: OnPanic, start: F) -> Self
+    where
+        F: Fn(CancellationToken) -> BoxFuture<'static, ()> + Send + 'static,
+    {
+        self.components.push(Component {
+            name,
+            stage,
+            on_panic,
+            start: Box::new(start),
+        });
+        self
+    }
+
+    pub async fn run(self, shutdown: impl Future<Output = ()>) -> ExitCode {
+        let mut tasks = JoinSet::new();
+        let mut running = HashMap::new();
+        for index in 0..self.components.len() {
+            self.spawn(index, &mut tasks, &mut running);
+        }
+
+        let mut exit_code = ExitCode::SUCCESS;
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    tracing::info!("shutdown requested");
+                    break;
+                }
+                Some(result) = tasks.join_next_with_id() => {
+                    let (id, outcome) = match result {
+                        Ok((id, ())) => (id, Ok(())),
+                        Err(err) => (err.id(), Err(err)),
+                    };
+                    let Some(stopped) = running.remove(&id) else {
+                        continue;
+                    };
+                    let component = &self.components[stopped.component];
+
+                    match (outcome, component.on_panic) {
+                        (Err(err), OnPanic::Restart) if err.is_panic() => {
+                            tracing::error!(component = component.name, ?err, "component panicked, restarting");
+                            tokio::time::sleep(RESTART_DELAY).await;
+                            self.spawn(stopped.component, &mut tasks, &mut running);
+                        }
+                        (outcome, _) => {
+                            tracing::error!(
+                                component = component.name,
+                                error = ?outcome.err(),
+                                "component stopped unexpectedly, shutting down"
+                            );
+                            exit_code = ExitCode::FAILURE;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        for stage in Stage::SHUTDOWN_ORDER {
+            self.stop_stage(stage, &mut tasks, &mut running).await;
+        }
+
+        exit_code
+    }
+
+    fn spawn(&self, index: usize, tasks: &mut JoinSet<()>, running: &mut HashMap<Id, Running>) {
+        let component = &self.components[index];
+        let token = self.stages[&component.stage].child_token();
+        tracing::info!(component = component.name, "starting component");
+
+        let abort = tasks.spawn((component.start)(token));
+        running.insert(
+            abort.id(),
+            Running {
+                component: index,
+                abort,
+            },
+        );
+    }
+
+    async fn stop_stage(
+        &self,
+        stage: Stage,
+        tasks: &mut JoinSet<()>,
+        running: &mut HashMap<Id, Running>,
+    ) {
+        let in_stage = |running: &HashMap<Id, Running>| {
+            running
+                .values()
+                .filter(|r| self.components[r.component].stage == stage)
+                .count()
+        };
+        if in_stage(running) == 0 {
+            return;
+        }
+
+        tracing::info!(?stage, "stopping stage");
+        self.stages[&stage].cancel();
+
+        let drained = timeout(self.drain_timeout, async {
+            while in_stage(running) > 0 {
+                let Some(result) = tasks.join_next_with_id().await else {
+                    break;
+                };
+                let id = result.as_ref().map_or_else(JoinError::id, |(id, ())| *id);
+                if let Some(stopped) = running.remove(&id) {
+                    let name = self.components[stopped.component].name;
+                    match result {
+                        Ok(_) => tracing::info!(component = name, "component stopped"),
+                        Err(err) => tracing::error!(
+                            component = name,
+                            ?err,
+                            "component failed while stopping"
+                        ),
+                    }
+                }
+            }
+        })
+        .await;
+
+        if drained.is_err() {
+            running.retain(|_, r| {
+                let component = &self.components[r.component];
+                if component.stage != stage {
+                    return true;
+                }
+                tracing::warn!(
+                    component = component.name,
+                    "drain timed out, aborting component"
+                );
+                r.abort.abort();
+                false
+            });
+        }
+    }
+}
+
+/// Completes on Ctrl+C or, on Unix, SIGTERM.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
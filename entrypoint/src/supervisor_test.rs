@@ -0,0 +1,115 @@
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::supervisor::OnPanic;
+use crate::supervisor::Stage;
+use crate::supervisor::Supervisor;
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[tokio::test]
+async fn stops_stages_in_order() {
+    let stopped = Arc::new(Mutex::new(Vec::new()));
+    let mut supervisor = Supervisor::new(DRAIN_TIMEOUT);
+    for (name, stage) in [
+        ("relay", Stage::Outbox),
+        ("crons", Stage::Jobs),
+        ("consumer", Stage::Intake),
+    ] {
+        let stopped = Arc::clone(&stopped);
+        supervisor = supervisor.add(name, stage, OnPanic::Restart, move |shutdown| {
+            let stopped = Arc::clone(&stopped);
+            Box::pin(async move {
+                shutdown.cancelled().await;
+                stopped.lock().unwrap().push(name);
+            })
+        });
+    }
+
+    let exit_code = supervisor
+        .run(tokio::time::sleep(Duration::from_millis(20)))
+        .await;
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert_eq!(*stopped.lock().unwrap(), ["consumer", "crons", "relay"]);
+}
+
+#[tokio::test]
+async fn restarts_panicked_component() {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let restarted = Arc::new(Notify::new());
+
+    let supervisor =
+        Supervisor::new(DRAIN_TIMEOUT).add("consumer", Stage::Intake, OnPanic::Restart, {
+            let starts = Arc::clone(&starts);
+            let restarted = Arc::clone(&restarted);
+            move |shutdown| {
+                let start = starts.fetch_add(1, Ordering::SeqCst);
+                let restarted = Arc::clone(&restarted);
+                Box::pin(async move {
+                    if start == 0 {
+                        panic!("broker connection lost");
+                    }
+                    restarted.notify_one();
+                    shutdown.cancelled().await;
+                })
+            }
+        });
+
+    let exit_code = supervisor.run(restarted.notified()).await;
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert_eq!(starts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn exits_when_critical_component_panics() {
+    let relay_stopped = Arc::new(Notify::new());
+
+    let supervisor = Supervisor::new(DRAIN_TIMEOUT)
+        .add("http", Stage::Intake, OnPanic::Exit, |_| {
+            Box::pin(async { panic!("address in use") })
+        })
+        .add("relay", Stage::Outbox, OnPanic::Restart, {
+            let relay_stopped = Arc::clone(&relay_stopped);
+            move |shutdown| {
+                let relay_stopped = Arc::clone(&relay_stopped);
+                Box::pin(async move {
+                    shutdown.cancelled().await;
+                    relay_stopped.notify_one();
+                })
+            }
+        });
+
+    let exit_code = supervisor.run(std::future::pending()).await;
+
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    tokio::time::timeout(Duration::from_secs(1), relay_stopped.notified())
+        .await
+        .expect("the other components must still be stopped");
+}
+
+#[tokio::test]
+async fn aborts_component_that_outlives_drain_timeout() {
+    let supervisor = Supervisor::new(Duration::from_millis(50)).add(
+        "stuck",
+        Stage::Jobs,
+        OnPanic::Restart,
+        |_| Box::pin(std::future::pending()),
+    );
+
+    let exit_code = tokio::time::timeout(
+        Duration::from_secs(5),
+        supervisor.run(tokio::time::sleep(Duration::from_millis(10))),
+    )
+    .await
+    .expect("shutdown must not wait past the drain timeout");
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+}
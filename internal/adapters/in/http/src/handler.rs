@@ -1,87 +1,117 @@
 use application::errors::command_errors::CommandError;
 use application::errors::query_errors::QueryError;
 use application::usecases::CommandHandler;
+use application::usecases::commands::add_storage_place_command::AddStoragePlaceCommand;
+use application::usecases::commands::add_storage_place_handler::AddStoragePlaceHandler;
 use application::usecases::commands::create_courier_command::CreateCourierCommand;
 use application::usecases::commands::create_courier_handler::CreateCourierHandler;
 use application::usecases::commands::create_order_command::CreateOrderCommand;
 use application::usecases::commands::create_order_handler::CreateOrderHandler;
-use application::usecases::events::event_bus::EventBus;
+use application::usecases::commands::deactivate_courier_command::DeactivateCourierCommand;
+use application::usecases::commands::deactivate_courier_handler::DeactivateCourierHandler;
+use application::usecases::commands::remove_storage_place_command::RemoveStoragePlaceCommand;
+use application::usecases::commands::remove_storage_place_handler::RemoveStoragePlaceHandler;
+use application::usecases::commands::replay_outbox_command::ReplayOutboxCommand;
+use application::usecases::commands::replay_outbox_handler::ReplayOutboxHandler;
+use application::usecases::commands::update_courier_command::UpdateCourierCommand;
+use application::usecases::commands::update_courier_handler::UpdateCourierHandler;
 use application::usecases::queries::get_all_couriers_handler::GetAllCouriersHandler;
 use application::usecases::queries::get_all_couriers_query::GetAllCouriers;
 use application::usecases::queries::get_all_incomplete_orders_handler::GetAllIncompleteOrdersHandler;
 use application::usecases::queries::get_all_incomplete_orders_query::GetAllIncompleteOrders;
+use application::usecases::queries::get_courier_handler::GetCourierHandler;
+use application::usecases::queries::get_courier_query::GetCourier;
+use application::usecases::queries::get_order_handler::GetOrderHandler;
+use application::usecases::queries::get_order_query::GetOrder;
+use application::usecases::queries::list_orders_handler::ListOrdersHandler;
+use application::usecases::queries::list_orders_query::ListOrders;
 use async_trait::async_trait;
 use axum::http::Method;
 use axum_extra::extract::CookieJar;
 use axum_extra::extract::Host;
+use chrono::DateTime;
+use chrono::Utc;
+use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::courier::courier_aggregate::CourierName;
 use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::courier::storage_place::StoragePlace;
+use domain::model::kernel::location::Coordinates;
+use domain::model::kernel::location::Location;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
 use openapi::apis::ErrorHandler;
+use openapi::apis::default::AddStoragePlaceResponse;
 use openapi::apis::default::CreateCourierResponse;
 use openapi::apis::default::CreateOrderResponse;
+use openapi::apis::default::DeactivateCourierResponse;
 use openapi::apis::default::Default as DefaultApi;
+use openapi::apis::default::GetCourierResponse;
 use openapi::apis::default::GetCouriersResponse;
+use openapi::apis::default::GetOrderResponse;
 use openapi::apis::default::GetOrdersResponse;
+use openapi::apis::default::ListOrdersResponse;
+use openapi::apis::default::RemoveStoragePlaceResponse;
+use openapi::apis::default::ReplayOutboxResponse;
+use openapi::apis::default::UpdateCourierResponse;
 use openapi::models;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::geo_service_port::GeoServicePort;
+use ports::order_repository_port::OrderFilter;
 use ports::order_repository_port::OrderRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 use crate::state::AppState;
 
-pub struct ServerImpl<CR, OR, UOW, GS, EB>
+pub struct ServerImpl<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
 {
-    state: Arc<AppState<CR, OR, UOW, GS, EB>>,
+    state: Arc<AppState<CR, OR, UOW, GS>>,
 }
 
-impl<CR, OR, UOW, GS, EB> ServerImpl<CR, OR, UOW, GS, EB>
+impl<CR, OR, UOW, GS> ServerImpl<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
 {
-    pub fn new(state: Arc<AppState<CR, OR, UOW, GS, EB>>) -> Self {
+    pub fn new(state: Arc<AppState<CR, OR, UOW, GS>>) -> Self {
         Self { state }
     }
 
-    fn state(&self) -> &AppState<CR, OR, UOW, GS, EB> {
+    fn state(&self) -> &AppState<CR, OR, UOW, GS> {
         self.state.as_ref()
     }
 }
 
 #[async_trait]
-impl<CR, OR, UOW, GS, EB, E> ErrorHandler<E> for ServerImpl<CR, OR, UOW, GS, EB>
+impl<CR, OR, UOW, GS, E> ErrorHandler<E> for ServerImpl<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
     E: Send + Sync + Debug + 'static,
 {
 }
 
 #[allow(unused_variables)]
 #[async_trait]
-impl<CR, OR, UOW, GS, E, EB> DefaultApi<E> for ServerImpl<CR, OR, UOW, GS, EB>
+impl<CR, OR, UOW, GS, E> DefaultApi<E> for ServerImpl<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
     E: Debug + Send + Sync + 'static,
 {
     async fn create_courier(
@@ -92,7 +122,7 @@ where
         body: &Option<models::NewCourier>,
     ) -> Result<CreateCourierResponse, E> {
         let repo = self.state().courier_repo();
-        let mut handler = CreateCourierHandler::new(repo);
+        let mut handler = CreateCourierHandler::new(repo, self.state().coordinate_system());
 
         let command = match body {
             Some(b) => {
@@ -126,8 +156,9 @@ where
             Ok(_) => Ok(CreateCourierResponse::Status201),
             Err(err) => {
                 let code = match &err {
-                    CommandError::ArgumentError(_) => 400,
-                    CommandError::ExecutionError(_) => 409,
+                    CommandError::ArgumentError(_) | CommandError::DomainError(_) => 400,
+                    CommandError::ExecutionError(_) | CommandError::Conflict(_) => 409,
+                    CommandError::NotFound(_) => 404,
                 };
 
                 Ok(CreateCourierResponse::Status409(models::Error {
@@ -144,27 +175,29 @@ where
         host: &Host,
         cookies: &CookieJar,
     ) -> Result<CreateOrderResponse, E> {
-        let repo = self.state().order_repo();
+        let uow = self.state().unit_of_work();
         let geo_service = self.state().geo_service();
-        let event_bus = self.state().order_event_bus();
-        let mut handler = CreateOrderHandler::new(repo, geo_service, event_bus);
+        let mut handler = CreateOrderHandler::new(uow, geo_service, None);
 
-        let command = match CreateOrderCommand::new(Uuid::new_v4(), "Unknown street".into(), 5) {
-            Ok(cmd) => cmd,
-            Err(err) => {
-                return Ok(CreateOrderResponse::Status0(models::Error {
-                    message: err.to_string(),
-                    code: 400,
-                }));
-            }
-        };
+        let command =
+            match CreateOrderCommand::new(Uuid::new_v4(), "Unknown street".into(), 5, None) {
+                Ok(cmd) => cmd,
+                Err(err) => {
+                    return Ok(CreateOrderResponse::Status0(models::Error {
+                        message: err.to_string(),
+                        code: 400,
+                    }));
+                }
+            };
 
         match handler.execute(command).await {
             Ok(_) => Ok(CreateOrderResponse::Status201),
             Err(err) => {
                 let code = match &err {
-                    CommandError::ArgumentError(_) => 400,
+                    CommandError::ArgumentError(_) | CommandError::DomainError(_) => 400,
                     CommandError::ExecutionError(_) => 500,
+                    CommandError::NotFound(_) => 404,
+                    CommandError::Conflict(_) => 409,
                 };
 
                 Ok(CreateOrderResponse::Status0(models::Error {
@@ -193,10 +226,7 @@ where
                     .map(|c| models::Courier {
                         id: c.id.0,
                         name: c.name.0.clone(),
-                        location: models::Location {
-                            x: c.location.x() as u32,
-                            y: c.location.y() as u32,
-                        },
+                        location: to_api_location(&c.location),
                     })
                     .collect(),
             )),
@@ -204,6 +234,7 @@ where
                 let code = match &err {
                     QueryError::ArgumentError(_) => 400,
                     QueryError::ExecutionError(_) => 500,
+                    QueryError::NotFound(_) => 404,
                 };
 
                 Ok(GetCouriersResponse::Status0(models::Error {
@@ -214,6 +245,257 @@ where
         }
     }
 
+    async fn get_courier(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::GetCourierPathParams,
+    ) -> Result<GetCourierResponse, E> {
+        let mut handler =
+            GetCourierHandler::new(self.state().courier_repo(), self.state().order_repo());
+        let query = GetCourier {
+            courier_id: CourierId(path_params.courier_id),
+        };
+
+        match handler.execute(query).await {
+            Ok(details) => {
+                let courier = details.courier;
+                Ok(GetCourierResponse::Status200(models::CourierDetails {
+                    id: courier.id().0,
+                    name: courier.name().clone(),
+                    speed: u32::from(*courier.speed()),
+                    status: courier.status().into(),
+                    location: to_api_location(courier.location()),
+                    storage_places: courier
+                        .storage_places()
+                        .iter()
+                        .map(to_api_storage_place)
+                        .collect(),
+                    orders: details
+                        .orders
+                        .iter()
+                        .map(|order| models::Order {
+                            id: order.id().0,
+                            location: to_api_location(order.location()),
+                        })
+                        .collect(),
+                }))
+            }
+            Err(QueryError::NotFound(message)) => {
+                Ok(GetCourierResponse::Status404(models::Error {
+                    message,
+                    code: 404,
+                }))
+            }
+            Err(err) => {
+                let code = match &err {
+                    QueryError::ArgumentError(_) => 400,
+                    _ => 500,
+                };
+
+                Ok(GetCourierResponse::Status0(models::Error {
+                    message: err.to_string(),
+                    code,
+                }))
+            }
+        }
+    }
+
+    async fn update_courier(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::UpdateCourierPathParams,
+        body: &Option<models::UpdateCourier>,
+    ) -> Result<UpdateCourierResponse, E> {
+        let mut handler = UpdateCourierHandler::new(self.state().courier_repo());
+
+        let command = match UpdateCourierCommand::new(
+            path_params.courier_id,
+            body.as_ref().and_then(|b| b.name.clone()).map(CourierName),
+            body.as_ref()
+                .and_then(|b| b.speed)
+                .map(|speed| CourierSpeed(speed as u8)),
+        ) {
+            Ok(cmd) => cmd,
+            Err(err) => {
+                return Ok(UpdateCourierResponse::Status400(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+
+        match handler.execute(command).await {
+            Ok(_) => Ok(UpdateCourierResponse::Status204),
+            Err(err) => Ok(match err {
+                CommandError::ArgumentError(_) | CommandError::DomainError(_) => {
+                    UpdateCourierResponse::Status400(models::Error {
+                        message: err.to_string(),
+                        code: 400,
+                    })
+                }
+                CommandError::NotFound(_) => UpdateCourierResponse::Status404(models::Error {
+                    message: err.to_string(),
+                    code: 404,
+                }),
+                CommandError::ExecutionError(_) | CommandError::Conflict(_) => {
+                    UpdateCourierResponse::Status409(models::Error {
+                        message: err.to_string(),
+                        code: 409,
+                    })
+                }
+            }),
+        }
+    }
+
+    async fn add_storage_place(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::AddStoragePlacePathParams,
+        body: &Option<models::NewStoragePlace>,
+    ) -> Result<AddStoragePlaceResponse, E> {
+        let mut handler = AddStoragePlaceHandler::new(self.state().courier_repo());
+
+        let Some(body) = body else {
+            return Ok(AddStoragePlaceResponse::Status400(models::Error {
+                message: "storage place is required".to_string(),
+                code: 400,
+            }));
+        };
+        let command = match u16::try_from(body.total_volume)
+            .map_err(|_| CommandError::ArgumentError("total_volume is too large".to_string()))
+            .and_then(|volume| {
+                AddStoragePlaceCommand::new(path_params.courier_id, body.name.clone(), volume)
+            }) {
+            Ok(cmd) => cmd,
+            Err(err) => {
+                return Ok(AddStoragePlaceResponse::Status400(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+
+        match handler.execute(command).await {
+            Ok(storage_place_id) => Ok(AddStoragePlaceResponse::Status201(models::StoragePlace {
+                id: storage_place_id,
+                name: body.name.clone(),
+                total_volume: body.total_volume,
+                order_id: None,
+            })),
+            Err(err) => Ok(match err {
+                CommandError::ArgumentError(_) | CommandError::DomainError(_) => {
+                    AddStoragePlaceResponse::Status400(models::Error {
+                        message: err.to_string(),
+                        code: 400,
+                    })
+                }
+                CommandError::NotFound(_) => AddStoragePlaceResponse::Status404(models::Error {
+                    message: err.to_string(),
+                    code: 404,
+                }),
+                CommandError::ExecutionError(_) | CommandError::Conflict(_) => {
+                    AddStoragePlaceResponse::Status409(models::Error {
+                        message: err.to_string(),
+                        code: 409,
+                    })
+                }
+            }),
+        }
+    }
+
+    async fn remove_storage_place(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::RemoveStoragePlacePathParams,
+    ) -> Result<RemoveStoragePlaceResponse, E> {
+        let mut handler = RemoveStoragePlaceHandler::new(self.state().courier_repo());
+
+        let command = match RemoveStoragePlaceCommand::new(
+            path_params.courier_id,
+            path_params.storage_place_id,
+        ) {
+            Ok(cmd) => cmd,
+            Err(err) => {
+                return Ok(RemoveStoragePlaceResponse::Status0(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+
+        match handler.execute(command).await {
+            Ok(_) => Ok(RemoveStoragePlaceResponse::Status204),
+            Err(err) => Ok(match err {
+                CommandError::NotFound(_) => RemoveStoragePlaceResponse::Status404(models::Error {
+                    message: err.to_string(),
+                    code: 404,
+                }),
+                CommandError::ExecutionError(_) | CommandError::Conflict(_) => {
+                    RemoveStoragePlaceResponse::Status409(models::Error {
+                        message: err.to_string(),
+                        code: 409,
+                    })
+                }
+                CommandError::ArgumentError(_) | CommandError::DomainError(_) => {
+                    RemoveStoragePlaceResponse::Status0(models::Error {
+                        message: err.to_string(),
+                        code: 400,
+                    })
+                }
+            }),
+        }
+    }
+
+    async fn deactivate_courier(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::DeactivateCourierPathParams,
+    ) -> Result<DeactivateCourierResponse, E> {
+        let mut handler = DeactivateCourierHandler::new(self.state().courier_repo());
+
+        let command = match DeactivateCourierCommand::new(path_params.courier_id) {
+            Ok(cmd) => cmd,
+            Err(err) => {
+                return Ok(DeactivateCourierResponse::Status0(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+
+        match handler.execute(command).await {
+            Ok(_) => Ok(DeactivateCourierResponse::Status204),
+            Err(err) => Ok(match err {
+                CommandError::NotFound(_) => DeactivateCourierResponse::Status404(models::Error {
+                    message: err.to_string(),
+                    code: 404,
+                }),
+                CommandError::ExecutionError(_) | CommandError::Conflict(_) => {
+                    DeactivateCourierResponse::Status409(models::Error {
+                        message: err.to_string(),
+                        code: 409,
+                    })
+                }
+                CommandError::ArgumentError(_) | CommandError::DomainError(_) => {
+                    DeactivateCourierResponse::Status0(models::Error {
+                        message: err.to_string(),
+                        code: 400,
+                    })
+                }
+            }),
+        }
+    }
+
     async fn get_orders(
         &self,
         method: &Method,
@@ -229,10 +511,7 @@ where
                     .into_iter()
                     .map(|order| models::Order {
                         id: order.id().0,
-                        location: models::Location {
-                            x: order.location().x() as u32,
-                            y: order.location().y() as u32,
-                        },
+                        location: to_api_location(order.location()),
                     })
                     .collect();
                 Ok(GetOrdersResponse::Status200(orders))
@@ -243,4 +522,183 @@ where
             })),
         }
     }
+
+    async fn get_order(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        path_params: &models::GetOrderPathParams,
+    ) -> Result<GetOrderResponse, E> {
+        let mut handler = GetOrderHandler::new(self.state().order_repo());
+        let query = GetOrder {
+            order_id: OrderId::new(path_params.order_id),
+        };
+
+        match handler.execute(query).await {
+            Ok(details) => {
+                let order = details.order;
+                Ok(GetOrderResponse::Status200(models::OrderDetails {
+                    id: order.id().0,
+                    status: order.status().into(),
+                    courier_id: order.courier_id().map(|courier_id| courier_id.0),
+                    volume: u32::from(order.volume()),
+                    location: to_api_location(order.location()),
+                    pickup_location: order.pickup_location().map(to_api_location),
+                    created_at: to_api_time(order.created_at()),
+                    history: details
+                        .history
+                        .iter()
+                        .map(|entry| models::OrderHistoryEntry {
+                            status: (&entry.status).into(),
+                            courier_id: entry.courier_id.map(|courier_id| courier_id.0),
+                            occurred_at: to_api_time(entry.occurred_at),
+                        })
+                        .collect(),
+                }))
+            }
+            Err(QueryError::NotFound(message)) => Ok(GetOrderResponse::Status404(models::Error {
+                message,
+                code: 404,
+            })),
+            Err(err) => {
+                let code = match &err {
+                    QueryError::ArgumentError(_) => 400,
+                    _ => 500,
+                };
+
+                Ok(GetOrderResponse::Status0(models::Error {
+                    message: err.to_string(),
+                    code,
+                }))
+            }
+        }
+    }
+
+    async fn list_orders(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        query_params: &models::ListOrdersQueryParams,
+    ) -> Result<ListOrdersResponse, E> {
+        let statuses = match query_params
+            .status
+            .iter()
+            .flatten()
+            .map(|status| OrderStatus::try_from(status.as_str()))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(statuses) => statuses,
+            Err(message) => {
+                return Ok(ListOrdersResponse::Status400(models::Error {
+                    message,
+                    code: 400,
+                }));
+            }
+        };
+        let filter = OrderFilter {
+            statuses,
+            courier_id: query_params.courier_id.map(CourierId),
+            created_from: query_params.created_from.map(SystemTime::from),
+            created_before: query_params.created_to.map(SystemTime::from),
+        };
+        let query = match ListOrders::new(filter, query_params.limit, query_params.offset) {
+            Ok(query) => query,
+            Err(err) => {
+                return Ok(ListOrdersResponse::Status400(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+        let (limit, offset) = (query.limit(), query.offset());
+
+        let mut handler = ListOrdersHandler::new(self.state().order_repo());
+        match handler.execute(query).await {
+            Ok(page) => Ok(ListOrdersResponse::Status200(models::OrdersPage {
+                items: page.orders.iter().map(to_api_order_summary).collect(),
+                total: page.total,
+                limit,
+                offset,
+            })),
+            Err(err) => Ok(ListOrdersResponse::Status0(models::Error {
+                message: err.to_string(),
+                code: 500,
+            })),
+        }
+    }
+
+    async fn replay_outbox(
+        &self,
+        method: &Method,
+        host: &Host,
+        cookies: &CookieJar,
+        body: &models::OutboxReplayRange,
+    ) -> Result<ReplayOutboxResponse, E> {
+        let command = match ReplayOutboxCommand::new(
+            SystemTime::from(body.from),
+            SystemTime::from(body.to),
+        ) {
+            Ok(command) => command,
+            Err(err) => {
+                return Ok(ReplayOutboxResponse::Status400(models::Error {
+                    message: err.to_string(),
+                    code: 400,
+                }));
+            }
+        };
+
+        let mut handler = ReplayOutboxHandler::new(self.state().unit_of_work());
+        match handler.execute(command).await {
+            Ok(replayed) => Ok(ReplayOutboxResponse::Status200(
+                models::OutboxReplayResult { replayed },
+            )),
+            Err(err) => Ok(ReplayOutboxResponse::Status0(models::Error {
+                message: err.to_string(),
+                code: 500,
+            })),
+        }
+    }
+}
+
+/// Grid locations fill `x` and `y`; geographic ones leave them at zero and
+/// fill `latitude` and `longitude`.
+fn to_api_location(location: &Location) -> models::Location {
+    match location.coordinates() {
+        Coordinates::Grid { x, y } => models::Location::new(x.into(), y.into()),
+        Coordinates::Geographic {
+            latitude,
+            longitude,
+        } => models::Location {
+            x: 0,
+            y: 0,
+            latitude: Some(latitude),
+            longitude: Some(longitude),
+        },
+    }
+}
+
+fn to_api_storage_place(storage_place: &StoragePlace) -> models::StoragePlace {
+    models::StoragePlace {
+        id: *storage_place.id(),
+        name: storage_place.name().to_string(),
+        total_volume: u32::from(storage_place.total_volume()),
+        order_id: storage_place.order_id().map(|order_id| order_id.0),
+    }
+}
+
+fn to_api_order_summary(order: &Order) -> models::OrderSummary {
+    models::OrderSummary {
+        id: order.id().0,
+        status: order.status().into(),
+        courier_id: order.courier_id().map(|courier_id| courier_id.0),
+        volume: u32::from(order.volume()),
+        location: to_api_location(order.location()),
+        created_at: to_api_time(order.created_at()),
+    }
+}
+
+fn to_api_time(time: SystemTime) -> DateTime<Utc> {
+    DateTime::<Utc>::from(time)
 }
@@ -1,16 +1,20 @@
-use application::usecases::events::event_bus::EventBus;
 use domain::model::courier::courier_aggregate::Courier;
 use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::kernel::coordinate_system::CoordinateSystem;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::courier_repository_port::GetAllCouriersResponse;
 use ports::errors::RepositoryError;
 use ports::geo_service_port::GeoServicePort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
 use ports::order_repository_port::OrderRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::PoisonError;
 use tokio::sync::Mutex as AsyncMutex;
 
 pub struct Shared<T> {
@@ -64,6 +68,10 @@ where
         self.with_lock("courier repository", |inner| inner.get_all_free())
     }
 
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        self.with_lock("courier repository", |inner| inner.get_all_scheduled())
+    }
+
     fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
         self.with_lock("courier repository", |inner| inner.get_all_couriers())
     }
@@ -93,47 +101,71 @@ where
         self.with_lock("order repository", |inner| inner.get_all_assigned())
     }
 
-    fn raw(&mut self, query: String) -> Result<Vec<Order>, RepositoryError> {
-        self.with_lock("order repository", |inner| inner.raw(query))
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        self.with_lock("order repository", |inner| inner.find(criteria))
+    }
+
+    fn count(&mut self, filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        self.with_lock("order repository", |inner| inner.count(filter))
+    }
+
+    fn get_history(&mut self, id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        self.with_lock("order repository", |inner| inner.get_history(id))
     }
 }
 
-pub struct AppState<CR, OR, UOW, GS, EB>
+pub struct AppState<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
 {
     courier_repo: Shared<CR>,
     order_repo: Shared<OR>,
     uow: Arc<Mutex<UOW>>,
     geo_service: GS,
-    order_event_bus: EB,
+    coordinate_system: CoordinateSystem,
+}
+
+impl<CR, OR, UOW, GS> Clone for AppState<CR, OR, UOW, GS>
+where
+    CR: CourierRepositoryPort + Send + 'static,
+    OR: OrderRepositoryPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
+    GS: GeoServicePort + Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            courier_repo: self.courier_repo.clone(),
+            order_repo: self.order_repo.clone(),
+            uow: Arc::clone(&self.uow),
+            geo_service: self.geo_service.clone(),
+            coordinate_system: self.coordinate_system,
+        }
+    }
 }
 
-impl<CR, OR, UOW, GS, EB> AppState<CR, OR, UOW, GS, EB>
+impl<CR, OR, UOW, GS> AppState<CR, OR, UOW, GS>
 where
     CR: CourierRepositoryPort + Send + 'static,
     OR: OrderRepositoryPort + Send + 'static,
-    UOW: UnitOfWorkPort + Send + 'static,
+    UOW: UnitOfWorkPort + Clone + Send + 'static,
     GS: GeoServicePort + Clone + Send + Sync + 'static,
-    EB: EventBus + 'static,
 {
     pub fn new(
         courier_repo: CR,
         order_repo: OR,
         uow: UOW,
         geo_service: GS,
-        order_event_bus: EB,
+        coordinate_system: CoordinateSystem,
     ) -> Self {
         Self {
             courier_repo: Shared::new(courier_repo),
             order_repo: Shared::new(order_repo),
             uow: Arc::new(Mutex::new(uow)),
             geo_service,
-            order_event_bus,
+            coordinate_system,
         }
     }
 
@@ -145,16 +177,20 @@ where
         self.order_repo.clone()
     }
 
-    pub fn unit_of_work(&self) -> Arc<Mutex<UOW>> {
-        Arc::clone(&self.uow)
+    /// A fresh unit of work per request, cloned from the shared one.
+    pub fn unit_of_work(&self) -> UOW {
+        self.uow
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
     }
 
     pub fn geo_service(&self) -> GS {
         self.geo_service.clone()
     }
 
-    pub fn order_event_bus(&self) -> EB {
-        self.order_event_bus.clone()
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.coordinate_system
     }
 }
 
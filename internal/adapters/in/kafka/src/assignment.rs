@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use rdkafka::ClientContext;
+use rdkafka::Offset;
+use rdkafka::TopicPartitionList;
+use rdkafka::consumer::BaseConsumer;
+use rdkafka::consumer::Consumer;
+use rdkafka::consumer::ConsumerContext;
+use rdkafka::consumer::Rebalance;
+use tokio::sync::mpsc;
+use tracing::info;
+use tracing::warn;
+
+pub type TopicPartition = (String, i32);
+
+/// Partitions this consumer owns, with the offset to resume each from.
+/// Offsets are committed only while the partition is owned, so nothing is
+/// committed for a partition after it was handed to another consumer.
+#[derive(Debug, Clone, Default)]
+pub struct PartitionAssignment {
+    owned: Arc<Mutex<HashMap<TopicPartition, Option<i64>>>>,
+}
+
+impl PartitionAssignment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn assign(&self, partitions: &TopicPartitionList) {
+        let mut owned = self.lock();
+        for key in keys(partitions) {
+            owned.entry(key).or_insert(None);
+        }
+    }
+
+    /// Returns the partitions that were owned until now.
+    pub fn revoke(&self, partitions: &TopicPartitionList) -> Vec<TopicPartition> {
+        let mut owned = self.lock();
+        keys(partitions)
+            .into_iter()
+            .filter(|key| owned.remove(key).is_some())
+            .collect()
+    }
+
+    pub fn revoke_all(&self) -> Vec<TopicPartition> {
+        self.lock().drain().map(|(key, _)| key).collect()
+    }
+
+    /// Records `next_offset` as where the partition resumes from and hands it
+    /// to `commit`, unless the partition is no longer owned. Revocation waits
+    /// until `commit` returns.
+    pub fn commit<F>(&self, topic: &str, partition: i32, next_offset: i64, commit: F) -> bool
+    where
+        F: FnOnce(&TopicPartitionList),
+    {
+        let mut owned = self.lock();
+        let Some(resume_from) = owned.get_mut(&(topic.to_string(), partition)) else {
+            return false;
+        };
+
+        let mut offsets = TopicPartitionList::new();
+        if let Err(e) = offsets.add_partition_offset(topic, partition, Offset::Offset(next_offset))
+        {
+            warn!("could not build offset to commit: {:?}", e);
+            return false;
+        }
+
+        *resume_from = Some(next_offset);
+        commit(&offsets);
+        true
+    }
+
+    /// The offsets recorded by `commit` for the partitions still owned.
+    pub fn committed(&self) -> TopicPartitionList {
+        let mut offsets = TopicPartitionList::new();
+        for ((topic, partition), resume_from) in self.lock().iter() {
+            if let Some(offset) = resume_from
+                && let Err(e) =
+                    offsets.add_partition_offset(topic, *partition, Offset::Offset(*offset))
+            {
+                warn!("could not build offset to commit: {:?}", e);
+            }
+        }
+        offsets
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<TopicPartition, Option<i64>>> {
+        self.owned.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn keys(partitions: &TopicPartitionList) -> Vec<TopicPartition> {
+    partitions
+        .elements()
+        .iter()
+        .map(|elem| (elem.topic().to_string(), elem.partition()))
+        .collect()
+}
+
+/// Keeps a `PartitionAssignment` in step with the consumer group and reports
+/// revoked partitions, so the consume loop can stop their workers.
+pub struct AssignmentContext {
+    assignment: PartitionAssignment,
+    revoked: mpsc::UnboundedSender<TopicPartition>,
+}
+
+impl AssignmentContext {
+    pub fn new(assignment: PartitionAssignment) -> (Self, mpsc::UnboundedReceiver<TopicPartition>) {
+        let (revoked, revocations) = mpsc::unbounded_channel();
+        (
+            Self {
+                assignment,
+                revoked,
+            },
+            revocations,
+        )
+    }
+
+    fn report(&self, revoked: Vec<TopicPartition>) {
+        if revoked.is_empty() {
+            return;
+        }
+
+        info!(?revoked, "partitions revoked");
+        for key in revoked {
+            let _ = self.revoked.send(key);
+        }
+    }
+}
+
+impl ClientContext for AssignmentContext {}
+
+impl ConsumerContext for AssignmentContext {
+    fn pre_rebalance(&self, _: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+        match rebalance {
+            Rebalance::Revoke(partitions) => self.report(self.assignment.revoke(partitions)),
+            // The whole assignment is dropped after a failed rebalance.
+            Rebalance::Error(e) => {
+                warn!("rebalance failed: {}", e);
+                self.report(self.assignment.revoke_all());
+            }
+            Rebalance::Assign(_) => {}
+        }
+    }
+
+    fn post_rebalance(&self, base_consumer: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+        if let Rebalance::Assign(partitions) = rebalance {
+            info!(assigned = partitions.count(), "partitions assigned");
+            self.assignment.assign(partitions);
+            // A partition halted before it was revoked starts over.
+            if let Err(e) = base_consumer.resume(partitions) {
+                warn!("could not resume assigned partitions: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use rdkafka::Offset;
+use rdkafka::TopicPartitionList;
+
+use crate::assignment::PartitionAssignment;
+
+fn partitions(keys: &[(&str, i32)]) -> TopicPartitionList {
+    let mut list = TopicPartitionList::new();
+    for (topic, partition) in keys {
+        list.add_partition(topic, *partition);
+    }
+    list
+}
+
+fn commits(assignment: &PartitionAssignment, topic: &str, partition: i32, offset: i64) -> bool {
+    assignment.commit(topic, partition, offset, |offsets| {
+        assert_eq!(
+            offsets.find_partition(topic, partition).unwrap().offset(),
+            Offset::Offset(offset)
+        );
+    })
+}
+
+#[test]
+fn commits_owned_partitions_only() {
+    let assignment = PartitionAssignment::new();
+    assignment.assign(&partitions(&[("basket.confirmed", 0)]));
+
+    assert!(commits(&assignment, "basket.confirmed", 0, 11));
+    assert!(!commits(&assignment, "basket.confirmed", 1, 11));
+    assert!(!commits(&assignment, "basket.cancelled", 0, 11));
+}
+
+#[test]
+fn stops_committing_revoked_partitions() {
+    let assignment = PartitionAssignment::new();
+    assignment.assign(&partitions(&[
+        ("basket.confirmed", 0),
+        ("basket.confirmed", 1),
+    ]));
+
+    let revoked = assignment.revoke(&partitions(&[
+        ("basket.confirmed", 1),
+        ("basket.confirmed", 2),
+    ]));
+
+    assert_eq!(revoked, vec![("basket.confirmed".to_string(), 1)]);
+    assert!(commits(&assignment, "basket.confirmed", 0, 5));
+    assert!(!commits(&assignment, "basket.confirmed", 1, 5));
+}
+
+#[test]
+fn lists_last_commits_of_owned_partitions() {
+    let assignment = PartitionAssignment::new();
+    assignment.assign(&partitions(&[
+        ("basket.confirmed", 0),
+        ("basket.confirmed", 1),
+        ("basket.cancelled", 0),
+    ]));
+    commits(&assignment, "basket.confirmed", 0, 3);
+    commits(&assignment, "basket.confirmed", 0, 4);
+    commits(&assignment, "basket.cancelled", 0, 9);
+    assignment.revoke(&partitions(&[("basket.cancelled", 0)]));
+
+    let committed = assignment.committed();
+
+    assert_eq!(committed.count(), 1);
+    assert_eq!(
+        committed
+            .find_partition("basket.confirmed", 0)
+            .unwrap()
+            .offset(),
+        Offset::Offset(4)
+    );
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use crate::mapper::BasketEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasketTopicHandler {
+    Confirmed,
+    Cancelled,
+}
+
+impl BasketTopicHandler {
+    fn accepts(&self, event: &BasketEvent) -> bool {
+        matches!(
+            (self, event),
+            (Self::Confirmed, BasketEvent::Confirmed(_))
+                | (Self::Cancelled, BasketEvent::Cancelled(_))
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BasketTopics {
+    routes: HashMap<String, Vec<BasketTopicHandler>>,
+}
+
+impl BasketTopics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same topic may be subscribed several times to serve mixed streams.
+    pub fn subscribe(mut self, topic: &str, handler: BasketTopicHandler) -> Self {
+        let handlers = self.routes.entry(topic.to_string()).or_default();
+        if !handlers.contains(&handler) {
+            handlers.push(handler);
+        }
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.routes.keys().map(String::as_str).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub(crate) fn accepts(&self, topic: &str, event: &BasketEvent) -> bool {
+        self.routes
+            .get(topic)
+            .is_some_and(|handlers| handlers.iter().any(|h| h.accepts(event)))
+    }
+}
@@ -0,0 +1,45 @@
+use application::usecases::commands::cancel_order_command::CancelOrderCommand;
+use application::usecases::commands::create_order_command::CreateOrderCommand;
+use uuid::Uuid;
+
+use crate::basket_topics::BasketTopicHandler;
+use crate::basket_topics::BasketTopics;
+use crate::mapper::BasketEvent;
+
+fn confirmed() -> BasketEvent {
+    BasketEvent::Confirmed(
+        CreateOrderCommand::new(Uuid::new_v4(), "Tverskaya".into(), 5, None).unwrap(),
+    )
+}
+
+fn cancelled() -> BasketEvent {
+    BasketEvent::Cancelled(CancelOrderCommand::new(Uuid::new_v4()).unwrap())
+}
+
+#[test]
+fn routes_events_to_their_topic_handler() {
+    let topics = BasketTopics::new()
+        .subscribe("basket.confirmed", BasketTopicHandler::Confirmed)
+        .subscribe("basket.cancelled", BasketTopicHandler::Cancelled);
+
+    let mut names = topics.names();
+    names.sort();
+    assert_eq!(names, vec!["basket.cancelled", "basket.confirmed"]);
+
+    assert!(topics.accepts("basket.confirmed", &confirmed()));
+    assert!(!topics.accepts("basket.confirmed", &cancelled()));
+    assert!(topics.accepts("basket.cancelled", &cancelled()));
+    assert!(!topics.accepts("basket.cancelled", &confirmed()));
+    assert!(!topics.accepts("baskets.events", &confirmed()));
+}
+
+#[test]
+fn shared_topic_accepts_every_subscribed_handler() {
+    let topics = BasketTopics::new()
+        .subscribe("baskets.events", BasketTopicHandler::Confirmed)
+        .subscribe("baskets.events", BasketTopicHandler::Cancelled);
+
+    assert_eq!(topics.names(), vec!["baskets.events"]);
+    assert!(topics.accepts("baskets.events", &confirmed()));
+    assert!(topics.accepts("baskets.events", &cancelled()));
+}
@@ -1,64 +1,88 @@
-use crate::mapper::BasketEventPayload;
-use crate::shared::Shared;
+use crate::assignment::AssignmentContext;
+use crate::assignment::PartitionAssignment;
+use crate::assignment::TopicPartition;
+use crate::basket_topics::BasketTopics;
+use crate::consumer_options::KafkaConsumerOptions;
+use crate::dead_letter::DeadLetterProducer;
+use crate::errors::ConsumerError;
+use crate::mapper::BasketEvent;
+use crate::mapper::CONTENT_TYPE_HEADER;
+use crate::mapper::ContentType;
+use crate::partition_workers::Dispatch;
+use crate::partition_workers::PartitionWorkers;
+use crate::partition_workers::panic_message;
+use crate::retry::ConsumerFailure;
+use crate::retry::ConsumerRetryPolicy;
+use crate::retry::retry_transient;
+use application::errors::command_errors::CommandError;
 use application::usecases::CommandHandler;
+use application::usecases::commands::cancel_order_command::CancelOrderCommand;
+use application::usecases::commands::cancel_order_handler::CancelOrderHandler;
 use application::usecases::commands::create_order_command::CreateOrderCommand;
 use application::usecases::commands::create_order_handler::CreateOrderHandler;
-use application::usecases::events::event_bus::EventBus;
+use futures::FutureExt;
 use ports::geo_service_port::GeoServicePort;
-use ports::order_repository_port::OrderRepositoryPort;
-use rdkafka::ClientConfig;
+use ports::unit_of_work_port::UnitOfWorkPort;
 use rdkafka::Message;
+use rdkafka::TopicPartitionList;
+use rdkafka::consumer::CommitMode;
 use rdkafka::consumer::Consumer;
 use rdkafka::consumer::StreamConsumer;
-use std::str::FromStr;
+use rdkafka::error::KafkaError;
+use rdkafka::message::Headers;
+use rdkafka::message::OwnedMessage;
+use std::fmt::Debug;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tracing::Level;
 use tracing::debug;
+use tracing::error;
 use tracing::event;
-use tracing::info;
 use tracing::span;
 use tracing::warn;
 
-static TOPIC: [&str; 1] = ["baskets.events"];
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 4;
 
-pub struct BasketEventsConsumer<OR, GS, EB>
+pub struct BasketEventsConsumer<UOW, GS>
 where
-    OR: OrderRepositoryPort,
+    UOW: UnitOfWorkPort + Debug + Clone,
     GS: GeoServicePort + Clone,
-    EB: EventBus,
 {
-    consumer: StreamConsumer,
-    order_repo: Shared<OR>,
-    geo_service: GS,
-    event_bus: EB,
+    consumer: Arc<StreamConsumer<AssignmentContext>>,
+    assignment: PartitionAssignment,
+    revocations: mpsc::UnboundedReceiver<TopicPartition>,
+    processor: BasketEventProcessor<UOW, GS>,
+    max_in_flight: usize,
 }
 
-impl<OR, GS, EB> BasketEventsConsumer<OR, GS, EB>
+impl<UOW, GS> BasketEventsConsumer<UOW, GS>
 where
-    OR: OrderRepositoryPort,
-    GS: GeoServicePort + Clone,
-    EB: EventBus,
+    UOW: UnitOfWorkPort + Debug + Clone + Send + 'static,
+    GS: GeoServicePort + Clone + 'static,
 {
     pub fn new(
-        brokers: &str,
-        group_id: &str,
-        order_repo: Shared<OR>,
+        options: &KafkaConsumerOptions,
+        topics: BasketTopics,
+        uow: UOW,
         geo_service: GS,
-        event_bus: EB,
+        pickup_street: Option<String>,
+        dead_letter: DeadLetterProducer,
     ) -> Self {
-        let mut config = ClientConfig::new();
-
-        let consumer: StreamConsumer = config
-            .set("group.id", group_id)
-            .set("bootstrap.servers", brokers)
-            .set("enable.partition.eof", "false")
-            .set("session.timeout.ms", "6000")
-            .create()
+        let assignment = PartitionAssignment::new();
+        let (context, revocations) = AssignmentContext::new(assignment.clone());
+        let consumer: StreamConsumer<AssignmentContext> = options
+            .client_config()
+            .create_with_context(context)
             .expect("could not create consumer");
 
+        let names = topics.names();
         consumer
-            .subscribe(&TOPIC)
-            .unwrap_or_else(|e| panic!("could not subscribe to topic {:?}: {}", TOPIC, e));
+            .subscribe(&names)
+            .unwrap_or_else(|e| panic!("could not subscribe to topics {:?}: {}", names, e));
 
         consumer
             .client()
@@ -66,104 +90,328 @@ where
             .expect("kafka metadata fetch failed");
 
         Self {
-            consumer,
-            order_repo,
-            geo_service,
-            event_bus,
+            consumer: Arc::new(consumer),
+            assignment,
+            revocations,
+            processor: BasketEventProcessor::new(
+                topics,
+                uow,
+                geo_service,
+                pickup_street,
+                dead_letter,
+            ),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
         }
     }
 
-    pub async fn consume(&self) {
+    pub fn with_retry_policy(mut self, retry_policy: ConsumerRetryPolicy) -> Self {
+        self.processor = self.processor.with_retry_policy(retry_policy);
+        self
+    }
+
+    /// Caps how many messages, each from a different partition, are handled
+    /// at the same time.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Every message ends either handled or on the dead-letter topic before
+    /// its offset is committed, so a failure never blocks the partition for
+    /// good and is never silently dropped. When the dead-letter topic does not
+    /// take it either, the partition halts and stays paused, uncommitted,
+    /// until it is reassigned. Partitions are handled by their own tasks; each
+    /// commits its offsets once the message before them is done, for as long
+    /// as this consumer owns the partition.
+    ///
+    /// Once `shutdown` completes no new messages are taken, and the call
+    /// returns after the ones already received are handled.
+    pub async fn consume(self, shutdown: impl Future<Output = ()>) {
         let span = span!(Level::TRACE, "consumer");
         let _ = span.enter();
 
-        event!(Level::INFO, "consuming topic");
+        event!(
+            Level::INFO,
+            topics = ?self.processor.topics.names(),
+            max_in_flight = self.max_in_flight,
+            "consuming topics"
+        );
 
+        let consumer = Arc::clone(&self.consumer);
+        let assignment = self.assignment.clone();
+        let processor = Arc::new(self.processor);
+        let mut workers = PartitionWorkers::new(self.max_in_flight, move |msg: OwnedMessage| {
+            let consumer = Arc::clone(&consumer);
+            let assignment = assignment.clone();
+            let processor = Arc::clone(&processor);
+            async move {
+                processor.handle(&msg).await?;
+                commit(&consumer, &assignment, &msg);
+                Ok::<(), KafkaError>(())
+            }
+        });
+
+        let mut revocations = self.revocations;
+        tokio::pin!(shutdown);
         loop {
-            match self.consumer.recv().await {
-                Err(e) => warn!("could not consume message: {}", e),
-                Ok(msg) => {
-                    let payload = match msg.payload_view::<[u8]>() {
-                        None => continue,
-                        Some(Err(e)) => {
-                            warn!("error reading kafka payload: {:?}", e);
-                            continue;
-                        }
-                        Some(Ok(payload)) => {
-                            debug!("{:?}", payload);
-                            payload
-                        }
-                    };
-
-                    let event: BasketEventPayload = match serde_json::from_slice(payload) {
-                        Ok(event) => event,
-                        Err(err) => {
-                            warn!(?err, "failed to parse BasketConfirmedIntegrationEvent JSON");
-                            continue;
-                        }
-                    };
-
-                    info!(
-                        event_id = event.event_id,
-                        basket_id = event.basket_id,
-                        "received BasketConfirmedIntegrationEvent"
-                    );
-
-                    let address = match event.address {
-                        Some(address) => address,
-                        None => {
-                            warn!("event has no address");
-                            continue;
-                        }
-                    };
-
-                    let id = match uuid::Uuid::from_str(&event.basket_id) {
-                        Ok(id) => id,
-                        Err(err) => {
-                            warn!(?err, "event basket_id is not a valid UUID");
-                            continue;
-                        }
-                    };
-
-                    let volume = match u16::try_from(event.volume) {
-                        Ok(volume) => volume,
-                        Err(err) => {
-                            warn!(?err, "event volume is out of range for u16");
-                            continue;
-                        }
-                    };
-
-                    let command = match CreateOrderCommand::new(id, address.street, volume) {
-                        Ok(command) => command,
-                        Err(err) => {
-                            warn!(?err, "could not create command");
-                            continue;
-                        }
-                    };
-
-                    let mut handler = CreateOrderHandler::new(
-                        self.order_repo.clone(),
-                        self.geo_service.clone(),
-                        self.event_bus.clone(),
-                    );
-
-                    if let Err(err) = handler.execute(command).await {
-                        warn!(?err, "failed to handle BasketConfirmedIntegrationEvent");
-                        continue;
-                    }
-
-                    match self
-                        .consumer
-                        .commit_message(&msg, rdkafka::consumer::CommitMode::Async)
-                    {
-                        Ok(_) => continue,
-                        Err(e) => {
-                            warn!("could not commit message: {:?}", e);
-                            continue;
-                        }
-                    }
+            let received = tokio::select! {
+                biased;
+                _ = &mut shutdown => break,
+                Some((topic, partition)) = revocations.recv() => {
+                    workers.revoke(&topic, partition);
+                    continue;
                 }
+                received = self.consumer.recv() => received,
+            };
+
+            let msg = match received {
+                Ok(msg) => msg.detach(),
+                Err(e) => {
+                    warn!("could not consume message: {}", e);
+                    continue;
+                }
+            };
+
+            // Revocations reported while receiving come before the messages
+            // of the partitions assigned next.
+            while let Ok((topic, partition)) = revocations.try_recv() {
+                workers.revoke(&topic, partition);
             }
+
+            let topic = msg.topic().to_string();
+            let partition = msg.partition();
+            if workers.dispatch(&topic, partition, msg).await == Dispatch::Halted {
+                pause(&self.consumer, &topic, partition);
+            }
+        }
+
+        event!(Level::INFO, "draining basket consumer");
+        workers.drain().await;
+
+        // Commits above are asynchronous; repeat the last ones synchronously
+        // before the consumer closes.
+        let committed = self.assignment.committed();
+        if committed.count() > 0
+            && let Err(e) = self.consumer.commit(&committed, CommitMode::Sync)
+        {
+            warn!("could not commit final offsets: {:?}", e);
+        }
+    }
+}
+
+/// Commits the position after `msg`. Partition tasks handle messages in
+/// order, so everything before it is done as well.
+fn commit(
+    consumer: &StreamConsumer<AssignmentContext>,
+    assignment: &PartitionAssignment,
+    msg: &OwnedMessage,
+) {
+    let owned = assignment.commit(msg.topic(), msg.partition(), msg.offset() + 1, |offsets| {
+        if let Err(e) = consumer.commit(offsets, CommitMode::Async) {
+            warn!("could not commit message: {:?}", e);
         }
+    });
+
+    if !owned {
+        debug!(
+            topic = msg.topic(),
+            partition = msg.partition(),
+            "partition was revoked, not committing"
+        );
+    }
+}
+
+/// Stops fetching a halted partition; a later assignment resumes it.
+fn pause(consumer: &StreamConsumer<AssignmentContext>, topic: &str, partition: i32) {
+    let mut partitions = TopicPartitionList::new();
+    partitions.add_partition(topic, partition);
+
+    if let Err(e) = consumer.pause(&partitions) {
+        warn!(
+            topic,
+            partition, "could not pause halted partition: {:?}", e
+        );
+    }
+}
+
+/// Turns one basket message into a command and runs it. Shared by all
+/// partition tasks; every command gets its own unit of work, cloned from the
+/// one kept here, and so its own pooled connection.
+pub(crate) struct BasketEventProcessor<UOW, GS>
+where
+    UOW: UnitOfWorkPort + Debug + Clone,
+    GS: GeoServicePort + Clone,
+{
+    topics: BasketTopics,
+    uow: Mutex<UOW>,
+    geo_service: GS,
+    pickup_street: Option<String>,
+    dead_letter: DeadLetterProducer,
+    retry_policy: ConsumerRetryPolicy,
+}
+
+impl<UOW, GS> BasketEventProcessor<UOW, GS>
+where
+    UOW: UnitOfWorkPort + Debug + Clone,
+    GS: GeoServicePort + Clone,
+{
+    pub(crate) fn new(
+        topics: BasketTopics,
+        uow: UOW,
+        geo_service: GS,
+        pickup_street: Option<String>,
+        dead_letter: DeadLetterProducer,
+    ) -> Self {
+        Self {
+            topics,
+            uow: Mutex::new(uow),
+            geo_service,
+            pickup_street,
+            dead_letter,
+            retry_policy: ConsumerRetryPolicy::DEFAULT,
+        }
+    }
+
+    pub(crate) fn with_retry_policy(mut self, retry_policy: ConsumerRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    fn unit_of_work(&self) -> UOW {
+        self.uow
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) async fn process(&self, msg: &OwnedMessage) -> Result<(), ConsumerFailure> {
+        let payload = match msg.payload_view::<[u8]>() {
+            None => return Ok(()),
+            Some(Err(e)) => {
+                return Err(
+                    ConsumerError::Permanent(format!("unreadable payload: {:?}", e)).into(),
+                );
+            }
+            Some(Ok(payload)) => {
+                debug!("{:?}", payload);
+                payload
+            }
+        };
+
+        let content_type = ContentType::from_header(
+            msg.headers()
+                .and_then(|headers| {
+                    headers
+                        .iter()
+                        .find(|h| h.key.eq_ignore_ascii_case(CONTENT_TYPE_HEADER))
+                })
+                .and_then(|h| h.value),
+        );
+
+        let event = content_type.decode(payload).map_err(ConsumerError::from)?;
+
+        if !self.topics.accepts(msg.topic(), &event) {
+            return Err(ConsumerError::Permanent(format!(
+                "no handler for basket event on topic {}",
+                msg.topic()
+            ))
+            .into());
+        }
+
+        match event {
+            BasketEvent::Confirmed(command) => {
+                retry_transient(&self.retry_policy, || {
+                    self.handle_basket_confirmed(command.clone())
+                })
+                .await
+            }
+            BasketEvent::Cancelled(command) => {
+                retry_transient(&self.retry_policy, || {
+                    self.handle_basket_cancelled(command.clone())
+                })
+                .await
+            }
+        }
+    }
+
+    /// Processes `msg` and dead-letters it when that fails or panics. Errs
+    /// only when the dead-letter topic did not take it either.
+    async fn handle(&self, msg: &OwnedMessage) -> Result<(), KafkaError> {
+        let failure = match AssertUnwindSafe(self.process(msg)).catch_unwind().await {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(failure)) => failure,
+            Err(panic) => ConsumerError::Permanent(format!(
+                "handler panicked: {}",
+                panic_message(panic.as_ref())
+            ))
+            .into(),
+        };
+
+        self.dead_letter(msg, &failure).await
+    }
+
+    /// Retries with backoff as often as transient failures are. Moving on
+    /// after that would commit past the message and lose it, so the error is
+    /// left to halt the partition.
+    async fn dead_letter(
+        &self,
+        msg: &OwnedMessage,
+        failure: &ConsumerFailure,
+    ) -> Result<(), KafkaError> {
+        warn!(
+            topic = msg.topic(),
+            partition = msg.partition(),
+            offset = msg.offset(),
+            attempts = failure.attempts,
+            error = %failure.error,
+            "dead-lettering basket event"
+        );
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let Err(err) = self.dead_letter.publish(msg, failure).await else {
+                return Ok(());
+            };
+            if attempts >= self.retry_policy.max_attempts() {
+                return Err(err);
+            }
+
+            let delay = self.retry_policy.backoff(attempts);
+            error!(
+                ?err,
+                ?delay,
+                topic = self.dead_letter.topic(),
+                "could not publish to dead-letter topic"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn handle_basket_confirmed(
+        &self,
+        command: CreateOrderCommand,
+    ) -> Result<(), ConsumerError> {
+        let mut handler = CreateOrderHandler::new(
+            self.unit_of_work(),
+            self.geo_service.clone(),
+            self.pickup_street.clone(),
+        );
+
+        handler.execute(command).await.map_err(ConsumerError::from)
+    }
+
+    async fn handle_basket_cancelled(
+        &self,
+        command: CancelOrderCommand,
+    ) -> Result<(), ConsumerError> {
+        let mut handler = CancelOrderHandler::new(self.unit_of_work());
+
+        handler.execute(command).await.map_err(|err| match err {
+            // The order's create event may still be on its way, so the cancel
+            // is retried in place instead of being dead-lettered.
+            CommandError::NotFound(_) => ConsumerError::Transient(err.to_string()),
+            err => ConsumerError::from(err),
+        })
     }
 }
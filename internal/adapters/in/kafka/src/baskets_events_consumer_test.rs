@@ -0,0 +1,267 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::kernel::event::EventId;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
+use ports::errors::GeoClientError;
+use ports::errors::RepositoryError;
+use ports::geo_service_port::GeoServicePort;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use rdkafka::ClientConfig;
+use rdkafka::Timestamp;
+use rdkafka::message::OwnedMessage;
+use uuid::Uuid;
+
+use crate::basket_topics::BasketTopicHandler;
+use crate::basket_topics::BasketTopics;
+use crate::baskets_events_consumer::BasketEventProcessor;
+use crate::dead_letter::DeadLetterProducer;
+use crate::retry::ConsumerRetryPolicy;
+
+const CANCELLED_TOPIC: &str = "basket.cancelled";
+
+/// Holds one completed order, or none at all when `missing`.
+struct StubOrderRepository {
+    missing: bool,
+}
+
+impl OrderRepositoryPort for StubOrderRepository {
+    fn add(&mut self, _: &Order) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn update(&mut self, _: &Order) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_by_id(&mut self, id: OrderId) -> Result<Order, RepositoryError> {
+        if self.missing {
+            return Err(RepositoryError::NotFound(id.0.to_string()));
+        }
+
+        Ok(Order::restore(
+            id,
+            None,
+            None,
+            Location::new(1, 1).unwrap(),
+            Volume::new(5).unwrap(),
+            OrderStatus::Completed,
+            None,
+            false,
+            SystemTime::now(),
+            1,
+        ))
+    }
+
+    fn get_any_new(&mut self) -> Result<Order, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn find(&mut self, _: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn count(&mut self, _: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_history(&mut self, _: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct UnusedCourierRepository;
+
+impl CourierRepositoryPort for UnusedCourierRepository {
+    fn add(&mut self, _: Courier) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn update(&mut self, _: Courier) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_by_id(&mut self, _: CourierId) -> Result<Courier, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct UnusedOutboxRepository;
+
+impl OutboxRepositoryPort for UnusedOutboxRepository {
+    fn add(&mut self, _: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn update(&mut self, _: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _: usize,
+        _: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn delete_published_before(&mut self, _: SystemTime, _: usize) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn replay(&mut self, _: SystemTime, _: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct NewEventsInbox;
+
+impl InboxRepositoryPort for NewEventsInbox {
+    fn try_record(&mut self, _: EventId) -> Result<bool, RepositoryError> {
+        Ok(true)
+    }
+}
+
+/// Counts transactions, i.e. how many times the command was run.
+#[derive(Debug, Clone, Default)]
+struct CountingUnitOfWork {
+    transactions: Arc<AtomicUsize>,
+    order_missing: bool,
+}
+
+impl UnitOfWorkPort for CountingUnitOfWork {
+    type Uow = CountingUnitOfWork;
+    type CourierRepo = UnusedCourierRepository;
+    type OrderRepo = StubOrderRepository;
+    type OutboxRepo = UnusedOutboxRepository;
+    type InboxRepo = NewEventsInbox;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        self.transactions.fetch_add(1, Ordering::SeqCst);
+        f(self)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        UnusedCourierRepository
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        StubOrderRepository {
+            missing: self.order_missing,
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        UnusedOutboxRepository
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        NewEventsInbox
+    }
+}
+
+#[derive(Clone)]
+struct UnusedGeoService;
+
+#[async_trait]
+impl GeoServicePort for UnusedGeoService {
+    async fn get_location(&mut self, _: String) -> Result<Location, GeoClientError> {
+        unimplemented!()
+    }
+}
+
+fn cancelled_message() -> OwnedMessage {
+    let payload = format!(
+        r#"{{"eventId": "{}", "eventType": "BasketCancelledIntegrationEvent", "basketId": "{}"}}"#,
+        Uuid::new_v4(),
+        Uuid::new_v4()
+    );
+
+    OwnedMessage::new(
+        Some(payload.into_bytes()),
+        None,
+        CANCELLED_TOPIC.to_string(),
+        Timestamp::NotAvailable,
+        0,
+        7,
+        None,
+    )
+}
+
+#[tokio::test]
+async fn dead_letters_domain_rule_violation_without_retries() {
+    let uow = CountingUnitOfWork::default();
+    let processor = BasketEventProcessor::new(
+        BasketTopics::new().subscribe(CANCELLED_TOPIC, BasketTopicHandler::Cancelled),
+        uow.clone(),
+        UnusedGeoService,
+        None,
+        DeadLetterProducer::new(&ClientConfig::new(), "basket.dlq"),
+    );
+
+    let failure = processor.process(&cancelled_message()).await.unwrap_err();
+
+    assert!(!failure.error.is_transient(), "{}", failure.error);
+    assert_eq!(failure.attempts, 1);
+    assert_eq!(uow.transactions.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn retries_cancel_of_order_not_created_yet() {
+    let uow = CountingUnitOfWork {
+        order_missing: true,
+        ..Default::default()
+    };
+    let processor = BasketEventProcessor::new(
+        BasketTopics::new().subscribe(CANCELLED_TOPIC, BasketTopicHandler::Cancelled),
+        uow.clone(),
+        UnusedGeoService,
+        None,
+        DeadLetterProducer::new(&ClientConfig::new(), "basket.dlq"),
+    )
+    .with_retry_policy(ConsumerRetryPolicy::new(3, Duration::ZERO, Duration::ZERO));
+
+    let failure = processor.process(&cancelled_message()).await.unwrap_err();
+
+    assert!(failure.error.is_transient(), "{}", failure.error);
+    assert_eq!(failure.attempts, 3);
+    assert_eq!(uow.transactions.load(Ordering::SeqCst), 3);
+}
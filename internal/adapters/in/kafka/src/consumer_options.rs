@@ -0,0 +1,44 @@
+use rdkafka::ClientConfig;
+
+#[derive(Clone)]
+pub struct KafkaConsumerOptions {
+    brokers: String,
+    group_id: String,
+    properties: Vec<(String, String)>,
+}
+
+impl KafkaConsumerOptions {
+    pub fn new(brokers: String, group_id: String, properties: Vec<(String, String)>) -> Self {
+        Self {
+            brokers,
+            group_id,
+            properties,
+        }
+    }
+
+    pub fn brokers(&self) -> String {
+        self.brokers.clone()
+    }
+    pub fn group_id(&self) -> String {
+        self.group_id.clone()
+    }
+    pub fn properties(&self) -> &[(String, String)] {
+        &self.properties
+    }
+
+    pub fn client_config(&self) -> ClientConfig {
+        let mut config = ClientConfig::new();
+        config
+            .set("group.id", self.group_id())
+            .set("bootstrap.servers", self.brokers())
+            .set("enable.partition.eof", "false")
+            .set("enable.auto.commit", "false")
+            .set("session.timeout.ms", "6000");
+
+        for (key, value) in self.properties() {
+            config.set(key, value);
+        }
+
+        config
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use rdkafka::ClientConfig;
+use rdkafka::Message;
+use rdkafka::error::KafkaError;
+use rdkafka::message::Header;
+use rdkafka::message::Headers;
+use rdkafka::message::OwnedHeaders;
+use rdkafka::producer::FutureProducer;
+use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+
+use crate::retry::ConsumerFailure;
+
+pub const ERROR_HEADER: &str = "dlq-error";
+pub const ERROR_KIND_HEADER: &str = "dlq-error-kind";
+pub const ATTEMPTS_HEADER: &str = "dlq-attempts";
+pub const ORIGINAL_TOPIC_HEADER: &str = "dlq-original-topic";
+pub const ORIGINAL_PARTITION_HEADER: &str = "dlq-original-partition";
+pub const ORIGINAL_OFFSET_HEADER: &str = "dlq-original-offset";
+/// Milliseconds since the Unix epoch.
+pub const FAILED_AT_HEADER: &str = "dlq-failed-at";
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Copies messages the consumer gave up on to a dead-letter topic, keeping
+/// the original key, payload and headers so they can be replayed as-is.
+#[derive(Clone)]
+pub struct DeadLetterProducer {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl DeadLetterProducer {
+    pub fn new(config: &ClientConfig, topic: &str) -> Self {
+        let producer: FutureProducer = config
+            .create()
+            .expect("could not create dead-letter producer");
+
+        Self {
+            producer,
+            topic: topic.to_string(),
+        }
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub async fn publish<M: Message>(
+        &self,
+        msg: &M,
+        failure: &ConsumerFailure,
+    ) -> Result<(), KafkaError> {
+        let mut record = FutureRecord::<[u8], [u8]>::to(&self.topic).headers(dead_letter_headers(
+            msg.headers(),
+            &Origin::of(msg),
+            failure,
+        ));
+        if let Some(key) = msg.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = msg.payload() {
+            record = record.payload(payload);
+        }
+
+        self.producer
+            .send(record, Timeout::After(DELIVERY_TIMEOUT))
+            .await
+            .map(|_| ())
+            .map_err(|(err, _)| err)
+    }
+}
+
+/// Where a dead-lettered message was consumed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+impl Origin {
+    pub fn of<M: Message>(msg: &M) -> Self {
+        Self {
+            topic: msg.topic().to_string(),
+            partition: msg.partition(),
+            offset: msg.offset(),
+        }
+    }
+}
+
+/// The original headers followed by the failure details.
+pub fn dead_letter_headers<H: Headers>(
+    original: Option<&H>,
+    origin: &Origin,
+    failure: &ConsumerFailure,
+) -> OwnedHeaders {
+    let mut headers = OwnedHeaders::new();
+    if let Some(original) = original {
+        for header in original.iter() {
+            headers = headers.insert(header);
+        }
+    }
+
+    let failed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .to_string();
+
+    [
+        (ERROR_HEADER, failure.error.reason().to_string()),
+        (ERROR_KIND_HEADER, failure.error.kind().to_string()),
+        (ATTEMPTS_HEADER, failure.attempts.to_string()),
+        (ORIGINAL_TOPIC_HEADER, origin.topic.clone()),
+        (ORIGINAL_PARTITION_HEADER, origin.partition.to_string()),
+        (ORIGINAL_OFFSET_HEADER, origin.offset.to_string()),
+        (FAILED_AT_HEADER, failed_at),
+    ]
+    .into_iter()
+    .fold(headers, |headers, (key, value)| {
+        headers.insert(Header {
+            key,
+            value: Some(&value),
+        })
+    })
+}
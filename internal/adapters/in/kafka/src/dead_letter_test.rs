@@ -0,0 +1,69 @@
+use rdkafka::message::Header;
+use rdkafka::message::Headers;
+use rdkafka::message::OwnedHeaders;
+
+use crate::dead_letter::ATTEMPTS_HEADER;
+use crate::dead_letter::ERROR_HEADER;
+use crate::dead_letter::ERROR_KIND_HEADER;
+use crate::dead_letter::FAILED_AT_HEADER;
+use crate::dead_letter::ORIGINAL_OFFSET_HEADER;
+use crate::dead_letter::ORIGINAL_PARTITION_HEADER;
+use crate::dead_letter::ORIGINAL_TOPIC_HEADER;
+use crate::dead_letter::Origin;
+use crate::dead_letter::dead_letter_headers;
+use crate::errors::ConsumerError;
+use crate::retry::ConsumerFailure;
+
+fn header<'a>(headers: &'a OwnedHeaders, key: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.key == key)
+        .and_then(|h| h.value)
+        .and_then(|v| std::str::from_utf8(v).ok())
+}
+
+#[test]
+fn keeps_original_headers_and_adds_failure_details() {
+    let original = OwnedHeaders::new().insert(Header {
+        key: "content-type",
+        value: Some("application/json"),
+    });
+    let origin = Origin {
+        topic: "basket.confirmed".into(),
+        partition: 2,
+        offset: 42,
+    };
+    let failure = ConsumerFailure {
+        error: ConsumerError::Transient("db unavailable".into()),
+        attempts: 5,
+    };
+
+    let headers = dead_letter_headers(Some(&original), &origin, &failure);
+
+    assert_eq!(header(&headers, "content-type"), Some("application/json"));
+    assert_eq!(header(&headers, ERROR_HEADER), Some("db unavailable"));
+    assert_eq!(header(&headers, ERROR_KIND_HEADER), Some("transient"));
+    assert_eq!(header(&headers, ATTEMPTS_HEADER), Some("5"));
+    assert_eq!(
+        header(&headers, ORIGINAL_TOPIC_HEADER),
+        Some("basket.confirmed")
+    );
+    assert_eq!(header(&headers, ORIGINAL_PARTITION_HEADER), Some("2"));
+    assert_eq!(header(&headers, ORIGINAL_OFFSET_HEADER), Some("42"));
+    assert!(header(&headers, FAILED_AT_HEADER).is_some_and(|at| at.parse::<u128>().is_ok()));
+}
+
+#[test]
+fn works_without_original_headers() {
+    let origin = Origin {
+        topic: "basket.cancelled".into(),
+        partition: 0,
+        offset: 7,
+    };
+    let failure = ConsumerFailure::from(ConsumerError::Permanent("no address".into()));
+
+    let headers = dead_letter_headers::<OwnedHeaders>(None, &origin, &failure);
+
+    assert_eq!(header(&headers, ERROR_KIND_HEADER), Some("permanent"));
+    assert_eq!(header(&headers, ATTEMPTS_HEADER), Some("1"));
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Result;
+
+use application::errors::command_errors::CommandError;
+
+#[derive(Debug)]
+pub enum BasketEventError {
+    DecodeError(String),
+    UnsupportedEventType(String),
+    MapError(String),
+}
+
+impl Error for BasketEventError {}
+
+impl Display for BasketEventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+        match self {
+            Self::DecodeError(msg) => write!(f, "Could not decode basket event: {}", msg),
+            Self::UnsupportedEventType(event_type) => {
+                write!(f, "Unsupported basket event type: {}", event_type)
+            }
+            Self::MapError(msg) => write!(f, "Could not map basket event: {}", msg),
+        }
+    }
+}
+
+impl From<serde_json::Error> for BasketEventError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::DecodeError(value.to_string())
+    }
+}
+
+impl From<prost::DecodeError> for BasketEventError {
+    fn from(value: prost::DecodeError) -> Self {
+        Self::DecodeError(value.to_string())
+    }
+}
+
+impl From<CommandError> for BasketEventError {
+    fn from(value: CommandError) -> Self {
+        Self::MapError(value.to_string())
+    }
+}
+
+/// Decides what the consumer does with a message it could not handle.
+#[derive(Debug)]
+pub enum ConsumerError {
+    /// The message can never be handled, e.g. it does not decode; it goes
+    /// straight to the dead-letter topic.
+    Permanent(String),
+    /// Handling may succeed later, e.g. once the database is reachable again;
+    /// it is retried in place before giving up.
+    Transient(String),
+}
+
+impl ConsumerError {
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Transient(_))
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Permanent(_) => "permanent",
+            Self::Transient(_) => "transient",
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        match self {
+            Self::Permanent(reason) | Self::Transient(reason) => reason,
+        }
+    }
+}
+
+impl Error for ConsumerError {}
+
+impl Display for ConsumerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result {
+        write!(f, "{} consumer failure: {}", self.kind(), self.reason())
+    }
+}
+
+impl From<BasketEventError> for ConsumerError {
+    fn from(value: BasketEventError) -> Self {
+        Self::Permanent(value.to_string())
+    }
+}
+
+impl From<CommandError> for ConsumerError {
+    fn from(value: CommandError) -> Self {
+        match value {
+            CommandError::ArgumentError(_)
+            | CommandError::DomainError(_)
+            | CommandError::NotFound(_) => Self::Permanent(value.to_string()),
+            CommandError::ExecutionError(_) | CommandError::Conflict(_) => {
+                Self::Transient(value.to_string())
+            }
+        }
+    }
+}
@@ -1,3 +1,24 @@
+pub mod assignment;
+#[cfg(test)]
+pub mod assignment_test;
+pub mod basket_topics;
+#[cfg(test)]
+pub mod basket_topics_test;
 pub mod baskets_events_consumer;
+#[cfg(test)]
+pub mod baskets_events_consumer_test;
+pub mod consumer_options;
+pub mod dead_letter;
+#[cfg(test)]
+pub mod dead_letter_test;
+pub mod errors;
 mod mapper;
-pub mod shared;
+pub mod partition_workers;
+#[cfg(test)]
+pub mod partition_workers_test;
+pub mod retry;
+#[cfg(test)]
+pub mod retry_test;
+mod basket_event_gen {
+    include!("gen/basket_event.rs");
+}
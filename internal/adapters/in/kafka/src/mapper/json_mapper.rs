@@ -0,0 +1,146 @@
+use application::usecases::commands::cancel_order_command::CancelOrderCommand;
+use application::usecases::commands::create_order_command::CreateOrderCommand;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::errors::BasketEventError;
+use crate::mapper::BASKET_CANCELLED_EVENT_TYPE;
+use crate::mapper::BASKET_CONFIRMED_EVENT_TYPE;
+use crate::mapper::BasketEvent;
+use crate::mapper::parse_basket_id;
+use crate::mapper::parse_delivery_period;
+use crate::mapper::parse_event_id;
+use crate::mapper::parse_volume;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BasketEventEnvelope {
+    #[serde(default)]
+    pub event_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct BasketEventPayload {
+    #[serde(default)]
+    pub event_id: String,
+    #[serde(default)]
+    pub event_type: String,
+    #[serde(default)]
+    pub occurred_at: Option<String>,
+    pub basket_id: String,
+    pub address: Option<AddressPayload>,
+    #[serde(default)]
+    pub items: Vec<ItemPayload>,
+    #[serde(default)]
+    pub delivery_period: Option<DeliveryPeriodPayload>,
+    pub volume: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct BasketCancelledPayload {
+    #[serde(default)]
+    pub event_id: String,
+    #[serde(default)]
+    pub event_type: String,
+    #[serde(default)]
+    pub occurred_at: Option<String>,
+    pub basket_id: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct AddressPayload {
+    pub country: String,
+    pub city: String,
+    pub street: String,
+    pub house: String,
+    pub apartment: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct ItemPayload {
+    pub id: String,
+    pub good_id: String,
+    pub title: String,
+    pub price: f64,
+    pub quantity: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryPeriodPayload {
+    pub from: i32,
+    pub to: i32,
+}
+
+pub fn to_basket_event(payload: &[u8]) -> Result<BasketEvent, BasketEventError> {
+    let envelope: BasketEventEnvelope = serde_json::from_slice(payload)?;
+
+    match envelope.event_type.as_str() {
+        BASKET_CONFIRMED_EVENT_TYPE | "" => {
+            let event: BasketEventPayload = serde_json::from_slice(payload)?;
+            to_create_order_command(event).map(BasketEvent::Confirmed)
+        }
+        BASKET_CANCELLED_EVENT_TYPE => {
+            let event: BasketCancelledPayload = serde_json::from_slice(payload)?;
+            to_cancel_order_command(event).map(BasketEvent::Cancelled)
+        }
+        other => Err(BasketEventError::UnsupportedEventType(other.to_string())),
+    }
+}
+
+pub fn to_create_order_command(
+    event: BasketEventPayload,
+) -> Result<CreateOrderCommand, BasketEventError> {
+    info!(
+        event_id = event.event_id,
+        basket_id = event.basket_id,
+        "received BasketConfirmedIntegrationEvent JSON"
+    );
+
+    let address = event
+        .address
+        .ok_or_else(|| BasketEventError::MapError("event has no address".into()))?;
+    let id = parse_basket_id(&event.basket_id)?;
+    let volume = parse_volume(event.volume)?;
+    let delivery_period = event
+        .delivery_period
+        .map(|p| parse_delivery_period(p.from, p.to))
+        .transpose()?;
+
+    let command = CreateOrderCommand::new(id, address.street, volume, delivery_period)?;
+
+    Ok(match parse_event_id(&event.event_id) {
+        Some(event_id) => command.with_event_id(event_id),
+        None => command,
+    })
+}
+
+pub fn to_cancel_order_command(
+    event: BasketCancelledPayload,
+) -> Result<CancelOrderCommand, BasketEventError> {
+    info!(
+        event_id = event.event_id,
+        basket_id = event.basket_id,
+        reason = event.reason,
+        "received BasketCancelledIntegrationEvent JSON"
+    );
+
+    let id = parse_basket_id(&event.basket_id)?;
+
+    let command = CancelOrderCommand::new(id)?;
+
+    Ok(match parse_event_id(&event.event_id) {
+        Some(event_id) => command.with_event_id(event_id),
+        None => command,
+    })
+}
@@ -0,0 +1,155 @@
+use uuid::Uuid;
+
+use crate::errors::BasketEventError;
+use crate::mapper::BasketEvent;
+use crate::mapper::ContentType;
+use crate::mapper::json_mapper::to_basket_event;
+
+fn confirmed_json(basket_id: Uuid, event_type: &str) -> String {
+    format!(
+        r#"{{
+            "eventId": "{event_id}",
+            "eventType": "{event_type}",
+            "basketId": "{basket_id}",
+            "address": {{
+                "country": "Russia",
+                "city": "Moscow",
+                "street": "Tverskaya",
+                "house": "1",
+                "apartment": "2"
+            }},
+            "items": [],
+            "volume": 5
+        }}"#,
+        event_id = Uuid::new_v4(),
+    )
+}
+
+#[test]
+fn maps_confirmed_event_to_create_order_command() {
+    let basket_id = Uuid::new_v4();
+    let payload = confirmed_json(basket_id, "BasketConfirmedIntegrationEvent");
+
+    match to_basket_event(payload.as_bytes()) {
+        Ok(BasketEvent::Confirmed(command)) => {
+            assert_eq!(command.order_id().0, basket_id);
+            assert_eq!(command.street(), "Tverskaya");
+            assert_eq!(command.volume().value(), 5);
+        }
+        _ => panic!("expected confirmed event"),
+    }
+}
+
+#[test]
+fn maps_delivery_period() {
+    let payload = format!(
+        r#"{{
+            "eventType": "BasketConfirmedIntegrationEvent",
+            "basketId": "{}",
+            "address": {{"country": "", "city": "", "street": "Tverskaya", "house": "", "apartment": ""}},
+            "deliveryPeriod": {{"from": 9, "to": 12}},
+            "volume": 5
+        }}"#,
+        Uuid::new_v4()
+    );
+
+    match to_basket_event(payload.as_bytes()) {
+        Ok(BasketEvent::Confirmed(command)) => {
+            assert_eq!(command.delivery_period(), Some((9, 12)))
+        }
+        _ => panic!("expected confirmed event"),
+    }
+}
+
+#[test]
+fn treats_missing_event_type_as_confirmed() {
+    let basket_id = Uuid::new_v4();
+    let payload = confirmed_json(basket_id, "");
+
+    assert!(matches!(
+        to_basket_event(payload.as_bytes()),
+        Ok(BasketEvent::Confirmed(_))
+    ));
+}
+
+#[test]
+fn maps_cancelled_event_to_cancel_order_command() {
+    let basket_id = Uuid::new_v4();
+    let payload = format!(
+        r#"{{"eventType": "BasketCancelledIntegrationEvent", "basketId": "{basket_id}", "reason": "changed mind"}}"#
+    );
+
+    match to_basket_event(payload.as_bytes()) {
+        Ok(BasketEvent::Cancelled(command)) => assert_eq!(command.order_id().0, basket_id),
+        _ => panic!("expected cancelled event"),
+    }
+}
+
+#[test]
+fn rejects_confirmed_event_without_address() {
+    let payload = format!(
+        r#"{{"eventType": "BasketConfirmedIntegrationEvent", "basketId": "{}", "volume": 5}}"#,
+        Uuid::new_v4()
+    );
+
+    assert!(matches!(
+        to_basket_event(payload.as_bytes()),
+        Err(BasketEventError::MapError(_))
+    ));
+}
+
+#[test]
+fn rejects_unsupported_event_type() {
+    let payload = r#"{"eventType": "BasketCreatedIntegrationEvent"}"#;
+
+    assert!(matches!(
+        to_basket_event(payload.as_bytes()),
+        Err(BasketEventError::UnsupportedEventType(t)) if t == "BasketCreatedIntegrationEvent"
+    ));
+}
+
+#[test]
+fn rejects_malformed_json() {
+    assert!(matches!(
+        to_basket_event(b"not json"),
+        Err(BasketEventError::DecodeError(_))
+    ));
+}
+
+#[test]
+fn defaults_to_json_content_type() {
+    assert_eq!(ContentType::from_header(None), ContentType::Json);
+    assert_eq!(
+        ContentType::from_header(Some(b"application/json; charset=utf-8")),
+        ContentType::Json
+    );
+}
+
+#[test]
+fn carries_event_id_for_redelivery_detection() {
+    let event_id = Uuid::new_v4();
+    let payload = format!(
+        r#"{{"eventId": "{event_id}", "eventType": "BasketCancelledIntegrationEvent", "basketId": "{}"}}"#,
+        Uuid::new_v4()
+    );
+
+    match to_basket_event(payload.as_bytes()) {
+        Ok(BasketEvent::Cancelled(command)) => {
+            assert_eq!(command.event_id().map(|id| id.0), Some(event_id))
+        }
+        _ => panic!("expected cancelled event"),
+    }
+}
+
+#[test]
+fn maps_event_without_valid_event_id() {
+    let payload = format!(
+        r#"{{"eventId": "not-a-uuid", "eventType": "BasketCancelledIntegrationEvent", "basketId": "{}"}}"#,
+        Uuid::new_v4()
+    );
+
+    match to_basket_event(payload.as_bytes()) {
+        Ok(BasketEvent::Cancelled(command)) => assert!(command.event_id().is_none()),
+        _ => panic!("expected cancelled event"),
+    }
+}
@@ -0,0 +1,95 @@
+pub mod json_mapper;
+#[cfg(test)]
+pub mod json_mapper_test;
+pub mod protobuf_mapper;
+#[cfg(test)]
+pub mod protobuf_mapper_test;
+
+use std::str::FromStr;
+
+use application::usecases::commands::cancel_order_command::CancelOrderCommand;
+use application::usecases::commands::create_order_command::CreateOrderCommand;
+use domain::model::kernel::event::EventId;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::errors::BasketEventError;
+
+pub const BASKET_CONFIRMED_EVENT_TYPE: &str = "BasketConfirmedIntegrationEvent";
+pub const BASKET_CANCELLED_EVENT_TYPE: &str = "BasketCancelledIntegrationEvent";
+
+pub const CONTENT_TYPE_HEADER: &str = "content-type";
+
+pub enum BasketEvent {
+    Confirmed(CreateOrderCommand),
+    Cancelled(CancelOrderCommand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    Json,
+    Protobuf,
+}
+
+impl ContentType {
+    /// Producers that predate the header only ever sent JSON.
+    pub fn from_header(value: Option<&[u8]>) -> Self {
+        let Some(value) = value.and_then(|v| std::str::from_utf8(v).ok()) else {
+            return Self::Json;
+        };
+
+        let mime = value.split(';').next().unwrap_or_default().trim();
+
+        match mime.to_ascii_lowercase().as_str() {
+            "application/x-protobuf"
+            | "application/protobuf"
+            | "application/vnd.google.protobuf" => Self::Protobuf,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn decode(&self, payload: &[u8]) -> Result<BasketEvent, BasketEventError> {
+        match self {
+            Self::Json => json_mapper::to_basket_event(payload),
+            Self::Protobuf => protobuf_mapper::to_basket_event(payload),
+        }
+    }
+}
+
+fn parse_basket_id(basket_id: &str) -> Result<Uuid, BasketEventError> {
+    Uuid::from_str(basket_id).map_err(|err| {
+        BasketEventError::MapError(format!(
+            "basket_id {} is not a valid UUID: {}",
+            basket_id, err
+        ))
+    })
+}
+
+/// Events without a usable id are still handled, just without redelivery
+/// protection, so a producer bug does not stall the partition.
+fn parse_event_id(event_id: &str) -> Option<EventId> {
+    if event_id.is_empty() {
+        return None;
+    }
+
+    Uuid::from_str(event_id)
+        .map(EventId)
+        .inspect_err(|err| warn!(event_id, %err, "event_id is not a valid UUID"))
+        .ok()
+}
+
+fn parse_volume(volume: i32) -> Result<u16, BasketEventError> {
+    u16::try_from(volume).map_err(|_| {
+        BasketEventError::MapError(format!("volume {} is out of range for u16", volume))
+    })
+}
+
+fn parse_delivery_period(from: i32, to: i32) -> Result<(u8, u8), BasketEventError> {
+    match (u8::try_from(from), u8::try_from(to)) {
+        (Ok(from), Ok(to)) => Ok((from, to)),
+        _ => Err(BasketEventError::MapError(format!(
+            "delivery period {}-{} is out of range",
+            from, to
+        ))),
+    }
+}
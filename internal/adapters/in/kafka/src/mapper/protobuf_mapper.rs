@@ -0,0 +1,86 @@
+use application::usecases::commands::cancel_order_command::CancelOrderCommand;
+use application::usecases::commands::create_order_command::CreateOrderCommand;
+use prost::Message;
+use tracing::info;
+
+use crate::basket_event_gen::BasketCancelledIntegrationEvent;
+use crate::basket_event_gen::BasketConfirmedIntegrationEvent;
+use crate::errors::BasketEventError;
+use crate::mapper::BASKET_CANCELLED_EVENT_TYPE;
+use crate::mapper::BASKET_CONFIRMED_EVENT_TYPE;
+use crate::mapper::BasketEvent;
+use crate::mapper::parse_basket_id;
+use crate::mapper::parse_delivery_period;
+use crate::mapper::parse_event_id;
+use crate::mapper::parse_volume;
+
+/// Both basket messages keep `event_type` under tag 2, so it can be read
+/// before the concrete message is known; other fields are skipped.
+#[derive(Clone, PartialEq, Message)]
+struct BasketEventEnvelope {
+    #[prost(string, tag = "2")]
+    event_type: String,
+}
+
+pub fn to_basket_event(payload: &[u8]) -> Result<BasketEvent, BasketEventError> {
+    let envelope = BasketEventEnvelope::decode(payload)?;
+
+    match envelope.event_type.as_str() {
+        BASKET_CONFIRMED_EVENT_TYPE | "" => {
+            let event = BasketConfirmedIntegrationEvent::decode(payload)?;
+            to_create_order_command(event).map(BasketEvent::Confirmed)
+        }
+        BASKET_CANCELLED_EVENT_TYPE => {
+            let event = BasketCancelledIntegrationEvent::decode(payload)?;
+            to_cancel_order_command(event).map(BasketEvent::Cancelled)
+        }
+        other => Err(BasketEventError::UnsupportedEventType(other.to_string())),
+    }
+}
+
+pub fn to_create_order_command(
+    event: BasketConfirmedIntegrationEvent,
+) -> Result<CreateOrderCommand, BasketEventError> {
+    info!(
+        event_id = event.event_id,
+        basket_id = event.basket_id,
+        "received BasketConfirmedIntegrationEvent protobuf"
+    );
+
+    let address = event
+        .address
+        .ok_or_else(|| BasketEventError::MapError("event has no address".into()))?;
+    let id = parse_basket_id(&event.basket_id)?;
+    let volume = parse_volume(event.volume)?;
+    let delivery_period = event
+        .delivery_period
+        .map(|p| parse_delivery_period(p.from, p.to))
+        .transpose()?;
+
+    let command = CreateOrderCommand::new(id, address.street, volume, delivery_period)?;
+
+    Ok(match parse_event_id(&event.event_id) {
+        Some(event_id) => command.with_event_id(event_id),
+        None => command,
+    })
+}
+
+pub fn to_cancel_order_command(
+    event: BasketCancelledIntegrationEvent,
+) -> Result<CancelOrderCommand, BasketEventError> {
+    info!(
+        event_id = event.event_id,
+        basket_id = event.basket_id,
+        reason = event.reason,
+        "received BasketCancelledIntegrationEvent protobuf"
+    );
+
+    let id = parse_basket_id(&event.basket_id)?;
+
+    let command = CancelOrderCommand::new(id)?;
+
+    Ok(match parse_event_id(&event.event_id) {
+        Some(event_id) => command.with_event_id(event_id),
+        None => command,
+    })
+}
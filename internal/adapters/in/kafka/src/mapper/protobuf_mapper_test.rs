@@ -0,0 +1,128 @@
+use prost::Message;
+use uuid::Uuid;
+
+use crate::basket_event_gen::Address;
+use crate::basket_event_gen::BasketCancelledIntegrationEvent;
+use crate::basket_event_gen::BasketConfirmedIntegrationEvent;
+use crate::basket_event_gen::DeliveryPeriod;
+use crate::errors::BasketEventError;
+use crate::mapper::BasketEvent;
+use crate::mapper::ContentType;
+use crate::mapper::protobuf_mapper::to_basket_event;
+
+fn confirmed_event(basket_id: Uuid) -> BasketConfirmedIntegrationEvent {
+    BasketConfirmedIntegrationEvent {
+        event_id: Uuid::new_v4().to_string(),
+        event_type: "BasketConfirmedIntegrationEvent".into(),
+        occurred_at: None,
+        basket_id: basket_id.to_string(),
+        address: Some(Address {
+            country: "Russia".into(),
+            city: "Moscow".into(),
+            street: "Tverskaya".into(),
+            house: "1".into(),
+            apartment: "2".into(),
+        }),
+        items: vec![],
+        delivery_period: None,
+        volume: 5,
+    }
+}
+
+#[test]
+fn maps_confirmed_event_to_create_order_command() {
+    let basket_id = Uuid::new_v4();
+    let payload = confirmed_event(basket_id).encode_to_vec();
+
+    match to_basket_event(&payload) {
+        Ok(BasketEvent::Confirmed(command)) => {
+            assert_eq!(command.order_id().0, basket_id);
+            assert_eq!(command.street(), "Tverskaya");
+            assert_eq!(command.volume().value(), 5);
+        }
+        _ => panic!("expected confirmed event"),
+    }
+}
+
+#[test]
+fn maps_cancelled_event_to_cancel_order_command() {
+    let basket_id = Uuid::new_v4();
+    let payload = BasketCancelledIntegrationEvent {
+        event_id: Uuid::new_v4().to_string(),
+        event_type: "BasketCancelledIntegrationEvent".into(),
+        occurred_at: None,
+        basket_id: basket_id.to_string(),
+        reason: "changed mind".into(),
+    }
+    .encode_to_vec();
+
+    match to_basket_event(&payload) {
+        Ok(BasketEvent::Cancelled(command)) => assert_eq!(command.order_id().0, basket_id),
+        _ => panic!("expected cancelled event"),
+    }
+}
+
+#[test]
+fn maps_delivery_period() {
+    let mut event = confirmed_event(Uuid::new_v4());
+    event.delivery_period = Some(DeliveryPeriod { from: 18, to: 21 });
+
+    match to_basket_event(&event.encode_to_vec()) {
+        Ok(BasketEvent::Confirmed(command)) => {
+            assert_eq!(command.delivery_period(), Some((18, 21)))
+        }
+        _ => panic!("expected confirmed event"),
+    }
+}
+
+#[test]
+fn rejects_out_of_range_volume() {
+    let mut event = confirmed_event(Uuid::new_v4());
+    event.volume = -1;
+
+    assert!(matches!(
+        to_basket_event(&event.encode_to_vec()),
+        Err(BasketEventError::MapError(_))
+    ));
+}
+
+#[test]
+fn rejects_unsupported_event_type() {
+    let mut event = confirmed_event(Uuid::new_v4());
+    event.event_type = "BasketCreatedIntegrationEvent".into();
+
+    assert!(matches!(
+        to_basket_event(&event.encode_to_vec()),
+        Err(BasketEventError::UnsupportedEventType(_))
+    ));
+}
+
+#[test]
+fn rejects_json_payload() {
+    assert!(to_basket_event(br#"{"basketId": "x"}"#).is_err());
+}
+
+#[test]
+fn detects_protobuf_content_type() {
+    assert_eq!(
+        ContentType::from_header(Some(b"application/x-protobuf")),
+        ContentType::Protobuf
+    );
+    assert_eq!(
+        ContentType::from_header(Some(b"Application/Protobuf; proto=basket_event")),
+        ContentType::Protobuf
+    );
+}
+
+#[test]
+fn carries_event_id_for_redelivery_detection() {
+    let event = confirmed_event(Uuid::new_v4());
+    let event_id = Uuid::parse_str(&event.event_id).unwrap();
+
+    match to_basket_event(&event.encode_to_vec()) {
+        Ok(BasketEvent::Confirmed(command)) => {
+            assert_eq!(command.event_id().map(|id| id.0), Some(event_id))
+        }
+        _ => panic!("expected confirmed event"),
+    }
+}
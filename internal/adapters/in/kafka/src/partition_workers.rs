@@ -0,0 +1,138 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use futures::FutureExt;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::debug;
+use tracing::error;
+
+/// Messages a partition may queue before dispatching blocks the consumer.
+pub const PARTITION_QUEUE_CAPACITY: usize = 32;
+
+/// What became of a dispatched message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dispatch {
+    /// Queued behind the earlier messages of its partition.
+    Queued,
+    /// Dropped: the partition halted on an earlier message it could not
+    /// finish, so nothing from it is handled or committed until it is revoked.
+    Halted,
+}
+
+/// Runs one task per topic partition, so messages of a partition are handled
+/// in order while different partitions proceed side by side. At most
+/// `max_in_flight` messages are handled at the same time across all tasks.
+///
+/// A partition halts when its handler fails or panics. Its task is not
+/// restarted: later messages would commit past the one that was lost.
+pub struct PartitionWorkers<M, H> {
+    handler: Arc<H>,
+    permits: Arc<Semaphore>,
+    workers: HashMap<(String, i32), Worker<M>>,
+}
+
+struct Worker<M> {
+    queue: mpsc::Sender<M>,
+    task: JoinHandle<()>,
+}
+
+impl<M, H, Fut, E> PartitionWorkers<M, H>
+where
+    M: Send + 'static,
+    H: Fn(M) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send,
+    E: Display,
+{
+    pub fn new(max_in_flight: usize, handler: H) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            permits: Arc::new(Semaphore::new(max_in_flight.max(1))),
+            workers: HashMap::new(),
+        }
+    }
+
+    /// Queues `message` behind the earlier messages of its partition. Waits
+    /// while that partition's queue is full.
+    pub async fn dispatch(&mut self, topic: &str, partition: i32, message: M) -> Dispatch {
+        let worker = self
+            .workers
+            .entry((topic.to_string(), partition))
+            .or_insert_with(|| spawn_worker(topic, partition, &self.handler, &self.permits));
+
+        match worker.queue.send(message).await {
+            Ok(()) => Dispatch::Queued,
+            Err(_) => Dispatch::Halted,
+        }
+    }
+
+    /// Stops the task of a partition handed to another consumer, dropping
+    /// what it had queued; the new owner starts from the last commit. A
+    /// partition assigned again later gets a fresh task.
+    pub fn revoke(&mut self, topic: &str, partition: i32) {
+        if let Some(worker) = self.workers.remove(&(topic.to_string(), partition)) {
+            debug!(topic, partition, "stopping revoked partition worker");
+            worker.task.abort();
+        }
+    }
+
+    /// Stops accepting messages and waits until every queued one is handled.
+    pub async fn drain(self) {
+        for (_, worker) in self.workers {
+            drop(worker.queue);
+            let _ = worker.task.await;
+        }
+    }
+}
+
+fn spawn_worker<M, H, Fut, E>(
+    topic: &str,
+    partition: i32,
+    handler: &Arc<H>,
+    permits: &Arc<Semaphore>,
+) -> Worker<M>
+where
+    M: Send + 'static,
+    H: Fn(M) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send,
+    E: Display,
+{
+    debug!(topic, partition, "starting partition worker");
+
+    let (queue, mut messages) = mpsc::channel::<M>(PARTITION_QUEUE_CAPACITY);
+    let handler = Arc::clone(handler);
+    let permits = Arc::clone(permits);
+    let topic = topic.to_string();
+
+    let task = tokio::spawn(async move {
+        while let Some(message) = messages.recv().await {
+            let Ok(_permit) = permits.acquire().await else {
+                return;
+            };
+
+            let reason = match AssertUnwindSafe(handler(message)).catch_unwind().await {
+                Ok(Ok(())) => continue,
+                Ok(Err(err)) => err.to_string(),
+                Err(panic) => format!("handler panicked: {}", panic_message(panic.as_ref())),
+            };
+
+            // Returning drops the queue, so the messages behind are never committed.
+            error!(topic, partition, %reason, "halting partition");
+            return;
+        }
+    });
+
+    Worker { queue, task }
+}
+
+pub(crate) fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
@@ -0,0 +1,199 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::partition_workers::Dispatch;
+use crate::partition_workers::PartitionWorkers;
+
+/// Records which message ran and the highest number running at once.
+#[derive(Clone, Default)]
+struct Probe {
+    handled: Arc<Mutex<Vec<(i32, u32)>>>,
+    running: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+type Handled = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+enum Outcome {
+    Handled,
+    Fails(&'static str),
+    Panics,
+}
+
+impl Probe {
+    fn handler(&self) -> impl Fn((i32, u32)) -> Handled + Send + Sync + 'static {
+        self.failing_on(|_| Outcome::Handled)
+    }
+
+    fn failing_on(
+        &self,
+        outcome: fn((i32, u32)) -> Outcome,
+    ) -> impl Fn((i32, u32)) -> Handled + Send + Sync + 'static {
+        let probe = self.clone();
+        move |message: (i32, u32)| {
+            let probe = probe.clone();
+            Box::pin(async move {
+                let running = probe.running.fetch_add(1, Ordering::SeqCst) + 1;
+                probe.peak.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                probe.running.fetch_sub(1, Ordering::SeqCst);
+                match outcome(message) {
+                    Outcome::Handled => {
+                        probe.handled.lock().unwrap().push(message);
+                        Ok(())
+                    }
+                    Outcome::Fails(reason) => Err(reason.to_string()),
+                    Outcome::Panics => panic!("poison message {:?}", message),
+                }
+            })
+        }
+    }
+
+    fn offsets_of(&self, partition: i32) -> Vec<u32> {
+        self.handled
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(p, _)| *p == partition)
+            .map(|(_, offset)| *offset)
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn keeps_partition_order() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(4, probe.handler());
+
+    for offset in 0..10 {
+        for partition in 0..3 {
+            workers
+                .dispatch("basket.confirmed", partition, (partition, offset))
+                .await;
+        }
+    }
+    workers.drain().await;
+
+    assert_eq!(probe.handled.lock().unwrap().len(), 30);
+    for partition in 0..3 {
+        assert_eq!(probe.offsets_of(partition), (0..10).collect::<Vec<_>>());
+    }
+}
+
+#[tokio::test]
+async fn handles_partitions_concurrently_within_the_limit() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(2, probe.handler());
+
+    for offset in 0..5 {
+        for partition in 0..4 {
+            workers
+                .dispatch("basket.confirmed", partition, (partition, offset))
+                .await;
+        }
+    }
+    workers.drain().await;
+
+    let peak = probe.peak.load(Ordering::SeqCst);
+    assert!(peak > 1, "partitions should overlap, peak was {}", peak);
+    assert!(
+        peak <= 2,
+        "at most two messages may run at once, peak was {}",
+        peak
+    );
+}
+
+#[tokio::test]
+async fn same_partition_number_on_different_topics_runs_separately() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(2, probe.handler());
+
+    workers.dispatch("basket.confirmed", 0, (0, 0)).await;
+    workers.dispatch("basket.cancelled", 0, (1, 0)).await;
+    workers.drain().await;
+
+    assert_eq!(probe.handled.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn panicking_message_halts_its_partition_only() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(
+        2,
+        probe.failing_on(|message| match message {
+            (0, 2) => Outcome::Panics,
+            _ => Outcome::Handled,
+        }),
+    );
+
+    let mut dispatched = Vec::new();
+    for offset in 0..5 {
+        for partition in 0..2 {
+            dispatched.push(
+                workers
+                    .dispatch("basket.confirmed", partition, (partition, offset))
+                    .await,
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+    workers.drain().await;
+
+    assert_eq!(probe.offsets_of(0), vec![0, 1], "nothing after the panic");
+    assert_eq!(probe.offsets_of(1), (0..5).collect::<Vec<_>>());
+    assert_eq!(dispatched.last(), Some(&Dispatch::Queued));
+    assert!(
+        dispatched.contains(&Dispatch::Halted),
+        "the halted partition must not get a new worker"
+    );
+}
+
+#[tokio::test]
+async fn failed_message_halts_its_partition() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(
+        1,
+        probe.failing_on(|message| match message {
+            (0, 0) => Outcome::Fails("dead-letter topic unavailable"),
+            _ => Outcome::Handled,
+        }),
+    );
+
+    assert_eq!(
+        workers.dispatch("basket.confirmed", 0, (0, 0)).await,
+        Dispatch::Queued
+    );
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        workers.dispatch("basket.confirmed", 0, (0, 1)).await,
+        Dispatch::Halted
+    );
+    workers.drain().await;
+
+    assert!(probe.offsets_of(0).is_empty());
+}
+
+#[tokio::test]
+async fn revoked_partition_starts_over_when_dispatched_again() {
+    let probe = Probe::default();
+    let mut workers = PartitionWorkers::new(
+        1,
+        probe.failing_on(|message| match message {
+            (0, 0) => Outcome::Panics,
+            _ => Outcome::Handled,
+        }),
+    );
+
+    workers.dispatch("basket.confirmed", 0, (0, 0)).await;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    workers.revoke("basket.confirmed", 0);
+    let dispatched = workers.dispatch("basket.confirmed", 0, (0, 1)).await;
+    workers.drain().await;
+
+    assert_eq!(dispatched, Dispatch::Queued);
+    assert_eq!(probe.offsets_of(0), vec![1]);
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::errors::ConsumerError;
+
+/// Exponential backoff between in-place attempts of a transient failure,
+/// capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsumerRetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl ConsumerRetryPolicy {
+    pub const DEFAULT: Self = Self {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(30),
+    };
+
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay: max_delay.max(base_delay),
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before the next try once `attempts` tries have failed.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for ConsumerRetryPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The error a message was given up on and how many times it was tried.
+#[derive(Debug)]
+pub struct ConsumerFailure {
+    pub error: ConsumerError,
+    pub attempts: u32,
+}
+
+impl From<ConsumerError> for ConsumerFailure {
+    fn from(error: ConsumerError) -> Self {
+        Self { error, attempts: 1 }
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails permanently or runs out of attempts.
+/// Waiting in place keeps later messages of the partition behind this one.
+pub async fn retry_transient<F, Fut>(
+    policy: &ConsumerRetryPolicy,
+    mut attempt: F,
+) -> Result<(), ConsumerFailure>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), ConsumerError>>,
+{
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        let error = match attempt().await {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        if !error.is_transient() || attempts >= policy.max_attempts() {
+            return Err(ConsumerFailure { error, attempts });
+        }
+
+        let delay = policy.backoff(attempts);
+        warn!(attempts, ?delay, %error, "retrying basket event");
+        tokio::time::sleep(delay).await;
+    }
+}
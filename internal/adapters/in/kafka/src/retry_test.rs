@@ -0,0 +1,107 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use application::errors::command_errors::CommandError;
+
+use crate::errors::BasketEventError;
+use crate::errors::ConsumerError;
+use crate::retry::ConsumerRetryPolicy;
+use crate::retry::retry_transient;
+
+fn immediate_retries(max_attempts: u32) -> ConsumerRetryPolicy {
+    ConsumerRetryPolicy::new(max_attempts, Duration::ZERO, Duration::ZERO)
+}
+
+#[test]
+fn undecodable_events_are_permanent() {
+    let error = ConsumerError::from(BasketEventError::DecodeError("bad json".into()));
+
+    assert!(!error.is_transient());
+}
+
+#[test]
+fn classifies_command_errors() {
+    let permanent = [
+        CommandError::ArgumentError("empty street".into()),
+        CommandError::DomainError("completed orders cannot be cancelled".into()),
+        CommandError::NotFound("order".into()),
+    ];
+    let transient = [
+        CommandError::ExecutionError("db unavailable".into()),
+        CommandError::Conflict("order".into()),
+    ];
+
+    assert!(
+        permanent
+            .into_iter()
+            .all(|e| !ConsumerError::from(e).is_transient())
+    );
+    assert!(
+        transient
+            .into_iter()
+            .all(|e| ConsumerError::from(e).is_transient())
+    );
+}
+
+#[tokio::test]
+async fn transient_failure_is_retried_until_it_succeeds() {
+    let calls = Cell::new(0);
+
+    let result = retry_transient(&immediate_retries(5), || {
+        calls.set(calls.get() + 1);
+        let call = calls.get();
+        async move {
+            if call < 3 {
+                return Err(ConsumerError::Transient("db unavailable".into()));
+            }
+            Ok(())
+        }
+    })
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(calls.get(), 3);
+}
+
+#[tokio::test]
+async fn transient_failure_gives_up_after_max_attempts() {
+    let calls = Cell::new(0);
+
+    let failure = retry_transient(&immediate_retries(3), || {
+        calls.set(calls.get() + 1);
+        async { Err(ConsumerError::Transient("db unavailable".into())) }
+    })
+    .await
+    .unwrap_err();
+
+    assert_eq!(failure.attempts, 3);
+    assert_eq!(calls.get(), 3);
+    assert!(failure.error.is_transient());
+}
+
+#[tokio::test]
+async fn permanent_failure_is_not_retried() {
+    let calls = Cell::new(0);
+
+    let failure = retry_transient(&immediate_retries(5), || {
+        calls.set(calls.get() + 1);
+        async { Err(ConsumerError::Permanent("no address".into())) }
+    })
+    .await
+    .unwrap_err();
+
+    assert_eq!(failure.attempts, 1);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let policy =
+        ConsumerRetryPolicy::new(10, Duration::from_millis(100), Duration::from_millis(500));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    assert_eq!(policy.backoff(40), Duration::from_millis(500));
+}
@@ -6,17 +6,21 @@ pub struct GetGeolocationRequest {
     pub street: ::prost::alloc::string::String,
 }
 /// Response
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct GetGeolocationReply {
     #[prost(message, optional, tag = "1")]
     pub location: ::core::option::Option<Location>,
 }
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct Location {
     #[prost(int32, tag = "1")]
     pub x: i32,
     #[prost(int32, tag = "2")]
     pub y: i32,
+    #[prost(double, tag = "3")]
+    pub latitude: f64,
+    #[prost(double, tag = "4")]
+    pub longitude: f64,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ErrorResponse {
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use domain::model::kernel::coordinate_system::CoordinateSystem;
 use domain::model::kernel::location::Location;
 use ports::errors::GeoClientError;
 use ports::geo_service_port::GeoServicePort;
@@ -7,19 +8,28 @@ use tonic::transport::Channel;
 use crate::api::GetGeolocationRequest;
 use crate::api::geo_client::GeoClient;
 use crate::errors::GeoClientGrpcError;
+use crate::mapper::to_domain;
 
 #[derive(Clone)]
 pub struct GeoService {
     client: GeoClient<Channel>,
+    coordinate_system: CoordinateSystem,
 }
 impl GeoService {
-    pub async fn new(address: String) -> Result<Self, GeoClientError> {
+    /// Replies are read as locations of `coordinate_system`.
+    pub async fn new(
+        address: String,
+        coordinate_system: CoordinateSystem,
+    ) -> Result<Self, GeoClientError> {
         let client = GeoClient::connect(address)
             .await
             .map_err(GeoClientGrpcError::from)
             .map_err(GeoClientError::from)?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            coordinate_system,
+        })
     }
 }
 
@@ -34,7 +44,7 @@ impl GeoServicePort for GeoService {
             .map_err(GeoClientError::from)?;
 
         match result.into_inner().location {
-            Some(location) => Ok(Location::try_from(location)?),
+            Some(location) => Ok(to_domain(location, &self.coordinate_system)?),
             None => Err(GeoClientError::ExecutionError(
                 "no location for such address".to_string(),
             )),
@@ -6,3 +6,5 @@ mod api {
 mod errors;
 pub mod geo_service;
 mod mapper;
+#[cfg(test)]
+mod mapper_test;
@@ -1,12 +1,17 @@
 use domain::errors::domain_model_errors::DomainModelError;
+use domain::model::kernel::coordinate_system::CoordinateSystem;
 use domain::model::kernel::location::Location as DomainLocation;
 
 use crate::api::Location;
 
-impl TryFrom<Location> for DomainLocation {
-    type Error = DomainModelError;
-
-    fn try_from(v: Location) -> Result<Self, Self::Error> {
-        Self::new(v.x as u8, v.y as u8)
+pub(crate) fn to_domain(
+    v: Location,
+    system: &CoordinateSystem,
+) -> Result<DomainLocation, DomainModelError> {
+    match system {
+        CoordinateSystem::Grid(bounds) => DomainLocation::try_from_raw(v.x, v.y, bounds),
+        CoordinateSystem::Geographic(settings) => {
+            DomainLocation::new_geographic_within(v.latitude, v.longitude, settings.area())
+        }
     }
 }
@@ -0,0 +1,70 @@
+use domain::model::kernel::coordinate_system::CoordinateSystem;
+use domain::model::kernel::coordinate_system::DistanceFormula;
+use domain::model::kernel::coordinate_system::GeoArea;
+use domain::model::kernel::coordinate_system::GeoSettings;
+use domain::model::kernel::grid::GridBounds;
+use domain::model::kernel::location::Location as DomainLocation;
+
+use crate::api::Location;
+use crate::mapper::to_domain;
+
+fn grid_location(x: i32, y: i32) -> Location {
+    Location {
+        x,
+        y,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn maps_location() {
+    let location = to_domain(grid_location(3, 7), &CoordinateSystem::default()).unwrap();
+
+    assert_eq!(location, DomainLocation::new(3, 7).unwrap());
+}
+
+#[test]
+fn rejects_coordinates_instead_of_truncating() {
+    let system = CoordinateSystem::Grid(GridBounds::MAX);
+
+    assert!(to_domain(grid_location(65_536, 1), &system).is_err());
+    assert!(to_domain(grid_location(1, -1), &system).is_err());
+}
+
+#[test]
+fn rejects_cells_outside_the_grid() {
+    let system = CoordinateSystem::Grid(GridBounds::new(100, 50).unwrap());
+
+    assert!(to_domain(grid_location(100, 50), &system).is_ok());
+    assert!(to_domain(grid_location(101, 1), &system).is_err());
+}
+
+#[test]
+fn maps_geographic_location() {
+    let area = GeoArea::new(55.5, 37.3, 56.0, 37.9).unwrap();
+    let settings = GeoSettings::new(area, DistanceFormula::Haversine, 60).unwrap();
+    let system = CoordinateSystem::Geographic(settings);
+    let reply = Location {
+        latitude: 55.7558,
+        longitude: 37.6173,
+        ..Default::default()
+    };
+
+    let location = to_domain(reply, &system).unwrap();
+
+    assert_eq!(
+        location,
+        DomainLocation::new_geographic(55.7558, 37.6173).unwrap()
+    );
+    assert!(
+        to_domain(
+            Location {
+                latitude: 59.9343,
+                longitude: 30.3351,
+                ..Default::default()
+            },
+            &system
+        )
+        .is_err()
+    );
+}
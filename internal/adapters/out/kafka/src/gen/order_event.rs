@@ -13,6 +13,21 @@ pub struct OrderCreatedIntegrationEvent {
     pub order_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct OrderPickedUpIntegrationEvent {
+    /// Metadata
+    #[prost(string, tag = "1")]
+    pub event_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub occurred_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Payload
+    #[prost(string, tag = "4")]
+    pub order_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub courier_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct OrderCompletedIntegrationEvent {
     /// Metadata
     #[prost(string, tag = "1")]
@@ -27,3 +42,31 @@ pub struct OrderCompletedIntegrationEvent {
     #[prost(string, tag = "5")]
     pub courier_id: ::prost::alloc::string::String,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct OrderCancelledIntegrationEvent {
+    /// Metadata
+    #[prost(string, tag = "1")]
+    pub event_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub occurred_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Payload
+    #[prost(string, tag = "4")]
+    pub order_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub courier_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct OrderDeliveryWindowMissedIntegrationEvent {
+    /// Metadata
+    #[prost(string, tag = "1")]
+    pub event_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub occurred_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// Payload
+    #[prost(string, tag = "4")]
+    pub order_id: ::prost::alloc::string::String,
+}
@@ -1,5 +1,6 @@
 mod mapper;
 pub mod orders_events_producer;
+pub mod producer_options;
 mod order_event_gen {
     include!("gen/order_event.rs");
 }
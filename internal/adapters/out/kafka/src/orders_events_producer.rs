@@ -1,44 +1,68 @@
 use domain::model::order::order_events::OrderEvent;
+use async_trait::async_trait;
+use ports::errors::ProducerError;
 use ports::events_producer_port::Events;
 use ports::events_producer_port::EventsProducerPort;
 use prost::Message;
 use prost_types::Timestamp;
-use rdkafka::ClientConfig;
 use rdkafka::producer::FutureProducer;
 use rdkafka::producer::FutureRecord;
+use rdkafka::util::Timeout;
+use std::time::Duration;
 use std::time::SystemTime;
 
+use crate::order_event_gen::OrderCancelledIntegrationEvent;
 use crate::order_event_gen::OrderCompletedIntegrationEvent;
 use crate::order_event_gen::OrderCreatedIntegrationEvent;
+use crate::order_event_gen::OrderDeliveryWindowMissedIntegrationEvent;
+use crate::order_event_gen::OrderPickedUpIntegrationEvent;
+use crate::producer_options::KafkaProducerOptions;
 
-static TOPIC: &str = "orders.events";
+/// How long `publish` waits for the broker to acknowledge a record.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
 
+#[derive(Clone)]
 pub struct OrdersEventsProducer {
     producer: FutureProducer,
+    topic: String,
 }
 
 impl OrdersEventsProducer {
-    pub fn new(brokers: &str, group_id: &str) -> Self {
-        let mut config = ClientConfig::new();
-
-        let producer: FutureProducer = config
-            .set("bootstrap.servers", brokers)
+    pub fn new(options: &KafkaProducerOptions, topic: &str) -> Self {
+        let producer: FutureProducer = options
+            .client_config()
             .create()
-            .expect("could not create consumer");
+            .expect("could not create producer");
 
-        Self { producer }
+        Self {
+            producer,
+            topic: topic.to_string(),
+        }
     }
 }
 
-impl<'a> EventsProducerPort for OrdersEventsProducer {
-    fn publish(&self, e: Events) {
+#[async_trait]
+impl EventsProducerPort for OrdersEventsProducer {
+    async fn publish(&self, e: Events, occurred_at: SystemTime) -> Result<(), ProducerError> {
+        // Events of one order share a partition, so consumers see them in order.
+        let key = match &e {
+            Events::Order(event) => event.order_id().0.to_string(),
+        };
         let payload = match e {
             Events::Order(event) => match event {
                 OrderEvent::Created { 0: e } => OrderCreatedIntegrationEvent {
                     event_id: e.id.0.to_string(),
                     event_type: e.name,
-                    occurred_at: Some(Timestamp::from(SystemTime::now())),
+                    occurred_at: Some(Timestamp::from(occurred_at)),
+                    order_id: e.order_id.0.to_string(),
+                }
+                .encode_to_vec(),
+                OrderEvent::PickedUp { 0: e } => OrderPickedUpIntegrationEvent {
+                    event_id: e.id.0.to_string(),
+                    event_type: e.name,
                     order_id: e.order_id.0.to_string(),
+                    courier_id: e.courier_id.0.to_string(),
+                    occurred_at: Some(Timestamp::from(occurred_at)),
                 }
                 .encode_to_vec(),
                 OrderEvent::Completed { 0: e } => OrderCompletedIntegrationEvent {
@@ -46,17 +70,38 @@ impl<'a> EventsProducerPort for OrdersEventsProducer {
                     event_type: e.name,
                     order_id: e.order_id.0.to_string(),
                     courier_id: e.courier_id.0.to_string(),
-                    occurred_at: Some(Timestamp::from(SystemTime::now())),
+                    occurred_at: Some(Timestamp::from(occurred_at)),
+                }
+                .encode_to_vec(),
+                OrderEvent::Cancelled { 0: e } => OrderCancelledIntegrationEvent {
+                    event_id: e.id.0.to_string(),
+                    event_type: e.name,
+                    order_id: e.order_id.0.to_string(),
+                    courier_id: e.courier_id.map(|c| c.0.to_string()).unwrap_or_default(),
+                    occurred_at: Some(Timestamp::from(occurred_at)),
                 }
                 .encode_to_vec(),
+                OrderEvent::DeliveryWindowMissed { 0: e } => {
+                    OrderDeliveryWindowMissedIntegrationEvent {
+                        event_id: e.id.0.to_string(),
+                        event_type: e.name,
+                        order_id: e.order_id.0.to_string(),
+                        occurred_at: Some(Timestamp::from(occurred_at)),
+                    }
+                    .encode_to_vec()
+                }
             },
         };
 
-        if let Err((error, _)) = self
-            .producer
-            .send_result(FutureRecord::<'a, Vec<u8>, Vec<u8>>::to(TOPIC).payload(&payload))
-        {
-            tracing::error!(?error, "failed to enqueue orders event to kafka");
-        }
+        self.producer
+            .send(
+                FutureRecord::<'_, str, Vec<u8>>::to(&self.topic)
+                    .key(key.as_str())
+                    .payload(&payload),
+                Timeout::After(DELIVERY_TIMEOUT),
+            )
+            .await
+            .map(|_| ())
+            .map_err(|(error, _)| ProducerError::DeliveryError(error.to_string()))
     }
 }
@@ -0,0 +1,33 @@
+use rdkafka::ClientConfig;
+
+pub struct KafkaProducerOptions {
+    brokers: String,
+    properties: Vec<(String, String)>,
+}
+
+impl KafkaProducerOptions {
+    pub fn new(brokers: String, properties: Vec<(String, String)>) -> Self {
+        Self {
+            brokers,
+            properties,
+        }
+    }
+
+    pub fn brokers(&self) -> String {
+        self.brokers.clone()
+    }
+    pub fn properties(&self) -> &[(String, String)] {
+        &self.properties
+    }
+
+    pub fn client_config(&self) -> ClientConfig {
+        let mut config = ClientConfig::new();
+        config.set("bootstrap.servers", self.brokers());
+
+        for (key, value) in self.properties() {
+            config.set(key, value);
+        }
+
+        config
+    }
+}
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "orders"
+	DROP COLUMN IF EXISTS "delivery_window_start",
+	DROP COLUMN IF EXISTS "delivery_window_end",
+	DROP COLUMN IF EXISTS "delivery_window_missed";
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "orders"
+	ADD COLUMN "delivery_window_start" TIMESTAMP,
+	ADD COLUMN "delivery_window_end" TIMESTAMP,
+	ADD COLUMN "delivery_window_missed" BOOLEAN NOT NULL DEFAULT FALSE;
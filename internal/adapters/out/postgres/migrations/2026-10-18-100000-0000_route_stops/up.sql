@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE "route_stops"(
+	"order_id" UUID NOT NULL PRIMARY KEY,
+	"courier_id" UUID NOT NULL,
+	"position" SMALLINT NOT NULL,
+	"location_x" SMALLINT NOT NULL,
+	"location_y" SMALLINT NOT NULL
+);
+
+CREATE INDEX "route_stops_courier_id_idx" ON "route_stops"("courier_id");
+
+INSERT INTO "route_stops"("order_id", "courier_id", "position", "location_x", "location_y")
+SELECT o."id", sp."courier_id", 0, o."location_x", o."location_y"
+FROM "storage_places" sp
+JOIN "orders" o ON o."id" = sp."order_id";
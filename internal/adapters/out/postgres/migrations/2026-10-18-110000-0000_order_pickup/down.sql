@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM "route_stops" WHERE "kind" <> 'drop_off';
+ALTER TABLE "route_stops" DROP CONSTRAINT "route_stops_pkey";
+ALTER TABLE "route_stops" DROP COLUMN IF EXISTS "kind";
+ALTER TABLE "route_stops" ADD PRIMARY KEY ("order_id");
+
+UPDATE "orders" SET "status" = 'assigned' WHERE "status" = 'picked_up';
+ALTER TABLE "orders"
+	DROP COLUMN IF EXISTS "pickup_location_x",
+	DROP COLUMN IF EXISTS "pickup_location_y";
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE "orders"
+	ADD COLUMN "pickup_location_x" SMALLINT,
+	ADD COLUMN "pickup_location_y" SMALLINT;
+
+ALTER TABLE "route_stops" ADD COLUMN "kind" TEXT NOT NULL DEFAULT 'drop_off';
+ALTER TABLE "route_stops" DROP CONSTRAINT "route_stops_pkey";
+ALTER TABLE "route_stops" ADD PRIMARY KEY ("order_id", "kind");
@@ -0,0 +1,14 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "route_stops"
+	ALTER COLUMN "location_x" TYPE SMALLINT,
+	ALTER COLUMN "location_y" TYPE SMALLINT;
+
+ALTER TABLE "orders"
+	ALTER COLUMN "location_x" TYPE SMALLINT,
+	ALTER COLUMN "location_y" TYPE SMALLINT,
+	ALTER COLUMN "pickup_location_x" TYPE SMALLINT,
+	ALTER COLUMN "pickup_location_y" TYPE SMALLINT;
+
+ALTER TABLE "couriers"
+	ALTER COLUMN "location_x" TYPE SMALLINT,
+	ALTER COLUMN "location_y" TYPE SMALLINT;
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE "couriers"
+	ALTER COLUMN "location_x" TYPE INTEGER,
+	ALTER COLUMN "location_y" TYPE INTEGER;
+
+ALTER TABLE "orders"
+	ALTER COLUMN "location_x" TYPE INTEGER,
+	ALTER COLUMN "location_y" TYPE INTEGER,
+	ALTER COLUMN "pickup_location_x" TYPE INTEGER,
+	ALTER COLUMN "pickup_location_y" TYPE INTEGER;
+
+ALTER TABLE "route_stops"
+	ALTER COLUMN "location_x" TYPE INTEGER,
+	ALTER COLUMN "location_y" TYPE INTEGER;
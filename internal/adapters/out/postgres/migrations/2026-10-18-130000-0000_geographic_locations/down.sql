@@ -0,0 +1,23 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM "route_stops" WHERE "location_x" IS NULL;
+ALTER TABLE "route_stops"
+	DROP COLUMN IF EXISTS "latitude",
+	DROP COLUMN IF EXISTS "longitude",
+	ALTER COLUMN "location_x" SET NOT NULL,
+	ALTER COLUMN "location_y" SET NOT NULL;
+
+DELETE FROM "orders" WHERE "location_x" IS NULL;
+ALTER TABLE "orders"
+	DROP COLUMN IF EXISTS "latitude",
+	DROP COLUMN IF EXISTS "longitude",
+	DROP COLUMN IF EXISTS "pickup_latitude",
+	DROP COLUMN IF EXISTS "pickup_longitude",
+	ALTER COLUMN "location_x" SET NOT NULL,
+	ALTER COLUMN "location_y" SET NOT NULL;
+
+DELETE FROM "couriers" WHERE "location_x" IS NULL;
+ALTER TABLE "couriers"
+	DROP COLUMN IF EXISTS "latitude",
+	DROP COLUMN IF EXISTS "longitude",
+	ALTER COLUMN "location_x" SET NOT NULL,
+	ALTER COLUMN "location_y" SET NOT NULL;
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+ALTER TABLE "couriers"
+	ALTER COLUMN "location_x" DROP NOT NULL,
+	ALTER COLUMN "location_y" DROP NOT NULL,
+	ADD COLUMN "latitude" DOUBLE PRECISION,
+	ADD COLUMN "longitude" DOUBLE PRECISION;
+
+ALTER TABLE "orders"
+	ALTER COLUMN "location_x" DROP NOT NULL,
+	ALTER COLUMN "location_y" DROP NOT NULL,
+	ADD COLUMN "latitude" DOUBLE PRECISION,
+	ADD COLUMN "longitude" DOUBLE PRECISION,
+	ADD COLUMN "pickup_latitude" DOUBLE PRECISION,
+	ADD COLUMN "pickup_longitude" DOUBLE PRECISION;
+
+ALTER TABLE "route_stops"
+	ALTER COLUMN "location_x" DROP NOT NULL,
+	ALTER COLUMN "location_y" DROP NOT NULL,
+	ADD COLUMN "latitude" DOUBLE PRECISION,
+	ADD COLUMN "longitude" DOUBLE PRECISION;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE TABLE "blocked_cells"(
+	"x" INTEGER NOT NULL,
+	"y" INTEGER NOT NULL,
+	PRIMARY KEY ("x", "y")
+);
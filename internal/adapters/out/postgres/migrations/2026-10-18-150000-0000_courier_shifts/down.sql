@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "couriers"
+	DROP COLUMN IF EXISTS "status",
+	DROP COLUMN IF EXISTS "shift_start",
+	DROP COLUMN IF EXISTS "shift_end",
+	DROP COLUMN IF EXISTS "ending_shift";
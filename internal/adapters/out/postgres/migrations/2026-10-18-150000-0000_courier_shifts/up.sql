@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE "couriers"
+	ADD COLUMN "status" TEXT NOT NULL DEFAULT 'available',
+	ADD COLUMN "shift_start" TIMESTAMP,
+	ADD COLUMN "shift_end" TIMESTAMP,
+	ADD COLUMN "ending_shift" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "order_history";
+
+ALTER TABLE "orders"
+	DROP COLUMN IF EXISTS "created_at";
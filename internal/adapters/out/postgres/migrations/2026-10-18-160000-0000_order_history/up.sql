@@ -0,0 +1,19 @@
+-- Your SQL goes here
+ALTER TABLE "orders"
+	ADD COLUMN "created_at" TIMESTAMP NOT NULL DEFAULT (now() AT TIME ZONE 'utc');
+
+CREATE INDEX "orders_created_at_idx" ON "orders"("created_at");
+
+CREATE TABLE "order_history"(
+	"id" BIGSERIAL NOT NULL PRIMARY KEY,
+	"order_id" UUID NOT NULL,
+	"status" TEXT NOT NULL,
+	"courier_id" UUID,
+	"occurred_at" TIMESTAMP NOT NULL
+);
+
+CREATE INDEX "order_history_order_id_idx" ON "order_history"("order_id", "occurred_at");
+
+INSERT INTO "order_history"("order_id", "status", "courier_id", "occurred_at")
+SELECT "id", "status", "courier_id", "created_at"
+FROM "orders";
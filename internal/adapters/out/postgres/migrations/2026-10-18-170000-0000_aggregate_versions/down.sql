@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "couriers"
+	DROP COLUMN IF EXISTS "version";
+
+ALTER TABLE "orders"
+	DROP COLUMN IF EXISTS "version";
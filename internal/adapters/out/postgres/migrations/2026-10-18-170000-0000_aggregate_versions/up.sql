@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE "orders"
+	ADD COLUMN "version" BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE "couriers"
+	ADD COLUMN "version" BIGINT NOT NULL DEFAULT 0;
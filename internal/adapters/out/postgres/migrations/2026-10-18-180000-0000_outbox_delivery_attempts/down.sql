@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS "outbox_pending_idx";
+
+ALTER TABLE "outbox"
+	DROP COLUMN IF EXISTS "dead_lettered_at",
+	DROP COLUMN IF EXISTS "next_attempt_at",
+	DROP COLUMN IF EXISTS "last_error",
+	DROP COLUMN IF EXISTS "attempts";
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE "outbox"
+	ADD COLUMN "attempts" INTEGER NOT NULL DEFAULT 0,
+	ADD COLUMN "last_error" TEXT,
+	ADD COLUMN "next_attempt_at" TIMESTAMP,
+	ADD COLUMN "dead_lettered_at" TIMESTAMP;
+
+CREATE INDEX "outbox_pending_idx" ON "outbox" ("next_attempt_at")
+	WHERE "processed_at" IS NULL AND "dead_lettered_at" IS NULL;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS "outbox_inserted" ON "outbox";
+
+DROP FUNCTION IF EXISTS "outbox_notify"();
+
+DROP INDEX IF EXISTS "outbox_occured_at_idx";
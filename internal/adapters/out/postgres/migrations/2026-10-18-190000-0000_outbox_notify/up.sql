@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE INDEX "outbox_occured_at_idx" ON "outbox" ("occured_at")
+	WHERE "processed_at" IS NULL AND "dead_lettered_at" IS NULL;
+
+CREATE FUNCTION "outbox_notify"() RETURNS TRIGGER AS $$
+BEGIN
+	PERFORM pg_notify('outbox_inserted', '');
+	RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER "outbox_inserted"
+	AFTER INSERT ON "outbox"
+	FOR EACH STATEMENT EXECUTE FUNCTION "outbox_notify"();
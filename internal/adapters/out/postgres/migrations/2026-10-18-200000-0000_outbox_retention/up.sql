@@ -0,0 +1,3 @@
+-- Your SQL goes here
+CREATE INDEX "outbox_processed_at_idx" ON "outbox" ("processed_at")
+	WHERE "processed_at" IS NOT NULL;
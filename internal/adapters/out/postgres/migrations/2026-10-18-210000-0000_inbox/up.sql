@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE "inbox"(
+	"event_id" UUID NOT NULL PRIMARY KEY,
+	"processed_at" TIMESTAMP NOT NULL
+);
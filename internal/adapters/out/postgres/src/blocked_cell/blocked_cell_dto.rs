@@ -0,0 +1,12 @@
+use diesel::pg::Pg;
+use diesel::prelude::*;
+
+use super::blocked_cell_schema::blocked_cells;
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = blocked_cells)]
+#[diesel(check_for_backend(Pg))]
+pub struct BlockedCellDto {
+    pub x: i32,
+    pub y: i32,
+}
@@ -0,0 +1,15 @@
+use super::blocked_cell_dto::BlockedCellDto;
+
+impl TryFrom<BlockedCellDto> for (u16, u16) {
+    type Error = String;
+
+    fn try_from(dto: BlockedCellDto) -> Result<Self, Self::Error> {
+        match (u16::try_from(dto.x), u16::try_from(dto.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(format!(
+                "blocked cell out of range. x: {}, y: {}",
+                dto.x, dto.y
+            )),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use diesel::PgConnection;
+use diesel::prelude::*;
+use diesel::r2d2::ConnectionManager;
+use ports::blocked_cell_repository_port::BlockedCellRepositoryPort;
+use ports::errors::RepositoryError;
+use r2d2::Pool;
+use r2d2::PooledConnection;
+
+use crate::errors::postgres_error::PostgresError;
+
+use super::blocked_cell_dto::BlockedCellDto;
+use super::blocked_cell_schema::blocked_cells::dsl::*;
+
+#[derive(Clone)]
+pub struct BlockedCellRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl BlockedCellRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { pool }
+    }
+
+    fn get_conn(
+        &mut self,
+    ) -> Result<PooledConnection<ConnectionManager<PgConnection>>, RepositoryError> {
+        self.pool
+            .get()
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)
+    }
+}
+
+impl BlockedCellRepositoryPort for BlockedCellRepository {
+    fn get_all(&mut self) -> Result<Vec<(u16, u16)>, RepositoryError> {
+        let mut conn = self.get_conn()?;
+
+        let rows: Vec<BlockedCellDto> = blocked_cells
+            .select(BlockedCellDto::as_select())
+            .load(&mut conn)
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        rows.into_iter()
+            .map(|row| row.try_into().map_err(RepositoryError::MapError))
+            .collect()
+    }
+}
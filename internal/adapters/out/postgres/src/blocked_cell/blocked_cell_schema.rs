@@ -0,0 +1,6 @@
+diesel::table! {
+    blocked_cells (x, y) {
+        x -> Integer,
+        y -> Integer,
+    }
+}
@@ -0,0 +1,4 @@
+pub mod blocked_cell_dto;
+pub mod blocked_cell_mapper;
+pub mod blocked_cell_repository;
+pub mod blocked_cell_schema;
@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 use super::courier_schema::couriers;
 use diesel::pg::Pg;
 use diesel::prelude::*;
@@ -11,8 +13,15 @@ pub struct CourierDto {
     pub id: Uuid,
     pub name: String,
     pub speed: i16,
-    pub location_x: i16,
-    pub location_y: i16,
+    pub location_x: Option<i32>,
+    pub location_y: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub status: String,
+    pub shift_start: Option<SystemTime>,
+    pub shift_end: Option<SystemTime>,
+    pub ending_shift: bool,
+    pub version: i64,
 }
 
 impl std::fmt::Display for CourierDto {
@@ -2,37 +2,61 @@ use domain::model::courier::courier_aggregate::Courier;
 use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::courier::courier_aggregate::CourierName;
 use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::courier::route_stop::RouteStop;
+use domain::model::courier::shift::CourierStatus;
+use domain::model::courier::shift::Shift;
 use domain::model::courier::storage_place::StoragePlace;
-use domain::model::kernel::location::Location;
 
 use crate::courier::courier_dto::CourierDto;
+use crate::location_columns::LocationColumns;
+use crate::route_stop::route_stop_dto::RouteStopDto;
 use crate::storage_place::storage_place_dto::StoragePlaceDto;
 
 impl From<&Courier> for CourierDto {
     fn from(order: &Courier) -> Self {
+        let location = LocationColumns::from(order.location());
         Self {
             id: order.id().0,
             name: order.name().clone(),
             speed: *order.speed() as i16,
-            location_x: order.location().x() as i16,
-            location_y: order.location().y() as i16,
+            location_x: location.x,
+            location_y: location.y,
+            latitude: location.latitude,
+            longitude: location.longitude,
+            status: order.status().into(),
+            shift_start: order.shift().map(|s| s.start()),
+            shift_end: order.shift().map(|s| s.end()),
+            ending_shift: order.is_ending_shift(),
+            version: order.version() as i64,
         }
     }
 }
 
 impl From<Courier> for CourierDto {
     fn from(order: Courier) -> Self {
+        let location = LocationColumns::from(order.location());
         Self {
             id: order.id().0,
             name: order.name().clone(),
             speed: *order.speed() as i16,
-            location_x: order.location().x() as i16,
-            location_y: order.location().y() as i16,
+            location_x: location.x,
+            location_y: location.y,
+            latitude: location.latitude,
+            longitude: location.longitude,
+            status: order.status().into(),
+            shift_start: order.shift().map(|s| s.start()),
+            shift_end: order.shift().map(|s| s.end()),
+            ending_shift: order.is_ending_shift(),
+            version: order.version() as i64,
         }
     }
 }
 
-pub struct CourierRecord(pub CourierDto, pub Vec<StoragePlaceDto>);
+pub struct CourierRecord(
+    pub CourierDto,
+    pub Vec<StoragePlaceDto>,
+    pub Vec<RouteStopDto>,
+);
 
 impl TryFrom<CourierRecord> for Courier {
     type Error = String;
@@ -40,12 +64,30 @@ impl TryFrom<CourierRecord> for Courier {
     fn try_from(v: CourierRecord) -> Result<Self, Self::Error> {
         let courier_dto = v.0;
         let storage_places_dto = v.1;
+        let mut route_stops_dto = v.2;
+        route_stops_dto.sort_by_key(|stop| stop.position);
 
-        let location = Location::new(courier_dto.location_x as u8, courier_dto.location_y as u8)?;
+        let location = LocationColumns {
+            x: courier_dto.location_x,
+            y: courier_dto.location_y,
+            latitude: courier_dto.latitude,
+            longitude: courier_dto.longitude,
+        }
+        .into_required_location()?;
         let storage_places = storage_places_dto
             .into_iter()
             .map(StoragePlace::try_from)
             .collect::<Result<Vec<_>, _>>()?;
+        let route = route_stops_dto
+            .into_iter()
+            .map(RouteStop::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let status = CourierStatus::try_from(courier_dto.status.as_str())?;
+        let shift = match (courier_dto.shift_start, courier_dto.shift_end) {
+            (Some(start), Some(end)) => Some(Shift::new(start, end)?),
+            (None, None) => None,
+            _ => return Err("incomplete shift".into()),
+        };
 
         Ok(Courier::restore(
             CourierId(courier_dto.id),
@@ -53,6 +95,11 @@ impl TryFrom<CourierRecord> for Courier {
             CourierSpeed(courier_dto.speed as u8),
             location,
             storage_places,
+            route,
+            status,
+            shift,
+            courier_dto.ending_shift,
+            courier_dto.version as u64,
         ))
     }
 }
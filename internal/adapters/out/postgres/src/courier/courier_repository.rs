@@ -8,7 +8,7 @@ use diesel::update;
 use domain::model::courier::courier_aggregate::Courier;
 use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::courier::courier_aggregate::CourierName;
-use domain::model::kernel::location::Location;
+use domain::model::courier::shift::CourierStatus;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::courier_repository_port::GetAllCouriersResponse;
 use ports::errors::RepositoryError;
@@ -18,15 +18,26 @@ use std::ptr::NonNull;
 use uuid::Uuid;
 
 use crate::courier::courier_mapper::CourierRecord;
+use crate::courier::courier_schema::couriers::dsl::ending_shift;
 use crate::courier::courier_schema::couriers::dsl::id;
+use crate::courier::courier_schema::couriers::dsl::latitude;
 use crate::courier::courier_schema::couriers::dsl::location_x;
 use crate::courier::courier_schema::couriers::dsl::location_y;
+use crate::courier::courier_schema::couriers::dsl::longitude;
 use crate::courier::courier_schema::couriers::dsl::name;
+use crate::courier::courier_schema::couriers::dsl::shift_start;
+use crate::courier::courier_schema::couriers::dsl::status;
+use crate::courier::courier_schema::couriers::dsl::version;
 use crate::courier::courier_schema::couriers::dsl::*;
 use crate::courier::courier_schema::couriers::table;
 use crate::errors::postgres_error::PostgresError;
+use crate::location_columns::LocationColumns;
+use crate::route_stop::route_stop_dto::RouteStopDto;
+use crate::route_stop::route_stop_schema::route_stops;
 use crate::storage_place::storage_place_dto::StoragePlaceDto;
+use crate::storage_place::storage_place_schema::storage_places::courier_id as storage_place_courier_id;
 use crate::storage_place::storage_place_schema::storage_places::dsl::*;
+use crate::storage_place::storage_place_schema::storage_places::id as storage_place_id;
 use crate::storage_place::storage_place_schema::storage_places::order_id;
 
 use super::courier_dto::CourierDto;
@@ -73,6 +84,60 @@ impl CourierRepository {
 
         Ok(RepositoryConn::Pooled(conn))
     }
+
+    fn route_to_dto(c: &Courier) -> Vec<RouteStopDto> {
+        c.route()
+            .iter()
+            .enumerate()
+            .map(|(position, stop)| RouteStopDto::from((position, stop, *c.id())))
+            .collect()
+    }
+
+    fn load_routes(
+        conn: &mut PgConnection,
+        courier_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<RouteStopDto>>, RepositoryError> {
+        let rows: Vec<RouteStopDto> = route_stops::table
+            .filter(route_stops::courier_id.eq_any(courier_ids))
+            .order(route_stops::position.asc())
+            .load(conn)
+            .map_err(PostgresError::from)?;
+
+        let mut routes: HashMap<Uuid, Vec<RouteStopDto>> = HashMap::new();
+        for stop in rows {
+            routes.entry(stop.courier_id).or_default().push(stop);
+        }
+
+        Ok(routes)
+    }
+
+    fn into_couriers(
+        conn: &mut PgConnection,
+        rows: Vec<(CourierDto, StoragePlaceDto)>,
+    ) -> Result<Vec<Courier>, RepositoryError> {
+        let mut grouped: HashMap<Uuid, (CourierDto, Vec<StoragePlaceDto>)> = HashMap::new();
+
+        for (c_dto, sp_dto) in rows {
+            grouped
+                .entry(c_dto.id)
+                .or_insert_with(|| (c_dto.clone(), Vec::new()))
+                .1
+                .push(sp_dto);
+        }
+
+        let courier_ids: Vec<Uuid> = grouped.keys().copied().collect();
+        let mut routes = Self::load_routes(conn, &courier_ids)?;
+
+        grouped
+            .into_values()
+            .map(|(c_dto, sp_dtos)| {
+                let route_dtos = routes.remove(&c_dto.id).unwrap_or_default();
+                CourierRecord(c_dto, sp_dtos, route_dtos)
+                    .try_into()
+                    .map_err(RepositoryError::from)
+            })
+            .collect()
+    }
 }
 
 impl CourierRepositoryPort for CourierRepository {
@@ -84,47 +149,87 @@ impl CourierRepositoryPort for CourierRepository {
             .into_iter()
             .map(|f| StoragePlaceDto::from_dto(f, courier_dto.id))
             .collect();
+        let route_stops_dto = Self::route_to_dto(&c);
 
-        let mut conn = self.pool.get().map_err(PostgresError::from)?;
+        let mut connection = self.connection()?;
 
-        conn.transaction(|tx| {
-            insert_into(couriers).values(&courier_dto).execute(tx)?;
+        connection
+            .as_mut()
+            .transaction(|tx| {
+                insert_into(couriers).values(&courier_dto).execute(tx)?;
 
-            insert_into(storage_places)
-                .values(storage_places_dto)
-                .execute(tx)?;
+                insert_into(storage_places)
+                    .values(storage_places_dto)
+                    .execute(tx)?;
 
-            diesel::result::QueryResult::Ok(())
-        })
-        .map_err(PostgresError::from)?;
+                insert_into(route_stops::table)
+                    .values(route_stops_dto)
+                    .execute(tx)?;
+
+                diesel::result::QueryResult::Ok(())
+            })
+            .map_err(PostgresError::from)?;
 
         Ok(())
     }
 
     fn update(&mut self, c: Courier) -> Result<(), RepositoryError> {
-        let courier_dto: CourierDto = c.clone().into();
+        let mut courier_dto: CourierDto = c.clone().into();
+        let loaded_version = courier_dto.version;
+        courier_dto.version += 1;
         let storage_places_dto: Vec<StoragePlaceDto> = c
             .storage_places()
             .to_owned()
             .into_iter()
             .map(|f| StoragePlaceDto::from_dto(f, courier_dto.id))
             .collect();
-        let mut conn = self.pool.get().map_err(PostgresError::from)?;
+        let route_stops_dto = Self::route_to_dto(&c);
+        let mut connection = self.connection()?;
 
-        conn.transaction(|tx| {
-            update(couriers.find(courier_dto.id))
+        connection
+            .as_mut()
+            .transaction(|tx| {
+                let updated = update(
+                    couriers
+                        .find(courier_dto.id)
+                        .filter(version.eq(loaded_version)),
+                )
                 .set(&courier_dto)
                 .execute(tx)?;
+                if updated == 0 {
+                    return Err(PostgresError::Conflict(format!(
+                        "courier {} is no longer at version {}",
+                        courier_dto.id, loaded_version
+                    )));
+                }
+
+                let kept: Vec<Uuid> = storage_places_dto.iter().map(|sp| sp.id).collect();
+                diesel::delete(
+                    storage_places
+                        .filter(storage_place_courier_id.eq(courier_dto.id))
+                        .filter(storage_place_id.ne_all(kept)),
+                )
+                .execute(tx)?;
 
-            if !storage_places_dto.is_empty() {
                 for sp in storage_places_dto {
-                    update(storage_places.find(sp.id)).set(&sp).execute(tx)?;
+                    insert_into(storage_places)
+                        .values(&sp)
+                        .on_conflict(storage_place_id)
+                        .do_update()
+                        .set(&sp)
+                        .execute(tx)?;
                 }
-            }
 
-            diesel::result::QueryResult::Ok(())
-        })
-        .map_err(PostgresError::from)?;
+                diesel::delete(
+                    route_stops::table.filter(route_stops::courier_id.eq(courier_dto.id)),
+                )
+                .execute(tx)?;
+                insert_into(route_stops::table)
+                    .values(route_stops_dto)
+                    .execute(tx)?;
+
+                Ok(())
+            })?;
 
         Ok(())
     }
@@ -152,52 +257,88 @@ impl CourierRepositoryPort for CourierRepository {
             (courier, storage)
         };
 
-        let record = CourierRecord(courier_dto, storage_dtos);
+        let route_dtos = Self::load_routes(connection.as_mut(), &[courier_dto.id])?
+            .remove(&courier_dto.id)
+            .unwrap_or_default();
+        let record = CourierRecord(courier_dto, storage_dtos, route_dtos);
 
         record.try_into().map_err(RepositoryError::from)
     }
 
     fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        let on_duty: [String; 2] = [CourierStatus::Available.into(), CourierStatus::Busy.into()];
+        let in_transaction = self.shared_connection.is_some();
         let mut connection = self.connection()?;
 
+        let candidates = couriers
+            .filter(status.eq_any(on_duty))
+            .filter(ending_shift.eq(false))
+            .filter(
+                id.eq_any(
+                    storage_places
+                        .filter(order_id.is_null())
+                        .select(storage_place_courier_id),
+                ),
+            )
+            .select(id);
+        // Inside a transaction the couriers stay locked until it ends; the ones
+        // another transaction already holds are skipped instead of awaited.
+        let free_ids: Vec<Uuid> = if in_transaction {
+            candidates
+                .for_update()
+                .skip_locked()
+                .load(connection.as_mut())
+        } else {
+            candidates.load(connection.as_mut())
+        }
+        .map_err(PostgresError::from)?;
+
         let rows: Vec<(CourierDto, StoragePlaceDto)> = couriers
             .inner_join(storage_places)
-            .filter(order_id.is_null())
+            .filter(id.eq_any(free_ids))
             .load(connection.as_mut())
             .map_err(PostgresError::from)?;
 
-        let mut grouped: HashMap<Uuid, (CourierDto, Vec<StoragePlaceDto>)> = HashMap::new();
+        Self::into_couriers(connection.as_mut(), rows)
+    }
 
-        for (c_dto, sp_dto) in rows {
-            grouped
-                .entry(c_dto.id)
-                .or_insert_with(|| (c_dto.clone(), Vec::new()))
-                .1
-                .push(sp_dto);
-        }
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        let mut connection = self.connection()?;
 
-        grouped
-            .into_values()
-            .map(|(c_dto, sp_dtos)| {
-                CourierRecord(c_dto, sp_dtos)
-                    .try_into()
-                    .map_err(RepositoryError::from)
-            })
-            .collect()
+        let rows: Vec<(CourierDto, StoragePlaceDto)> = couriers
+            .inner_join(storage_places)
+            .filter(shift_start.is_not_null())
+            .load(connection.as_mut())
+            .map_err(PostgresError::from)?;
+
+        Self::into_couriers(connection.as_mut(), rows)
     }
 
     fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
         let mut connection = self.connection()?;
 
         let rows = table
-            .select((id, name, location_x, location_y))
-            .load::<(Uuid, String, i16, i16)>(connection.as_mut())
+            .select((id, name, location_x, location_y, latitude, longitude))
+            .load::<(
+                Uuid,
+                String,
+                Option<i32>,
+                Option<i32>,
+                Option<f64>,
+                Option<f64>,
+            )>(connection.as_mut())
             .map_err(PostgresError::from)?;
 
         let result: Vec<GetAllCouriersResponse> = rows
             .iter()
             .filter_map(|v| {
-                let location = Location::new(v.2 as u8, v.3 as u8);
+                let location = LocationColumns {
+                    x: v.2,
+                    y: v.3,
+                    latitude: v.4,
+                    longitude: v.5,
+                }
+                .into_required_location();
 
                 match location {
                     Ok(l) => Some(GetAllCouriersResponse {
@@ -3,7 +3,14 @@ diesel::table! {
         id -> Uuid,
         name -> Text,
         speed -> SmallInt,
-        location_x -> SmallInt,
-        location_y -> SmallInt,
+        location_x -> Nullable<Integer>,
+        location_y -> Nullable<Integer>,
+        latitude -> Nullable<Double>,
+        longitude -> Nullable<Double>,
+        status -> Text,
+        shift_start -> Nullable<Timestamp>,
+        shift_end -> Nullable<Timestamp>,
+        ending_shift -> Bool,
+        version -> BigInt,
     }
 }
@@ -1,3 +1,4 @@
+use diesel::result::DatabaseErrorKind;
 use diesel::result::Error as DieselError;
 use ports::errors::RepositoryError;
 use r2d2::Error as R2D2Error;
@@ -7,6 +8,7 @@ pub enum PostgresError {
     Diesel(DieselError),
     R2D2(R2D2Error),
     Map(String),
+    Conflict(String),
 }
 
 impl From<DieselError> for PostgresError {
@@ -24,9 +26,13 @@ impl From<R2D2Error> for PostgresError {
 impl From<PostgresError> for RepositoryError {
     fn from(err: PostgresError) -> Self {
         match err {
+            PostgresError::Diesel(
+                e @ DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _),
+            ) => RepositoryError::AlreadyExists(e.to_string()),
             PostgresError::Diesel(e) => RepositoryError::DatabaseError(e.to_string()),
             PostgresError::Map(msg) => RepositoryError::MapError(msg),
             PostgresError::R2D2(e) => RepositoryError::DatabaseError(e.to_string()),
+            PostgresError::Conflict(msg) => RepositoryError::Conflict(msg),
         }
     }
 }
@@ -0,0 +1,15 @@
+use std::time::SystemTime;
+
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::inbox_schema::inbox;
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = inbox)]
+#[diesel(check_for_backend(Pg))]
+pub struct InboxDto {
+    pub event_id: Uuid,
+    pub processed_at: SystemTime,
+}
@@ -0,0 +1,98 @@
+use diesel::PgConnection;
+use diesel::dsl::insert_into;
+use diesel::prelude::*;
+use diesel::r2d2::ConnectionManager;
+use domain::model::kernel::event::EventId;
+use ports::errors::RepositoryError;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use r2d2::Pool;
+use r2d2::PooledConnection;
+use std::ops::DerefMut;
+use std::ptr::NonNull;
+use std::time::SystemTime;
+
+use crate::errors::postgres_error::PostgresError;
+use crate::inbox::inbox_dto::InboxDto;
+
+use super::inbox_schema::inbox::dsl::*;
+
+pub struct InboxRepository {
+    pool: Pool<ConnectionManager<PgConnection>>,
+    shared_connection: Option<NonNull<PgConnection>>,
+}
+
+// SAFETY: `shared_connection` is only accessed via `&mut self`, preventing cross-thread use.
+unsafe impl Send for InboxRepository {}
+
+impl InboxRepository {
+    pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self {
+            pool,
+            shared_connection: None,
+        }
+    }
+
+    pub fn with_shared_connection(
+        pool: Pool<ConnectionManager<PgConnection>>,
+        connection: NonNull<PgConnection>,
+    ) -> Self {
+        Self {
+            pool,
+            shared_connection: Some(connection),
+        }
+    }
+
+    fn connection(&mut self) -> Result<RepositoryConn<'_>, RepositoryError> {
+        if let Some(conn_ptr) = self.shared_connection {
+            // SAFETY: conn_ptr originates from an active transaction and remains valid
+            // while the transaction closure executes.
+            let conn = unsafe { &mut *conn_ptr.as_ptr() };
+            return Ok(RepositoryConn::Borrowed(conn));
+        }
+
+        let conn = self
+            .pool
+            .get()
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(RepositoryConn::Pooled(conn))
+    }
+}
+
+impl InboxRepositoryPort for InboxRepository {
+    /// A concurrent transaction inserting the same id blocks here until it
+    /// finishes, so only one of two racing deliveries is ever recorded.
+    fn try_record(&mut self, event: EventId) -> Result<bool, RepositoryError> {
+        let dto = InboxDto {
+            event_id: event.0,
+            processed_at: SystemTime::now(),
+        };
+
+        let mut connection = self.connection()?;
+
+        let inserted = insert_into(inbox)
+            .values(&dto)
+            .on_conflict(event_id)
+            .do_nothing()
+            .execute(connection.as_mut())
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(inserted == 1)
+    }
+}
+
+enum RepositoryConn<'a> {
+    Borrowed(&'a mut PgConnection),
+    Pooled(PooledConnection<ConnectionManager<PgConnection>>),
+}
+
+impl<'a> RepositoryConn<'a> {
+    fn as_mut(&mut self) -> &mut PgConnection {
+        match self {
+            RepositoryConn::Borrowed(conn) => conn,
+            RepositoryConn::Pooled(conn) => conn.deref_mut(),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+diesel::table! {
+    inbox (event_id) {
+        event_id -> Uuid,
+        processed_at -> Timestamp,
+    }
+}
@@ -0,0 +1,3 @@
+pub mod inbox_dto;
+pub mod inbox_repository;
+pub mod inbox_schema;
@@ -1,8 +1,13 @@
+pub mod blocked_cell;
 pub mod connection;
 pub mod courier;
 pub mod errors;
+pub mod inbox;
+pub mod location_columns;
 pub mod order;
+pub mod order_history;
 pub mod outbox;
+pub mod route_stop;
 pub mod storage_place;
 pub mod unit_of_work;
 pub use diesel::PgConnection;
@@ -0,0 +1,66 @@
+use domain::errors::domain_model_errors::DomainModelError;
+use domain::model::kernel::coordinate_system::GeoArea;
+use domain::model::kernel::grid::GridBounds;
+use domain::model::kernel::location::Coordinates;
+use domain::model::kernel::location::Location;
+
+/// A location spread over its `*_x`/`*_y` and `*latitude`/`*longitude`
+/// columns. Grid locations fill the first pair, geographic ones the second.
+#[derive(Default)]
+pub struct LocationColumns {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl From<&Location> for LocationColumns {
+    fn from(location: &Location) -> Self {
+        match location.coordinates() {
+            Coordinates::Grid { x, y } => Self {
+                x: Some(x.into()),
+                y: Some(y.into()),
+                ..Self::default()
+            },
+            Coordinates::Geographic {
+                latitude,
+                longitude,
+            } => Self {
+                latitude: Some(latitude),
+                longitude: Some(longitude),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+impl From<Option<&Location>> for LocationColumns {
+    fn from(location: Option<&Location>) -> Self {
+        location.map(Self::from).unwrap_or_default()
+    }
+}
+
+impl LocationColumns {
+    pub fn into_location(self) -> Result<Option<Location>, DomainModelError> {
+        match (self.x, self.y, self.latitude, self.longitude) {
+            (None, None, None, None) => Ok(None),
+            // Cells were checked against the configured grid when written.
+            (Some(x), Some(y), None, None) => {
+                Location::try_from_raw(x, y, &GridBounds::MAX).map(Some)
+            }
+            // Couriers moving along a great circle may leave the configured
+            // area slightly, so stored points are only checked for validity.
+            (None, None, Some(latitude), Some(longitude)) => {
+                Location::new_geographic_within(latitude, longitude, &GeoArea::WORLD).map(Some)
+            }
+            _ => Err(DomainModelError::MapError(
+                "incomplete location columns".to_string(),
+            )),
+        }
+    }
+
+    pub fn into_required_location(self) -> Result<Location, DomainModelError> {
+        self.into_location()?
+            .ok_or_else(|| DomainModelError::MapError("missing location".to_string()))
+    }
+}
@@ -1,18 +1,31 @@
+use std::time::SystemTime;
+
 use diesel::pg::Pg;
 use diesel::prelude::*;
 use uuid::Uuid;
 
 use super::order_schema::orders;
 
-#[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset, QueryableByName)]
+#[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
 #[diesel(table_name = orders)]
 #[diesel(treat_none_as_default_value = false)]
 #[diesel(check_for_backend(Pg))]
 pub struct OrderDto {
     pub id: Uuid,
     pub courier_id: Option<Uuid>,
-    pub location_x: i16,
-    pub location_y: i16,
+    pub location_x: Option<i32>,
+    pub location_y: Option<i32>,
     pub volume: i16,
     pub status: String,
+    pub delivery_window_start: Option<SystemTime>,
+    pub delivery_window_end: Option<SystemTime>,
+    pub delivery_window_missed: bool,
+    pub pickup_location_x: Option<i32>,
+    pub pickup_location_y: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub pickup_latitude: Option<f64>,
+    pub pickup_longitude: Option<f64>,
+    pub created_at: SystemTime,
+    pub version: i64,
 }
@@ -1,21 +1,35 @@
 use domain::model::courier::courier_aggregate::CourierId;
-use domain::model::kernel::location::Location;
 use domain::model::kernel::volume::Volume;
+use domain::model::order::delivery_window::DeliveryWindow;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
 use domain::model::order::order_aggregate::OrderStatus;
 
+use crate::location_columns::LocationColumns;
 use crate::order::order_dto::OrderDto;
 
 impl From<&Order> for OrderDto {
     fn from(order: &Order) -> Self {
+        let location = LocationColumns::from(order.location());
+        let pickup = LocationColumns::from(order.pickup_location());
         Self {
             id: order.id().value(),
             courier_id: order.courier_id().map(|c| c.0),
-            location_x: order.location().x() as i16,
-            location_y: order.location().y() as i16,
+            location_x: location.x,
+            location_y: location.y,
             volume: order.volume() as i16,
             status: order.status().into(),
+            delivery_window_start: order.delivery_window().map(|w| w.start()),
+            delivery_window_end: order.delivery_window().map(|w| w.end()),
+            delivery_window_missed: order.is_delivery_window_missed(),
+            pickup_location_x: pickup.x,
+            pickup_location_y: pickup.y,
+            latitude: location.latitude,
+            longitude: location.longitude,
+            pickup_latitude: pickup.latitude,
+            pickup_longitude: pickup.longitude,
+            created_at: order.created_at(),
+            version: order.version() as i64,
         }
     }
 }
@@ -24,18 +38,42 @@ impl TryFrom<OrderDto> for Order {
     type Error = String;
 
     fn try_from(row: OrderDto) -> Result<Self, Self::Error> {
-        let status = match row.status.as_str() {
-            "created" => OrderStatus::Created,
-            "assigned" => OrderStatus::Assigned,
-            "completed" => OrderStatus::Completed,
-            _ => return Err("invalid status".into()),
-        };
+        let status = OrderStatus::try_from(row.status.as_str())?;
 
         let id = OrderId::new(row.id);
         let volume = Volume::new(row.volume as u16)?;
-        let location = Location::new(row.location_x as u8, row.location_y as u8)?;
+        let location = LocationColumns {
+            x: row.location_x,
+            y: row.location_y,
+            latitude: row.latitude,
+            longitude: row.longitude,
+        }
+        .into_required_location()?;
         let courier_id = row.courier_id.map(CourierId);
+        let delivery_window = match (row.delivery_window_start, row.delivery_window_end) {
+            (Some(start), Some(end)) => Some(DeliveryWindow::new(start, end)?),
+            (None, None) => None,
+            _ => return Err("incomplete delivery window".into()),
+        };
+        let pickup_location = LocationColumns {
+            x: row.pickup_location_x,
+            y: row.pickup_location_y,
+            latitude: row.pickup_latitude,
+            longitude: row.pickup_longitude,
+        }
+        .into_location()?;
 
-        Ok(Order::restore(id, courier_id, location, volume, status))
+        Ok(Order::restore(
+            id,
+            courier_id,
+            pickup_location,
+            location,
+            volume,
+            status,
+            delivery_window,
+            row.delivery_window_missed,
+            row.created_at,
+            row.version as u64,
+        ))
     }
 }
@@ -1,22 +1,32 @@
 use diesel::PgConnection;
 use diesel::insert_into;
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::r2d2::ConnectionManager;
 use diesel::r2d2::Pool;
 use diesel::r2d2::PooledConnection;
-use diesel::sql_query;
 use diesel::update;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
 use domain::model::order::order_aggregate::OrderStatus;
 use ports::errors::RepositoryError;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::order_repository_port::OrderSort;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
+use std::time::SystemTime;
+use uuid::Uuid;
 
 use crate::errors::postgres_error::PostgresError;
+use crate::order_history::order_history_dto::NewOrderHistoryDto;
+use crate::order_history::order_history_dto::OrderHistoryDto;
+use crate::order_history::order_history_schema::order_history;
 
 use super::order_dto::OrderDto;
+use super::order_schema::orders;
 use super::order_schema::orders::dsl::*;
 
 pub struct OrderRepository {
@@ -61,6 +71,69 @@ impl OrderRepository {
 
         Ok(RepositoryConn::Pooled(conn))
     }
+
+    fn filtered(filter: &OrderFilter) -> orders::BoxedQuery<'_, Pg> {
+        let mut query = orders.into_boxed();
+        if !filter.statuses.is_empty() {
+            let statuses: Vec<String> = filter.statuses.iter().map(|s| (*s).into()).collect();
+            query = query.filter(status.eq_any(statuses));
+        }
+        if let Some(courier) = filter.courier_id {
+            query = query.filter(courier_id.eq(courier.0));
+        }
+        if let Some(from) = filter.created_from {
+            query = query.filter(created_at.ge(from));
+        }
+        if let Some(before) = filter.created_before {
+            query = query.filter(created_at.lt(before));
+        }
+        query
+    }
+
+    fn sorted(criteria: &OrderCriteria) -> orders::BoxedQuery<'_, Pg> {
+        let mut query = Self::filtered(&criteria.filter);
+        query = match criteria.sort {
+            OrderSort::OldestFirst => query.order((created_at.asc(), id.asc())),
+            OrderSort::NewestFirst => query.order((created_at.desc(), id.asc())),
+        };
+        if criteria.offset > 0 {
+            query = query.offset(i64::from(criteria.offset));
+        }
+        if let Some(limit) = criteria.limit {
+            query = query.limit(i64::from(limit));
+        }
+        query
+    }
+
+    /// Locks the matching orders no other transaction holds, so concurrent
+    /// dispatchers never pick the same order. Boxed queries cannot be locked,
+    /// hence the candidates are read first and locked by id.
+    fn lock_matching(
+        conn: &mut PgConnection,
+        criteria: &OrderCriteria,
+    ) -> QueryResult<Vec<OrderDto>> {
+        let candidates: Vec<Uuid> = Self::filtered(&criteria.filter).select(id).load(conn)?;
+        let locked = orders.filter(id.eq_any(candidates));
+        let limit = criteria.limit.map_or(i64::MAX, i64::from);
+        let offset = i64::from(criteria.offset);
+
+        match criteria.sort {
+            OrderSort::OldestFirst => locked
+                .order((created_at.asc(), id.asc()))
+                .limit(limit)
+                .offset(offset)
+                .for_update()
+                .skip_locked()
+                .load(conn),
+            OrderSort::NewestFirst => locked
+                .order((created_at.desc(), id.asc()))
+                .limit(limit)
+                .offset(offset)
+                .for_update()
+                .skip_locked()
+                .load(conn),
+        }
+    }
 }
 
 impl OrderRepositoryPort for OrderRepository {
@@ -68,22 +141,55 @@ impl OrderRepositoryPort for OrderRepository {
         let dto: OrderDto = order.into();
         let mut connection = self.connection()?;
 
-        let _ = insert_into(orders)
-            .values(&dto)
-            .execute(connection.as_mut())
+        let entry = NewOrderHistoryDto::record(order, order.created_at());
+
+        connection
+            .as_mut()
+            .transaction(|tx| {
+                insert_into(orders).values(&dto).execute(tx)?;
+                insert_into(order_history::table)
+                    .values(&entry)
+                    .execute(tx)?;
+
+                diesel::result::QueryResult::Ok(())
+            })
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
         Ok(())
     }
 
     fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
-        let dto: OrderDto = order.into();
+        let mut dto: OrderDto = order.into();
+        let loaded_version = dto.version;
+        dto.version += 1;
         let mut connection = self.connection()?;
 
-        update(orders.find(dto.id))
-            .set(&dto)
-            .execute(connection.as_mut())
-            .map_err(PostgresError::from)
+        let entry = NewOrderHistoryDto::record(order, SystemTime::now());
+
+        connection
+            .as_mut()
+            .transaction(|tx| {
+                let previous: (String, Option<Uuid>) =
+                    orders.find(dto.id).select((status, courier_id)).first(tx)?;
+
+                let updated = update(orders.find(dto.id).filter(version.eq(loaded_version)))
+                    .set(&dto)
+                    .execute(tx)?;
+                if updated == 0 {
+                    return Err(PostgresError::Conflict(format!(
+                        "order {} is no longer at version {}",
+                        dto.id, loaded_version
+                    )));
+                }
+
+                if previous != (entry.status.clone(), entry.courier_id) {
+                    insert_into(order_history::table)
+                        .values(&entry)
+                        .execute(tx)?;
+                }
+
+                Ok(())
+            })
             .map_err(RepositoryError::from)?;
         Ok(())
     }
@@ -115,11 +221,11 @@ impl OrderRepositoryPort for OrderRepository {
     }
 
     fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError> {
-        let s: String = OrderStatus::Assigned.into();
+        let statuses: [String; 2] = [OrderStatus::Assigned.into(), OrderStatus::PickedUp.into()];
         let mut connection = self.connection()?;
 
         let rows: Vec<OrderDto> = orders
-            .filter(status.eq(s))
+            .filter(status.eq_any(statuses))
             .load(connection.as_mut())
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
@@ -132,10 +238,50 @@ impl OrderRepositoryPort for OrderRepository {
         result
     }
 
-    fn raw(&mut self, query: String) -> Result<Vec<Order>, RepositoryError> {
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        let in_transaction = self.shared_connection.is_some();
+        let mut connection = self.connection()?;
+
+        let rows = if in_transaction {
+            Self::lock_matching(connection.as_mut(), criteria)
+        } else {
+            Self::sorted(criteria).load(connection.as_mut())
+        }
+        .map_err(PostgresError::from)
+        .map_err(RepositoryError::from)?;
+
+        let found: Vec<Order> = rows
+            .into_iter()
+            .map(|dto| dto.try_into().map_err(RepositoryError::MapError))
+            .collect::<Result<_, _>>()?;
+
+        // A row changed by a transaction that committed after the candidates
+        // were read may no longer match.
+        Ok(found
+            .into_iter()
+            .filter(|order| criteria.filter.matches(order))
+            .collect())
+    }
+
+    fn count(&mut self, filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        let mut connection = self.connection()?;
+
+        let total: i64 = Self::filtered(filter)
+            .count()
+            .get_result(connection.as_mut())
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(total as u64)
+    }
+
+    fn get_history(&mut self, order: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
         let mut connection = self.connection()?;
 
-        let rows: Vec<OrderDto> = sql_query(query)
+        let rows: Vec<OrderHistoryDto> = order_history::table
+            .filter(order_history::order_id.eq(order.value()))
+            .order((order_history::occurred_at.asc(), order_history::id.asc()))
+            .select(OrderHistoryDto::as_select())
             .load(connection.as_mut())
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
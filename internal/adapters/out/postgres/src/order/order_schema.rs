@@ -2,9 +2,20 @@ diesel::table! {
     orders {
         id -> Uuid,
         courier_id -> Nullable<Uuid>,
-        location_x -> SmallInt,
-        location_y -> SmallInt,
+        location_x -> Nullable<Integer>,
+        location_y -> Nullable<Integer>,
         volume -> SmallInt,
         status -> Text,
+        delivery_window_start -> Nullable<Timestamp>,
+        delivery_window_end -> Nullable<Timestamp>,
+        delivery_window_missed -> Bool,
+        pickup_location_x -> Nullable<Integer>,
+        pickup_location_y -> Nullable<Integer>,
+        latitude -> Nullable<Double>,
+        longitude -> Nullable<Double>,
+        pickup_latitude -> Nullable<Double>,
+        pickup_longitude -> Nullable<Double>,
+        created_at -> Timestamp,
+        version -> BigInt,
     }
 }
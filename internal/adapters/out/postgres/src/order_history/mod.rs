@@ -0,0 +1,3 @@
+pub mod order_history_dto;
+pub mod order_history_mapper;
+pub mod order_history_schema;
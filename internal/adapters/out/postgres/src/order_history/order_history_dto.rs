@@ -0,0 +1,28 @@
+use std::time::SystemTime;
+
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::order_history_schema::order_history;
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = order_history)]
+#[diesel(check_for_backend(Pg))]
+pub struct OrderHistoryDto {
+    pub id: i64,
+    pub order_id: Uuid,
+    pub status: String,
+    pub courier_id: Option<Uuid>,
+    pub occurred_at: SystemTime,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = order_history)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewOrderHistoryDto {
+    pub order_id: Uuid,
+    pub status: String,
+    pub courier_id: Option<Uuid>,
+    pub occurred_at: SystemTime,
+}
@@ -0,0 +1,32 @@
+use std::time::SystemTime;
+
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderStatus;
+use ports::order_repository_port::OrderHistoryEntry;
+
+use super::order_history_dto::NewOrderHistoryDto;
+use super::order_history_dto::OrderHistoryDto;
+
+impl NewOrderHistoryDto {
+    pub fn record(order: &Order, occurred_at: SystemTime) -> Self {
+        Self {
+            order_id: order.id().value(),
+            status: order.status().into(),
+            courier_id: order.courier_id().map(|c| c.0),
+            occurred_at,
+        }
+    }
+}
+
+impl TryFrom<OrderHistoryDto> for OrderHistoryEntry {
+    type Error = String;
+
+    fn try_from(dto: OrderHistoryDto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            status: OrderStatus::try_from(dto.status.as_str())?,
+            courier_id: dto.courier_id.map(CourierId),
+            occurred_at: dto.occurred_at,
+        })
+    }
+}
@@ -0,0 +1,9 @@
+diesel::table! {
+    order_history {
+        id -> BigInt,
+        order_id -> Uuid,
+        status -> Text,
+        courier_id -> Nullable<Uuid>,
+        occurred_at -> Timestamp,
+    }
+}
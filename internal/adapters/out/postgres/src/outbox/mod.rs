@@ -1,4 +1,5 @@
 pub mod outbox_dto;
+pub mod outbox_listener;
 pub mod outbox_mapper;
 pub mod outbox_repository;
 pub mod outbox_schema;
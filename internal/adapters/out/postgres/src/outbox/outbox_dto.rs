@@ -16,4 +16,8 @@ pub struct OutboxDto {
     pub payload: String,
     pub occured_at: SystemTime,
     pub processed_at: Option<SystemTime>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<SystemTime>,
+    pub dead_lettered_at: Option<SystemTime>,
 }
@@ -0,0 +1,84 @@
+use diesel::PgConnection;
+use diesel::RunQueryDsl;
+use diesel::r2d2::ConnectionManager;
+use diesel::sql_query;
+use ports::errors::RepositoryError;
+use ports::outbox_repository::OutboxListenerPort;
+use r2d2::Pool;
+use r2d2::PooledConnection;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::warn;
+
+use crate::errors::postgres_error::PostgresError;
+
+/// Channel the `outbox_inserted` trigger notifies on every insert statement.
+pub const OUTBOX_CHANNEL: &str = "outbox_inserted";
+
+/// libpq only hands out notifications that already reached the socket, so the
+/// listener checks its buffer at this pace instead of blocking on it.
+const NOTIFICATION_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Holds a pooled connection subscribed to [`OUTBOX_CHANNEL`] for its whole
+/// life, and unsubscribes it before it goes back to the pool.
+pub struct OutboxListener {
+    connection: PooledConnection<ConnectionManager<PgConnection>>,
+}
+
+impl OutboxListener {
+    pub fn new(pool: &Pool<ConnectionManager<PgConnection>>) -> Result<Self, RepositoryError> {
+        let mut connection = pool
+            .get()
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        sql_query(format!("LISTEN {}", OUTBOX_CHANNEL))
+            .execute(&mut connection)
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(Self { connection })
+    }
+
+    fn drain_notifications(&mut self) -> Result<bool, RepositoryError> {
+        let mut notified = false;
+        for notification in self.connection.notifications_iter() {
+            notification
+                .map_err(PostgresError::from)
+                .map_err(RepositoryError::from)?;
+            notified = true;
+        }
+
+        Ok(notified)
+    }
+}
+
+impl Drop for OutboxListener {
+    fn drop(&mut self) {
+        if let Err(err) = sql_query("UNLISTEN *").execute(&mut self.connection) {
+            warn!(?err, "could not unlisten outbox listener connection");
+        }
+        // Whatever arrived before the UNLISTEN would wait for the next borrower.
+        let _ = self.drain_notifications();
+    }
+}
+
+impl OutboxListenerPort for OutboxListener {
+    fn wait_for_messages(&mut self, timeout: Duration) -> Result<bool, RepositoryError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if self.drain_notifications()? {
+                return Ok(true);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+
+            thread::sleep(NOTIFICATION_CHECK_INTERVAL.min(deadline - now));
+        }
+    }
+}
@@ -10,6 +10,10 @@ impl From<Message> for OutboxDto {
             payload: m.payload,
             occured_at: m.occured_at,
             processed_at: m.processed_at,
+            attempts: m.attempts as i32,
+            last_error: m.last_error,
+            next_attempt_at: m.next_attempt_at,
+            dead_lettered_at: m.dead_lettered_at,
         }
     }
 }
@@ -22,6 +26,10 @@ impl From<&Message> for OutboxDto {
             payload: m.payload.clone(),
             occured_at: m.occured_at,
             processed_at: m.processed_at,
+            attempts: m.attempts as i32,
+            last_error: m.last_error.clone(),
+            next_attempt_at: m.next_attempt_at,
+            dead_lettered_at: m.dead_lettered_at,
         }
     }
 }
@@ -34,6 +42,10 @@ impl From<OutboxDto> for Message {
             payload: row.payload,
             occured_at: row.occured_at,
             processed_at: row.processed_at,
+            attempts: row.attempts.max(0) as u32,
+            last_error: row.last_error,
+            next_attempt_at: row.next_attempt_at,
+            dead_lettered_at: row.dead_lettered_at,
         }
     }
 }
@@ -46,6 +58,10 @@ impl From<&OutboxDto> for Message {
             payload: row.payload.clone(),
             occured_at: row.occured_at,
             processed_at: row.processed_at,
+            attempts: row.attempts.max(0) as u32,
+            last_error: row.last_error.clone(),
+            next_attempt_at: row.next_attempt_at,
+            dead_lettered_at: row.dead_lettered_at,
         }
     }
 }
@@ -1,4 +1,5 @@
 use diesel::PgConnection;
+use diesel::dsl::delete;
 use diesel::dsl::insert_into;
 use diesel::dsl::update;
 use diesel::prelude::*;
@@ -8,29 +9,58 @@ use ports::errors::RepositoryError;
 use ports::outbox_repository::OutboxRepositoryPort;
 use r2d2::Pool;
 use r2d2::PooledConnection;
+use std::ops::DerefMut;
+use std::ptr::NonNull;
+use std::time::Duration;
+use std::time::SystemTime;
+use uuid::Uuid;
 
 use crate::errors::postgres_error::PostgresError;
 use crate::outbox::outbox_dto::OutboxDto;
 
 use super::outbox_schema::outbox::dsl::*;
 
-#[derive(Clone)]
 pub struct OutboxRepository {
     pool: Pool<ConnectionManager<PgConnection>>,
+    shared_connection: Option<NonNull<PgConnection>>,
 }
 
+// SAFETY: `shared_connection` is only accessed via `&mut self`, preventing cross-thread use.
+unsafe impl Send for OutboxRepository {}
+
 impl OutboxRepository {
     pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            shared_connection: None,
+        }
     }
 
-    fn get_conn(
-        &mut self,
-    ) -> Result<PooledConnection<ConnectionManager<PgConnection>>, RepositoryError> {
-        self.pool
+    pub fn with_shared_connection(
+        pool: Pool<ConnectionManager<PgConnection>>,
+        connection: NonNull<PgConnection>,
+    ) -> Self {
+        Self {
+            pool,
+            shared_connection: Some(connection),
+        }
+    }
+
+    fn connection(&mut self) -> Result<RepositoryConn<'_>, RepositoryError> {
+        if let Some(conn_ptr) = self.shared_connection {
+            // SAFETY: conn_ptr originates from an active transaction and remains valid
+            // while the transaction closure executes.
+            let conn = unsafe { &mut *conn_ptr.as_ptr() };
+            return Ok(RepositoryConn::Borrowed(conn));
+        }
+
+        let conn = self
+            .pool
             .get()
             .map_err(PostgresError::from)
-            .map_err(RepositoryError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(RepositoryConn::Pooled(conn))
     }
 }
 
@@ -38,11 +68,11 @@ impl OutboxRepositoryPort for OutboxRepository {
     fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
         let dto: OutboxDto = message.into();
 
-        let mut conn = self.get_conn()?;
+        let mut connection = self.connection()?;
 
         insert_into(outbox)
             .values(&dto)
-            .execute(&mut conn)
+            .execute(connection.as_mut())
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
 
@@ -52,27 +82,115 @@ impl OutboxRepositoryPort for OutboxRepository {
     fn update(&mut self, message: &Message) -> Result<(), RepositoryError> {
         let dto: OutboxDto = message.into();
 
-        let mut conn = self.get_conn()?;
+        let mut connection = self.connection()?;
 
         update(outbox.find(dto.id))
             .set(&dto)
-            .execute(&mut conn)
+            .execute(connection.as_mut())
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
 
         Ok(())
     }
 
-    fn get_not_published_messages(&mut self) -> Result<Vec<Message>, RepositoryError> {
-        let mut conn = self.get_conn()?;
-
-        let rows: Vec<OutboxDto> = outbox
-            .filter(processed_at.is_null())
-            .limit(20)
-            .load(&mut conn)
+    fn claim_not_published_messages(
+        &mut self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        let mut connection = self.connection()?;
+        let now = SystemTime::now();
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        let rows: Vec<OutboxDto> = connection
+            .as_mut()
+            .transaction(|tx| {
+                let claimed: Vec<OutboxDto> = outbox
+                    .filter(processed_at.is_null())
+                    .filter(dead_lettered_at.is_null())
+                    .filter(next_attempt_at.is_null().or(next_attempt_at.le(now)))
+                    .order((occured_at.asc(), id.asc()))
+                    .limit(limit)
+                    .for_update()
+                    .skip_locked()
+                    .load(tx)?;
+
+                let ids: Vec<Uuid> = claimed.iter().map(|row| row.id).collect();
+                update(outbox.filter(id.eq_any(&ids)))
+                    .set(next_attempt_at.eq(now + lease))
+                    .execute(tx)?;
+
+                diesel::result::QueryResult::Ok(claimed)
+            })
             .map_err(PostgresError::from)
             .map_err(RepositoryError::from)?;
 
         Ok(rows.iter().map(Message::from).collect())
     }
+
+    fn delete_published_before(
+        &mut self,
+        cutoff: SystemTime,
+        limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        let mut connection = self.connection()?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        let expired: Vec<Uuid> = outbox
+            .select(id)
+            .filter(processed_at.lt(cutoff))
+            .order(processed_at.asc())
+            .limit(limit)
+            .load(connection.as_mut())
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        let deleted = delete(outbox.filter(id.eq_any(&expired)))
+            .execute(connection.as_mut())
+            .map_err(PostgresError::from)
+            .map_err(RepositoryError::from)?;
+
+        Ok(deleted as u64)
+    }
+
+    fn replay(&mut self, from: SystemTime, to: SystemTime) -> Result<u64, RepositoryError> {
+        let mut connection = self.connection()?;
+
+        let replayed = update(
+            outbox
+                .filter(occured_at.ge(from))
+                .filter(occured_at.lt(to))
+                .filter(
+                    processed_at
+                        .is_not_null()
+                        .or(dead_lettered_at.is_not_null()),
+                ),
+        )
+        .set((
+            processed_at.eq(None::<SystemTime>),
+            dead_lettered_at.eq(None::<SystemTime>),
+            next_attempt_at.eq(None::<SystemTime>),
+            last_error.eq(None::<String>),
+            attempts.eq(0),
+        ))
+        .execute(connection.as_mut())
+        .map_err(PostgresError::from)
+        .map_err(RepositoryError::from)?;
+
+        Ok(replayed as u64)
+    }
+}
+
+enum RepositoryConn<'a> {
+    Borrowed(&'a mut PgConnection),
+    Pooled(PooledConnection<ConnectionManager<PgConnection>>),
+}
+
+impl<'a> RepositoryConn<'a> {
+    fn as_mut(&mut self) -> &mut PgConnection {
+        match self {
+            RepositoryConn::Borrowed(conn) => conn,
+            RepositoryConn::Pooled(conn) => conn.deref_mut(),
+        }
+    }
 }
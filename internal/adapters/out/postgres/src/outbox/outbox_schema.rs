@@ -5,5 +5,9 @@ diesel::table! {
         payload -> Text,
         occured_at -> Timestamp,
         processed_at -> Nullable<Timestamp>,
+        attempts -> Integer,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Nullable<Timestamp>,
+        dead_lettered_at -> Nullable<Timestamp>,
     }
 }
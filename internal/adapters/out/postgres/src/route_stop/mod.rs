@@ -0,0 +1,3 @@
+pub mod route_stop_dto;
+pub mod route_stop_mapper;
+pub mod route_stop_schema;
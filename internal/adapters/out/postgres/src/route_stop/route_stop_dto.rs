@@ -0,0 +1,31 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::route_stop_schema::route_stops;
+use crate::courier::courier_dto::CourierDto;
+
+#[derive(Queryable, Identifiable, Insertable, Associations, Debug, Clone)]
+#[diesel(belongs_to(CourierDto, foreign_key = courier_id))]
+#[diesel(table_name = route_stops)]
+#[diesel(primary_key(order_id, kind))]
+#[diesel(check_for_backend(Pg))]
+pub struct RouteStopDto {
+    pub order_id: Uuid,
+    pub courier_id: Uuid,
+    pub position: i16,
+    pub location_x: Option<i32>,
+    pub location_y: Option<i32>,
+    pub kind: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl std::fmt::Display for RouteStopDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "order_id: {}, kind: {}, courier_id: {}, position: {}",
+            self.order_id, self.kind, self.courier_id, self.position
+        )
+    }
+}
@@ -0,0 +1,41 @@
+use domain::errors::domain_model_errors::DomainModelError;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::route_stop::RouteStop;
+use domain::model::courier::route_stop::StopKind;
+use domain::model::order::order_aggregate::OrderId;
+
+use super::route_stop_dto::RouteStopDto;
+use crate::location_columns::LocationColumns;
+
+impl From<(usize, &RouteStop, CourierId)> for RouteStopDto {
+    fn from((position, stop, courier_id): (usize, &RouteStop, CourierId)) -> Self {
+        let location = LocationColumns::from(stop.location());
+        Self {
+            order_id: stop.order_id().0,
+            courier_id: courier_id.0,
+            position: position as i16,
+            location_x: location.x,
+            location_y: location.y,
+            kind: stop.kind().into(),
+            latitude: location.latitude,
+            longitude: location.longitude,
+        }
+    }
+}
+
+impl TryFrom<RouteStopDto> for RouteStop {
+    type Error = DomainModelError;
+
+    fn try_from(dto: RouteStopDto) -> Result<Self, Self::Error> {
+        let kind = StopKind::try_from(dto.kind.as_str()).map_err(DomainModelError::MapError)?;
+        let location = LocationColumns {
+            x: dto.location_x,
+            y: dto.location_y,
+            latitude: dto.latitude,
+            longitude: dto.longitude,
+        }
+        .into_required_location()?;
+
+        Ok(Self::new(OrderId::new(dto.order_id), kind, location))
+    }
+}
@@ -0,0 +1,17 @@
+diesel::table! {
+    route_stops (order_id, kind) {
+        order_id -> Uuid,
+        courier_id -> Uuid,
+        position -> SmallInt,
+        location_x -> Nullable<Integer>,
+        location_y -> Nullable<Integer>,
+        kind -> Text,
+        latitude -> Nullable<Double>,
+        longitude -> Nullable<Double>,
+    }
+}
+
+use crate::courier::courier_schema::couriers;
+
+diesel::joinable!(route_stops -> couriers (courier_id));
+diesel::allow_tables_to_appear_in_same_query!(couriers, route_stops);
@@ -8,8 +8,17 @@ use std::ptr::NonNull;
 
 use crate::courier::courier_repository::CourierRepository;
 use crate::errors::postgres_error::PostgresError;
+use crate::inbox::inbox_repository::InboxRepository;
 use crate::order::order_repository::OrderRepository;
+use crate::outbox::outbox_repository::OutboxRepository;
 
+/// Hands out repositories over the pool, or over the connection of the
+/// transaction it was made for. It is `Send` but not `Sync`: callers sharing
+/// one across tasks keep it behind a mutex and clone a fresh one per request.
+///
+/// A clone shares only the pool. Cloned inside a transaction, it is not part
+/// of it: it checks out its own connection and its writes commit on their
+/// own, even when the transaction rolls back.
 pub struct UnitOfWork {
     pub pool: Pool<ConnectionManager<PgConnection>>,
     shared_connection: Option<NonNull<PgConnection>>,
@@ -27,6 +36,12 @@ impl std::fmt::Debug for UnitOfWork {
     }
 }
 
+impl Clone for UnitOfWork {
+    fn clone(&self) -> Self {
+        Self::new(self.pool.clone())
+    }
+}
+
 impl UnitOfWork {
     pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
         Self {
@@ -55,6 +70,8 @@ impl UnitOfWorkPort for UnitOfWork {
     type Uow = UnitOfWork;
     type CourierRepo = CourierRepository;
     type OrderRepo = OrderRepository;
+    type OutboxRepo = OutboxRepository;
+    type InboxRepo = InboxRepository;
 
     fn courier_repo(&mut self) -> Self::CourierRepo {
         if let Some(conn) = self.shared_connection {
@@ -72,6 +89,22 @@ impl UnitOfWorkPort for UnitOfWork {
         }
     }
 
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        if let Some(conn) = self.shared_connection {
+            OutboxRepository::with_shared_connection(self.pool.clone(), conn)
+        } else {
+            OutboxRepository::new(self.pool.clone())
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        if let Some(conn) = self.shared_connection {
+            InboxRepository::with_shared_connection(self.pool.clone(), conn)
+        } else {
+            InboxRepository::new(self.pool.clone())
+        }
+    }
+
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
         F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
@@ -0,0 +1,117 @@
+use application::errors::command_errors::CommandError;
+use application::usecases::CommandHandler;
+use application::usecases::commands::create_order_command::CreateOrderCommand;
+use application::usecases::commands::create_order_handler::CreateOrderHandler;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use domain::model::kernel::event::EventId;
+use domain::model::kernel::location::Location;
+use out_postgres::inbox::inbox_schema::inbox;
+use out_postgres::order::order_schema::orders;
+use out_postgres::outbox::outbox_schema::outbox;
+use out_postgres::unit_of_work::UnitOfWork;
+use ports::errors::GeoClientError;
+use ports::errors::RepositoryError;
+use ports::geo_service_port::GeoServicePort;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use uuid::Uuid;
+
+mod common;
+use common::TestPg;
+
+struct FixedGeoService;
+
+#[async_trait]
+impl GeoServicePort for FixedGeoService {
+    async fn get_location(&mut self, _address: String) -> Result<Location, GeoClientError> {
+        Location::new(1, 1).map_err(|e| GeoClientError::ExecutionError(e.to_string()))
+    }
+}
+
+#[tokio::test]
+async fn redelivered_basket_event_creates_order_once() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let event_id = EventId::default();
+    let basket_id = Uuid::new_v4();
+    let mut handler =
+        CreateOrderHandler::new(UnitOfWork::new(connections.clone()), FixedGeoService, None);
+
+    for _ in 0..2 {
+        let command = CreateOrderCommand::new(basket_id, "Tverskaya".into(), 5, None)
+            .unwrap()
+            .with_event_id(event_id);
+        handler
+            .execute(command)
+            .await
+            .expect("a redelivered event should be acknowledged");
+    }
+
+    let mut conn = connections.get().unwrap();
+    let order_count: i64 = orders::table.count().get_result(&mut conn).unwrap();
+    let outbox_count: i64 = outbox::table.count().get_result(&mut conn).unwrap();
+    let inbox_count: i64 = inbox::table.count().get_result(&mut conn).unwrap();
+    assert_eq!(order_count, 1);
+    assert_eq!(outbox_count, 1, "the duplicate must not stage events");
+    assert_eq!(inbox_count, 1);
+}
+
+#[tokio::test]
+async fn same_basket_under_new_event_is_a_domain_error() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let basket_id = Uuid::new_v4();
+    let mut handler =
+        CreateOrderHandler::new(UnitOfWork::new(connections.clone()), FixedGeoService, None);
+    let command = || {
+        CreateOrderCommand::new(basket_id, "Tverskaya".into(), 5, None)
+            .unwrap()
+            .with_event_id(EventId::default())
+    };
+
+    handler.execute(command()).await.unwrap();
+    let result = handler.execute(command()).await;
+
+    assert!(
+        matches!(result, Err(CommandError::DomainError(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn failed_attempt_leaves_event_unrecorded() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let event_id = EventId::default();
+    let mut uow = UnitOfWork::new(connections.clone());
+
+    let failed: Result<(), RepositoryError> = uow.transaction(|tx| {
+        assert!(tx.inbox_repo().try_record(event_id)?);
+        Err(RepositoryError::DatabaseError("order insert failed".into()))
+    });
+    assert!(failed.is_err());
+
+    let recorded = uow
+        .transaction(|tx| tx.inbox_repo().try_record(event_id))
+        .unwrap();
+    assert!(
+        recorded,
+        "the redelivery must be processed after a rollback"
+    );
+
+    let duplicate = uow
+        .transaction(|tx| tx.inbox_repo().try_record(event_id))
+        .unwrap();
+    assert!(!duplicate);
+}
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use diesel::RunQueryDsl;
+use diesel::dsl::sql;
+use diesel::sql_types::BigInt;
+use domain::model::kernel::message::Message;
+use out_postgres::outbox::outbox_listener::OutboxListener;
+use out_postgres::outbox::outbox_repository::OutboxRepository;
+use ports::outbox_repository::OutboxListenerPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use uuid::Uuid;
+
+mod common;
+use common::TestPg;
+
+const LEASE: Duration = Duration::from_secs(30);
+
+fn message_at(occured_at: SystemTime) -> Message {
+    let mut message = Message::new("created".into(), "{}".into());
+    message.occured_at = occured_at;
+    message
+}
+
+#[tokio::test]
+async fn relays_claim_disjoint_batches_oldest_first() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let start = SystemTime::now();
+    let mut repo = OutboxRepository::new(connections.clone());
+    let mut oldest_first = Vec::new();
+    for offset in (0..5u64).rev() {
+        let message = message_at(start + Duration::from_secs(offset));
+        repo.add(&message).unwrap();
+        oldest_first.push(message.id);
+    }
+    oldest_first.reverse();
+
+    let mut first_relay = OutboxRepository::new(connections.clone());
+    let mut second_relay = OutboxRepository::new(connections.clone());
+
+    let first: Vec<Uuid> = first_relay
+        .claim_not_published_messages(3, LEASE)
+        .unwrap()
+        .iter()
+        .map(|m| m.id)
+        .collect();
+    let second: Vec<Uuid> = second_relay
+        .claim_not_published_messages(3, LEASE)
+        .unwrap()
+        .iter()
+        .map(|m| m.id)
+        .collect();
+
+    assert_eq!(first, oldest_first[..3]);
+    assert_eq!(second, oldest_first[3..]);
+
+    let third = first_relay.claim_not_published_messages(3, LEASE).unwrap();
+    assert!(
+        third.is_empty(),
+        "leased messages must not be claimed twice"
+    );
+
+    let claimed: HashSet<Uuid> = first.into_iter().chain(second).collect();
+    assert_eq!(claimed.len(), 5);
+}
+
+#[tokio::test]
+async fn expired_lease_makes_message_claimable_again() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let mut repo = OutboxRepository::new(connections.clone());
+    let message = message_at(SystemTime::now());
+    repo.add(&message).unwrap();
+
+    let claimed = repo
+        .claim_not_published_messages(10, Duration::ZERO)
+        .unwrap();
+    assert_eq!(claimed.len(), 1);
+
+    let reclaimed = repo.claim_not_published_messages(10, LEASE).unwrap();
+    assert_eq!(reclaimed.len(), 1, "a crashed relay's claim must expire");
+    assert_eq!(reclaimed[0].id, message.id);
+}
+
+#[tokio::test]
+async fn listener_wakes_up_on_insert() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let mut listener = OutboxListener::new(&connections).unwrap();
+    assert!(
+        !listener
+            .wait_for_messages(Duration::from_millis(100))
+            .unwrap()
+    );
+
+    OutboxRepository::new(connections.clone())
+        .add(&message_at(SystemTime::now()))
+        .unwrap();
+
+    assert!(listener.wait_for_messages(Duration::from_secs(5)).unwrap());
+}
+
+#[tokio::test]
+async fn dropped_listener_returns_unsubscribed_connection() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    drop(OutboxListener::new(&connections).unwrap());
+
+    let borrowed: Vec<_> = (0..connections.max_size())
+        .map(|_| connections.get().unwrap())
+        .collect();
+    for mut connection in borrowed {
+        let channels: i64 = diesel::select(sql::<BigInt>(
+            "(SELECT count(*) FROM pg_listening_channels())",
+        ))
+        .get_result(&mut connection)
+        .unwrap();
+        assert_eq!(channels, 0);
+    }
+}
+
+#[tokio::test]
+async fn purge_deletes_only_published_messages_before_cutoff() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let now = SystemTime::now();
+    let mut repo = OutboxRepository::new(connections.clone());
+    let mut expired = message_at(now - Duration::from_secs(7200));
+    expired.processed_at = Some(now - Duration::from_secs(7200));
+    let mut recent = message_at(now);
+    recent.processed_at = Some(now);
+    let pending = message_at(now - Duration::from_secs(7200));
+    let mut dead = message_at(now - Duration::from_secs(7200));
+    dead.dead_letter("broker unavailable".into());
+    for message in [&expired, &recent, &pending, &dead] {
+        repo.add(message).unwrap();
+    }
+
+    let deleted = repo
+        .delete_published_before(now - Duration::from_secs(3600), 100)
+        .unwrap();
+
+    assert_eq!(deleted, 1);
+    let claimable: Vec<Uuid> = repo
+        .claim_not_published_messages(10, LEASE)
+        .unwrap()
+        .iter()
+        .map(|m| m.id)
+        .collect();
+    assert_eq!(claimable, vec![pending.id]);
+}
+
+#[tokio::test]
+async fn replay_requeues_messages_in_range_with_their_ids() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let now = SystemTime::now();
+    let mut repo = OutboxRepository::new(connections.clone());
+    let mut inside = message_at(now - Duration::from_secs(60));
+    inside.mark_published();
+    let mut dead = message_at(now - Duration::from_secs(30));
+    dead.dead_letter("broker unavailable".into());
+    let mut outside = message_at(now - Duration::from_secs(7200));
+    outside.mark_published();
+    for message in [&inside, &dead, &outside] {
+        repo.add(message).unwrap();
+    }
+
+    let replayed = repo.replay(now - Duration::from_secs(3600), now).unwrap();
+
+    assert_eq!(replayed, 2);
+    let claimed = repo.claim_not_published_messages(10, LEASE).unwrap();
+    let ids: Vec<Uuid> = claimed.iter().map(|m| m.id).collect();
+    assert_eq!(ids, vec![inside.id, dead.id]);
+    assert!(
+        claimed
+            .iter()
+            .all(|m| m.attempts == 0 && m.last_error.is_none())
+    );
+}
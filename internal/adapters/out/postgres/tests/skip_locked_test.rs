@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+use std::sync::Barrier;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use diesel::prelude::*;
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
+use domain::model::services::order_dispatcher::OrderDispatcher;
+use domain::model::services::order_dispatcher::OrderDispatcherService;
+use out_postgres::courier::courier_repository::CourierRepository;
+use out_postgres::order::order_repository::OrderRepository;
+use out_postgres::order::order_schema::orders;
+use out_postgres::storage_place::storage_place_schema::storage_places;
+use out_postgres::unit_of_work::UnitOfWork;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use uuid::Uuid;
+
+mod common;
+use common::TestPg;
+
+const WORKERS: usize = 4;
+const COURIERS: usize = 6;
+const ORDERS: usize = 12;
+
+#[tokio::test]
+async fn concurrent_dispatchers_never_double_assign() {
+    let TestPg {
+        connections,
+        _container,
+    } = TestPg::new().await;
+
+    let mut courier_repo = CourierRepository::new(connections.clone());
+    for i in 0..COURIERS {
+        let courier = Courier::new(
+            CourierName(format!("courier-{i}")),
+            CourierSpeed(2),
+            Location::new(1, 1).unwrap(),
+        )
+        .unwrap();
+        courier_repo.add(courier).unwrap();
+    }
+    let mut order_repo = OrderRepository::new(connections.clone());
+    for i in 0..ORDERS {
+        let order = Order::new(
+            OrderId::new(Uuid::new_v4()),
+            Location::new(2 + (i % 8) as u16, 5).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap();
+        order_repo.add(&order).unwrap();
+    }
+
+    let created = OrderFilter::with_statuses([OrderStatus::Created]);
+    let barrier = Barrier::new(WORKERS);
+    thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            let pool = connections.clone();
+            let created = created.clone();
+            let barrier = &barrier;
+            scope.spawn(move || {
+                let mut uow = UnitOfWork::new(pool.clone());
+                barrier.wait();
+
+                for _ in 0..ORDERS * 4 {
+                    uow.transaction(|tx| {
+                        let mut unassigned = tx
+                            .order_repo()
+                            .find(&OrderCriteria::matching(created.clone()))?;
+                        let mut free = tx.courier_repo().get_all_free()?;
+                        // Keep the rows locked long enough for the others to collide.
+                        thread::sleep(Duration::from_millis(20));
+
+                        if let Ok((order, courier)) = OrderDispatcherService.dispatch(
+                            &mut unassigned,
+                            &mut free,
+                            SystemTime::now(),
+                            &CityMap::default(),
+                        ) {
+                            tx.courier_repo().update(courier.to_owned())?;
+                            tx.order_repo().update(order)?;
+                        }
+                        Ok(())
+                    })
+                    .unwrap();
+
+                    let free_left = CourierRepository::new(pool.clone()).get_all_free().unwrap();
+                    if free_left.is_empty() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    let mut conn = connections.get().unwrap();
+    let assigned: Vec<(Uuid, Option<Uuid>)> = orders::table
+        .filter(orders::status.eq(String::from(OrderStatus::Assigned)))
+        .select((orders::id, orders::courier_id))
+        .load(&mut conn)
+        .unwrap();
+    let carried: Vec<(Uuid, Option<Uuid>)> = storage_places::table
+        .filter(storage_places::order_id.is_not_null())
+        .select((storage_places::courier_id, storage_places::order_id))
+        .load(&mut conn)
+        .unwrap();
+
+    assert_eq!(
+        assigned.len(),
+        COURIERS,
+        "every courier takes exactly one order"
+    );
+    assert_eq!(carried.len(), COURIERS, "every bag holds exactly one order");
+
+    let carried_orders: HashSet<Uuid> = carried.iter().filter_map(|(_, o)| *o).collect();
+    assert_eq!(carried_orders.len(), COURIERS, "no order sits in two bags");
+
+    let carrying_couriers: HashSet<Uuid> = carried.iter().map(|(c, _)| *c).collect();
+    assert_eq!(
+        carrying_couriers.len(),
+        COURIERS,
+        "no courier is double-booked"
+    );
+
+    for (order_id, courier_id) in assigned {
+        assert!(
+            carried.contains(&(courier_id.unwrap(), Some(order_id))),
+            "order {order_id} must be in the bag of its courier"
+        );
+    }
+}
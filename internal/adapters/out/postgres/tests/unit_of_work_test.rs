@@ -11,10 +11,12 @@ use domain::model::order::order_aggregate::OrderId;
 use out_postgres::courier::courier_dto::CourierDto;
 use out_postgres::courier::courier_schema::couriers;
 use out_postgres::order::order_schema::orders;
+use out_postgres::outbox::outbox_schema::outbox;
 use out_postgres::unit_of_work::UnitOfWork;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::errors::RepositoryError;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 
 mod common;
@@ -37,8 +39,15 @@ async fn test_transaction_commit_and_rollback() {
             id: Uuid::new_v4(),
             name: "rollback_courier".into(),
             speed: 10,
-            location_x: 1,
-            location_y: 2,
+            location_x: Some(1),
+            location_y: Some(2),
+            latitude: None,
+            longitude: None,
+            status: "available".into(),
+            shift_start: None,
+            shift_end: None,
+            ending_shift: false,
+            version: 0,
         };
 
         insert_into(out_postgres::courier::courier_schema::couriers::table)
@@ -63,8 +72,15 @@ async fn test_transaction_commit_and_rollback() {
             id: Uuid::new_v4(),
             name: "committed_courier".into(),
             speed: 12,
-            location_x: 3,
-            location_y: 4,
+            location_x: Some(3),
+            location_y: Some(4),
+            latitude: None,
+            longitude: None,
+            status: "available".into(),
+            shift_start: None,
+            shift_end: None,
+            ending_shift: false,
+            version: 0,
         };
 
         insert_into(out_postgres::courier::courier_schema::couriers::table)
@@ -92,7 +108,7 @@ async fn test_transaction_commit_and_rollback() {
         )
         .unwrap();
 
-        let order = Order::new(
+        let mut order = Order::new(
             OrderId::new(Uuid::new_v4()),
             Location::new(2, 2).unwrap(),
             Volume::new(3).unwrap(),
@@ -105,6 +121,11 @@ async fn test_transaction_commit_and_rollback() {
         let mut order_repo = tx.order_repo();
         order_repo.add(&order).unwrap();
 
+        let mut outbox_repo = tx.outbox_repo();
+        for event in order.pop_domain_events() {
+            outbox_repo.add(&(&event).try_into().unwrap()).unwrap();
+        }
+
         Ok(())
     });
 
@@ -119,4 +140,99 @@ async fn test_transaction_commit_and_rollback() {
 
     assert_eq!(count_couriers, 2, "there should be 2 couriers");
     assert_eq!(count_orders, 1, "there should be 1 order");
+
+    let count_messages: i64 = outbox::dsl::outbox
+        .count()
+        .first(&mut connections.get().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        count_messages, 1,
+        "outbox message must commit with the order"
+    );
+
+    let result: Result<(), RepositoryError> = uow.transaction(|tx| {
+        let mut order = Order::new(
+            OrderId::new(Uuid::new_v4()),
+            Location::new(3, 3).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap();
+
+        tx.order_repo().add(&order).unwrap();
+
+        let mut outbox_repo = tx.outbox_repo();
+        for event in order.pop_domain_events() {
+            outbox_repo.add(&(&event).try_into().unwrap()).unwrap();
+        }
+
+        Err(RepositoryError::MapError("force rollback".into()))
+    });
+
+    assert!(result.is_err(), "expected rollback due to error");
+
+    let count_orders: i64 = orders::dsl::orders
+        .count()
+        .first(&mut connections.get().unwrap())
+        .unwrap();
+    let count_messages: i64 = outbox::dsl::outbox
+        .count()
+        .first(&mut connections.get().unwrap())
+        .unwrap();
+
+    assert_eq!(count_orders, 1, "rolled back order must not persist");
+    assert_eq!(
+        count_messages, 1,
+        "rolled back outbox message must not persist"
+    );
+}
+
+#[tokio::test]
+async fn clone_in_transaction_writes_outside_it() {
+    let test_pg = TestPg::new().await;
+
+    let TestPg {
+        connections,
+        _container,
+    } = test_pg;
+
+    let mut uow = UnitOfWork::new(connections.clone());
+    let outside_id = OrderId::new(Uuid::new_v4());
+    let inside_id = OrderId::new(Uuid::new_v4());
+
+    let result: Result<(), RepositoryError> = uow.transaction(|tx| {
+        let mut outside = tx.clone();
+        assert!(outside.transaction_connection().is_none());
+
+        let order = Order::new(
+            outside_id,
+            Location::new(1, 1).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap();
+        outside.order_repo().add(&order).unwrap();
+
+        let order = Order::new(
+            inside_id,
+            Location::new(2, 2).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap();
+        tx.order_repo().add(&order).unwrap();
+
+        Err(RepositoryError::MapError("force rollback".into()))
+    });
+
+    assert!(result.is_err(), "expected rollback due to error");
+
+    let stored: Vec<Uuid> = orders::dsl::orders
+        .select(orders::dsl::id)
+        .load(&mut connections.get().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        stored,
+        vec![outside_id.value()],
+        "only the clone's write must survive the rollback"
+    );
 }
@@ -3,12 +3,18 @@ use std::fmt::Display;
 use std::fmt::Result;
 
 use domain::errors::domain_model_errors::DomainModelError;
+use ports::errors::GeoClientError;
 use ports::errors::RepositoryError;
 
 #[derive(Debug)]
 pub enum CommandError {
     ArgumentError(String),
+    /// The command breaks a domain rule or carries data that cannot be
+    /// mapped; running it again will not help.
+    DomainError(String),
     ExecutionError(String),
+    NotFound(String),
+    Conflict(String),
 }
 
 impl Error for CommandError {}
@@ -19,21 +25,46 @@ impl Display for CommandError {
             Self::ArgumentError(msg) => {
                 write!(f, "Command arguments error: {}", msg)
             }
+            Self::DomainError(msg) => {
+                write!(f, "Domain rule violated: {}", msg)
+            }
             Self::ExecutionError(msg) => {
                 write!(f, "Command execution failure: {}", msg)
             }
+            Self::NotFound(msg) => {
+                write!(f, "Not found: {}", msg)
+            }
+            Self::Conflict(msg) => {
+                write!(f, "Concurrent modification: {}", msg)
+            }
         }
     }
 }
 
 impl From<DomainModelError> for CommandError {
     fn from(value: DomainModelError) -> Self {
-        Self::ArgumentError(value.to_string())
+        Self::DomainError(value.to_string())
     }
 }
 
 impl From<RepositoryError> for CommandError {
     fn from(value: RepositoryError) -> Self {
-        Self::ExecutionError(value.to_string())
+        match value {
+            RepositoryError::NotFound(msg) => Self::NotFound(msg),
+            RepositoryError::Conflict(msg) => Self::Conflict(msg),
+            RepositoryError::MapError(_) | RepositoryError::AlreadyExists(_) => {
+                Self::DomainError(value.to_string())
+            }
+            RepositoryError::DatabaseError(_) => Self::ExecutionError(value.to_string()),
+        }
+    }
+}
+
+impl From<GeoClientError> for CommandError {
+    fn from(value: GeoClientError) -> Self {
+        match value {
+            GeoClientError::ConnectionError(_) => Self::ExecutionError(value.to_string()),
+            GeoClientError::ExecutionError(_) => Self::DomainError(value.to_string()),
+        }
     }
 }
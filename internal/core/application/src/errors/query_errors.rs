@@ -9,6 +9,7 @@ use ports::errors::RepositoryError;
 pub enum QueryError {
     ArgumentError(String),
     ExecutionError(String),
+    NotFound(String),
 }
 
 impl Error for QueryError {}
@@ -22,6 +23,9 @@ impl Display for QueryError {
             Self::ExecutionError(msg) => {
                 write!(f, "Command execution failure: {}", msg)
             }
+            Self::NotFound(msg) => {
+                write!(f, "Not found: {}", msg)
+            }
         }
     }
 }
@@ -34,6 +38,9 @@ impl From<DomainModelError> for QueryError {
 
 impl From<RepositoryError> for QueryError {
     fn from(value: RepositoryError) -> Self {
-        Self::ExecutionError(value.to_string())
+        match value {
+            RepositoryError::NotFound(msg) => Self::NotFound(msg),
+            other => Self::ExecutionError(other.to_string()),
+        }
     }
 }
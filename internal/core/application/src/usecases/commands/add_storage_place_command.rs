@@ -0,0 +1,40 @@
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::kernel::volume::Volume;
+
+use crate::errors::command_errors::CommandError;
+
+pub struct AddStoragePlaceCommand {
+    courier_id: CourierId,
+    name: String,
+    volume: Volume,
+}
+
+impl AddStoragePlaceCommand {
+    pub fn new(courier_id: Uuid, name: String, volume: u16) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+            name,
+            volume: Volume::new(volume)?,
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn volume(&self) -> Volume {
+        self.volume
+    }
+}
@@ -0,0 +1,42 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+use uuid::Uuid;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::add_storage_place_command::AddStoragePlaceCommand;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+
+pub struct AddStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> AddStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<AddStoragePlaceCommand, Uuid> for AddStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: AddStoragePlaceCommand) -> Result<Uuid, Self::Error> {
+        let courier_repository = &mut self.courier_repository;
+        retry_on_conflict(|| {
+            let mut courier = courier_repository.get_by_id(command.courier_id())?;
+            let storage_place_id =
+                courier.add_storage_place(command.name().to_string(), command.volume())?;
+            courier_repository.update(courier)?;
+
+            Ok(storage_place_id)
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use crate::errors::command_errors::CommandError;
+
+#[derive(Debug)]
+pub struct ApplyShiftSchedulesCommand;
+
+impl ApplyShiftSchedulesCommand {
+    pub fn new() -> Result<Self, CommandError> {
+        Ok(Self {})
+    }
+}
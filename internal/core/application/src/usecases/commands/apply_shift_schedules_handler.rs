@@ -0,0 +1,70 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+use std::time::SystemTime;
+use tracing::debug;
+use tracing::instrument;
+use tracing::warn;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::apply_shift_schedules_command::ApplyShiftSchedulesCommand;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+
+/// Starts and ends scheduled shifts whose time has come.
+pub struct ApplyShiftSchedulesHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> ApplyShiftSchedulesHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<ApplyShiftSchedulesCommand, ()> for ApplyShiftSchedulesHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    #[instrument(skip_all)]
+    async fn execute(&mut self, _c: ApplyShiftSchedulesCommand) -> Result<(), Self::Error> {
+        let now = SystemTime::now();
+        let courier_repository = &mut self.courier_repository;
+
+        for courier in courier_repository.get_all_scheduled()? {
+            let courier_id = *courier.id();
+            let mut loaded = Some(courier);
+            let applied = retry_on_conflict(|| {
+                let mut courier = match loaded.take() {
+                    Some(courier) => courier,
+                    None => courier_repository.get_by_id(courier_id)?,
+                };
+                if courier.apply_shift_schedule(now)? {
+                    debug!(
+                        "courier {} is now {}",
+                        courier_id.0,
+                        String::from(courier.status())
+                    );
+                    courier_repository.update(courier)?;
+                }
+                Ok(())
+            });
+
+            match applied {
+                Ok(()) => {}
+                Err(err @ (CommandError::DomainError(_) | CommandError::Conflict(_))) => {
+                    warn!("could not apply shift of courier {}: {}", courier_id.0, err)
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,56 +1,92 @@
+use domain::model::courier::city_map::CityMap;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
 use domain::model::services::order_dispatcher::OrderDispatcher;
-use domain::model::services::order_dispatcher::OrderDispatcherService;
 use ports::courier_repository_port::CourierRepositoryPort;
-use ports::errors::RepositoryError;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
 use ports::order_repository_port::OrderRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use std::fmt::Debug;
+use std::time::SystemTime;
 use tracing::Level;
 use tracing::instrument;
+use tracing::warn;
 
 use crate::errors::command_errors::CommandError;
 use crate::usecases::CommandHandler;
 use crate::usecases::commands::assign_order_command::AssignOrderCommand;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::outbox_events::stage_events;
 
-#[derive(Debug)]
-pub struct AssignOrderHandler<UOW>
+pub struct AssignOrderHandler<UOW, D>
 where
     UOW: UnitOfWorkPort + Debug,
+    D: OrderDispatcher,
 {
     uow: UOW,
+    dispatcher: D,
+    map: CityMap,
 }
 
-impl<UOW> AssignOrderHandler<UOW>
+impl<UOW, D> AssignOrderHandler<UOW, D>
 where
     UOW: UnitOfWorkPort + Debug,
+    D: OrderDispatcher,
 {
-    pub fn new(uow: UOW) -> Self {
-        Self { uow }
+    pub fn new(uow: UOW, dispatcher: D, map: CityMap) -> Self {
+        Self {
+            uow,
+            dispatcher,
+            map,
+        }
     }
 }
 
-impl<UOW> CommandHandler<AssignOrderCommand, ()> for AssignOrderHandler<UOW>
+impl<UOW, D> CommandHandler<AssignOrderCommand, ()> for AssignOrderHandler<UOW, D>
 where
     UOW: UnitOfWorkPort + Debug,
+    D: OrderDispatcher,
 {
     type Error = CommandError;
 
-    #[instrument(skip(self))]
+    #[instrument(skip_all)]
     async fn execute(&mut self, _: AssignOrderCommand) -> Result<(), Self::Error> {
-        self.uow
-            .transaction(|tx| {
-                let unassigned_order = {
+        let dispatcher = &self.dispatcher;
+        let map = &self.map;
+        let uow = &mut self.uow;
+        retry_on_conflict(|| {
+            uow.transaction(|tx| {
+                let mut unassigned_orders = {
                     let mut repo = tx.order_repo();
-                    repo.raw("SELECT * FROM orders WHERE status = 'created' LIMIT 1;".into())?
-                        .pop()
+                    repo.find(&OrderCriteria::matching(OrderFilter::with_statuses([
+                        OrderStatus::Created,
+                    ])))?
                 };
 
-                match unassigned_order {
-                    None => {
-                        tracing::event!(Level::DEBUG, "no unassigned order found");
-                        Ok(())
+                if unassigned_orders.is_empty() {
+                    tracing::event!(Level::DEBUG, "no unassigned order found");
+                    return Ok(());
+                }
+
+                // Each order is saved once: its version only allows a single update.
+                let now = SystemTime::now();
+                let mut changed: Vec<OrderId> = Vec::new();
+                for order in &mut unassigned_orders {
+                    if order.check_delivery_window(now) {
+                        warn!("order {} missed its delivery window", &order.id().0);
+                        changed.push(order.id());
                     }
-                    Some(mut order) => {
+                }
+
+                let mut available_couriers = {
+                    let mut repo = tx.courier_repo();
+                    repo.get_all_free()?
+                };
+
+                match dispatcher.dispatch(&mut unassigned_orders, &mut available_couriers, now, map)
+                {
+                    Ok((order, courier)) => {
                         let span_child = tracing::span!(
                             tracing::Level::TRACE,
                             "handler",
@@ -58,22 +94,33 @@ where
                         );
                         let _enter_child = span_child.enter();
 
-                        let mut available_couriers = {
-                            let mut repo = tx.courier_repo();
-                            repo.get_all_free()?
-                        };
-
-                        let courier =
-                            OrderDispatcherService::dispatch(&mut order, &mut available_couriers)
-                                .map_err(|e| RepositoryError::from(e.to_string()))?;
                         tx.courier_repo().update(courier.to_owned())?;
-                        tx.order_repo().update(&order)?;
+                        if !changed.contains(&order.id()) {
+                            changed.push(order.id());
+                        }
 
                         tracing::event!(tracing::Level::INFO, "succesfully assigned order",);
-                        Ok(())
+                    }
+                    Err(err) => {
+                        tracing::event!(Level::DEBUG, error = %err, "no order dispatched");
                     }
                 }
+
+                for order in unassigned_orders
+                    .iter()
+                    .filter(|order| changed.contains(&order.id()))
+                {
+                    tx.order_repo().update(order)?;
+                }
+
+                stage_events(
+                    tx,
+                    unassigned_orders
+                        .iter_mut()
+                        .flat_map(|order| order.pop_domain_events()),
+                )
             })
             .map_err(CommandError::from)
+        })
     }
 }
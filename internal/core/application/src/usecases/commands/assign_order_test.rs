@@ -1,20 +1,35 @@
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
 
+use domain::model::courier::city_map::CityMap;
 use domain::model::courier::courier_aggregate::Courier;
 use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::courier::courier_aggregate::CourierName;
 use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::event::EventId;
 use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
 use domain::model::kernel::volume::Volume;
+use domain::model::order::delivery_window::DeliveryWindow;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
 use domain::model::order::order_aggregate::OrderStatus;
+use domain::model::order::order_events::OrderEvent;
+use domain::model::services::dispatch_strategies::LeastLoadedDispatcher;
+use domain::model::services::order_dispatcher::OrderDispatcherService;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::courier_repository_port::GetAllCouriersResponse;
 use ports::errors::RepositoryError;
+use ports::events_producer_port::Events;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use uuid::Uuid;
 
@@ -22,13 +37,63 @@ use crate::usecases::CommandHandler;
 use crate::usecases::commands::assign_order_command::AssignOrderCommand;
 use crate::usecases::commands::assign_order_handler::AssignOrderHandler;
 
+struct TestInboxRepository;
+
+impl InboxRepositoryPort for TestInboxRepository {
+    fn try_record(&mut self, _event_id: EventId) -> Result<bool, RepositoryError> {
+        Ok(true)
+    }
+}
+
+struct TestOutboxRepository {
+    events: Rc<RefCell<Vec<Events>>>,
+}
+
+impl OutboxRepositoryPort for TestOutboxRepository {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let event =
+            Events::try_from(message).map_err(|err| RepositoryError::MapError(err.to_string()))?;
+        self.events.borrow_mut().push(event);
+        Ok(())
+    }
+
+    fn update(&mut self, _message: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _limit: usize,
+        _lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn delete_published_before(
+        &mut self,
+        _cutoff: SystemTime,
+        _limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn replay(&mut self, _from: SystemTime, _to: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+}
+
 #[derive(Clone, Debug)]
 struct StoredOrder {
     id: OrderId,
     courier_id: Option<CourierId>,
+    pickup_location: Option<Location>,
     location: Location,
     volume: Volume,
     status: OrderStatus,
+    delivery_window: Option<DeliveryWindow>,
+    delivery_window_missed: bool,
+    created_at: SystemTime,
+    version: u64,
 }
 
 impl Display for StoredOrder {
@@ -42,9 +107,14 @@ impl StoredOrder {
         Self {
             id: order.id(),
             courier_id: *order.courier_id(),
+            pickup_location: order.pickup_location().cloned(),
             location: order.location().clone(),
             volume: Volume::new(order.volume()).expect("volume must be positive"),
             status: copy_status(order.status()),
+            delivery_window: order.delivery_window(),
+            delivery_window_missed: order.is_delivery_window_missed(),
+            created_at: order.created_at(),
+            version: order.version(),
         }
     }
 
@@ -52,9 +122,14 @@ impl StoredOrder {
         Order::restore(
             self.id,
             self.courier_id,
+            self.pickup_location.clone(),
             self.location.clone(),
             self.volume,
             copy_status(&self.status),
+            self.delivery_window,
+            self.delivery_window_missed,
+            self.created_at,
+            self.version,
         )
     }
 
@@ -63,6 +138,9 @@ impl StoredOrder {
         self.location = order.location().clone();
         self.volume = Volume::new(order.volume()).expect("volume must be positive");
         self.status = copy_status(order.status());
+        self.delivery_window = order.delivery_window();
+        self.delivery_window_missed = order.is_delivery_window_missed();
+        self.version = order.version() + 1;
     }
 }
 
@@ -70,7 +148,9 @@ fn copy_status(status: &OrderStatus) -> OrderStatus {
     match status {
         OrderStatus::Created => OrderStatus::Created,
         OrderStatus::Assigned => OrderStatus::Assigned,
+        OrderStatus::PickedUp => OrderStatus::PickedUp,
         OrderStatus::Completed => OrderStatus::Completed,
+        OrderStatus::Cancelled => OrderStatus::Cancelled,
     }
 }
 
@@ -105,13 +185,17 @@ impl OrderRepositoryPort for TestOrderRepository {
         unimplemented!()
     }
 
-    fn raw(&mut self, _query: String) -> Result<Vec<Order>, RepositoryError> {
-        Ok(self
-            .orders
-            .borrow()
-            .iter()
-            .map(StoredOrder::to_order)
-            .collect())
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        let orders = self.orders.borrow();
+        Ok(criteria.apply(orders.iter().map(StoredOrder::to_order)))
+    }
+
+    fn count(&mut self, _filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_history(&mut self, _id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        unimplemented!()
     }
 }
 
@@ -146,6 +230,10 @@ impl CourierRepositoryPort for TestCourierRepository {
         Ok(self.couriers.borrow().clone())
     }
 
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(self.couriers.borrow().clone())
+    }
+
     fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
         unimplemented!()
     }
@@ -154,6 +242,7 @@ impl CourierRepositoryPort for TestCourierRepository {
 struct TestUnitOfWork {
     orders: Rc<RefCell<Vec<StoredOrder>>>,
     couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
 }
 
 impl std::fmt::Debug for TestUnitOfWork {
@@ -161,6 +250,7 @@ impl std::fmt::Debug for TestUnitOfWork {
         f.debug_struct("TestUnitOfWork")
             .field("orders", &self.orders)
             .field("couriers", &self.couriers)
+            .field("outbox", &self.outbox)
             .finish()
     }
 }
@@ -169,20 +259,28 @@ impl TestUnitOfWork {
     fn from_state(
         orders: Rc<RefCell<Vec<StoredOrder>>>,
         couriers: Rc<RefCell<Vec<Courier>>>,
+        outbox: Rc<RefCell<Vec<Events>>>,
     ) -> Self {
-        Self { orders, couriers }
+        Self {
+            orders,
+            couriers,
+            outbox,
+        }
     }
 }
 
 struct TestUnitOfWorkTx {
     orders: Rc<RefCell<Vec<StoredOrder>>>,
     couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
 }
 
 impl UnitOfWorkPort for TestUnitOfWork {
     type Uow = TestUnitOfWorkTx;
     type CourierRepo = TestCourierRepository;
     type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
 
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
@@ -191,6 +289,7 @@ impl UnitOfWorkPort for TestUnitOfWork {
         let mut tx = TestUnitOfWorkTx {
             orders: Rc::clone(&self.orders),
             couriers: Rc::clone(&self.couriers),
+            outbox: Rc::clone(&self.outbox),
         };
         f(&mut tx)
     }
@@ -206,12 +305,24 @@ impl UnitOfWorkPort for TestUnitOfWork {
             orders: Rc::clone(&self.orders),
         }
     }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
 }
 
 impl UnitOfWorkPort for TestUnitOfWorkTx {
     type Uow = TestUnitOfWorkTx;
     type CourierRepo = TestCourierRepository;
     type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
 
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
@@ -231,6 +342,16 @@ impl UnitOfWorkPort for TestUnitOfWorkTx {
             orders: Rc::clone(&self.orders),
         }
     }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
 }
 
 fn initial_state() -> (Vec<StoredOrder>, Vec<Courier>) {
@@ -271,10 +392,15 @@ async fn handle_assigns_order() {
     let orders_state = Rc::new(RefCell::new(orders));
     let couriers_state = Rc::new(RefCell::new(couriers));
 
-    let mut handler = AssignOrderHandler::new(TestUnitOfWork::from_state(
-        Rc::clone(&orders_state),
-        Rc::clone(&couriers_state),
-    ));
+    let mut handler = AssignOrderHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::new(RefCell::new(Vec::new())),
+        ),
+        OrderDispatcherService,
+        CityMap::default(),
+    );
     let command = AssignOrderCommand::new().expect("command should be valid");
 
     handler
@@ -289,3 +415,87 @@ async fn handle_assigns_order() {
     }));
     assert!(orders.iter().all(|order| order.courier_id.is_some()));
 }
+
+#[tokio::test]
+async fn handle_flags_missed_delivery_window() {
+    let (_, couriers) = initial_state();
+    let now = SystemTime::now();
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 1).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap();
+    order
+        .set_delivery_window(
+            DeliveryWindow::new(
+                now - Duration::from_secs(7200),
+                now - Duration::from_secs(60),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    let orders_state = Rc::new(RefCell::new(vec![StoredOrder::from_order(&order)]));
+    let couriers_state = Rc::new(RefCell::new(couriers));
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = AssignOrderHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::clone(&events),
+        ),
+        OrderDispatcherService,
+        CityMap::default(),
+    );
+
+    handler
+        .execute(AssignOrderCommand::new().unwrap())
+        .await
+        .expect("handler should finish successfully");
+
+    let orders = orders_state.borrow();
+    assert!(orders[0].delivery_window_missed);
+    assert!(matches!(orders[0].status, OrderStatus::Assigned));
+    assert!(matches!(
+        events.borrow().as_slice(),
+        [Events::Order(OrderEvent::DeliveryWindowMissed(e))] if e.order_id == order.id()
+    ));
+}
+
+#[tokio::test]
+async fn handle_uses_given_dispatch_strategy() {
+    let (orders, couriers) = initial_state();
+    let mut busy = couriers[0].clone();
+    busy.add_storage_place("trunk".into(), Volume::new(10).unwrap())
+        .unwrap();
+    busy.take_order(
+        OrderId::new(Uuid::new_v4()),
+        Volume::new(1).unwrap(),
+        None,
+        &Location::new(1, 1).unwrap(),
+        &CityMap::default(),
+    )
+    .unwrap();
+    let busy_id = *busy.id();
+    let couriers_state = Rc::new(RefCell::new(vec![busy, couriers[2].clone()]));
+    let orders_state = Rc::new(RefCell::new(orders));
+
+    let mut handler = AssignOrderHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::new(RefCell::new(Vec::new())),
+        ),
+        LeastLoadedDispatcher,
+        CityMap::default(),
+    );
+
+    handler
+        .execute(AssignOrderCommand::new().unwrap())
+        .await
+        .expect("handler should finish successfully");
+
+    let orders = orders_state.borrow();
+    assert!(orders[0].courier_id.is_some_and(|id| id != busy_id));
+}
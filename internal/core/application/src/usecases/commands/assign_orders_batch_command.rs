@@ -0,0 +1,9 @@
+use crate::errors::command_errors::CommandError;
+
+pub struct AssignOrdersBatchCommand;
+
+impl AssignOrdersBatchCommand {
+    pub fn new() -> Result<Self, CommandError> {
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,119 @@
+use domain::model::courier::city_map::CityMap;
+use domain::model::order::order_aggregate::OrderStatus;
+use domain::model::services::optimal_assignment::OptimalAssignmentService;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::errors::RepositoryError;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use std::fmt::Debug;
+use std::time::SystemTime;
+use tracing::Level;
+use tracing::instrument;
+use tracing::warn;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::assign_orders_batch_command::AssignOrdersBatchCommand;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::outbox_events::stage_events;
+
+pub struct AssignOrdersBatchHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    uow: UOW,
+    map: CityMap,
+}
+
+impl<UOW> AssignOrdersBatchHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    pub fn new(uow: UOW, map: CityMap) -> Self {
+        Self { uow, map }
+    }
+}
+
+impl<UOW> CommandHandler<AssignOrdersBatchCommand, ()> for AssignOrdersBatchHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    type Error = CommandError;
+
+    #[instrument(skip_all)]
+    async fn execute(&mut self, _: AssignOrdersBatchCommand) -> Result<(), Self::Error> {
+        let map = &self.map;
+        let uow = &mut self.uow;
+        retry_on_conflict(|| {
+            uow.transaction(|tx| {
+                let mut unassigned_orders = {
+                    let mut repo = tx.order_repo();
+                    repo.find(&OrderCriteria::matching(OrderFilter::with_statuses([
+                        OrderStatus::Created,
+                    ])))?
+                };
+
+                if unassigned_orders.is_empty() {
+                    tracing::event!(Level::DEBUG, "no unassigned order found");
+                    return Ok(());
+                }
+
+                // Each order is saved once: its version only allows a single update.
+                let now = SystemTime::now();
+                let mut changed: Vec<usize> = Vec::new();
+                for (order_idx, order) in unassigned_orders.iter_mut().enumerate() {
+                    if order.check_delivery_window(now) {
+                        warn!("order {} missed its delivery window", &order.id().0);
+                        changed.push(order_idx);
+                    }
+                }
+
+                let mut available_couriers = {
+                    let mut repo = tx.courier_repo();
+                    repo.get_all_free()?
+                };
+
+                let pairs = OptimalAssignmentService::assign(
+                    &mut unassigned_orders,
+                    &mut available_couriers,
+                    now,
+                    map,
+                )
+                .map_err(|err| RepositoryError::from(err.to_string()))?;
+
+                let mut updated_couriers: Vec<usize> = Vec::new();
+                for &(order_idx, courier_idx) in &pairs {
+                    if !changed.contains(&order_idx) {
+                        changed.push(order_idx);
+                    }
+                    if !updated_couriers.contains(&courier_idx) {
+                        updated_couriers.push(courier_idx);
+                        tx.courier_repo()
+                            .update(available_couriers[courier_idx].to_owned())?;
+                    }
+                }
+
+                for &order_idx in &changed {
+                    tx.order_repo().update(&unassigned_orders[order_idx])?;
+                }
+
+                tracing::event!(
+                    Level::INFO,
+                    assigned = pairs.len(),
+                    pending = unassigned_orders.len() - pairs.len(),
+                    "assigned orders batch"
+                );
+
+                stage_events(
+                    tx,
+                    unassigned_orders
+                        .iter_mut()
+                        .flat_map(|order| order.pop_domain_events()),
+                )
+            })
+            .map_err(CommandError::from)
+        })
+    }
+}
@@ -0,0 +1,433 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::event::EventId;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::delivery_window::DeliveryWindow;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
+use ports::errors::RepositoryError;
+use ports::events_producer_port::Events;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use uuid::Uuid;
+
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::assign_orders_batch_command::AssignOrdersBatchCommand;
+use crate::usecases::commands::assign_orders_batch_handler::AssignOrdersBatchHandler;
+
+struct TestInboxRepository;
+
+impl InboxRepositoryPort for TestInboxRepository {
+    fn try_record(&mut self, _event_id: EventId) -> Result<bool, RepositoryError> {
+        Ok(true)
+    }
+}
+
+struct TestOutboxRepository {
+    events: Rc<RefCell<Vec<Events>>>,
+}
+
+impl OutboxRepositoryPort for TestOutboxRepository {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let event =
+            Events::try_from(message).map_err(|err| RepositoryError::MapError(err.to_string()))?;
+        self.events.borrow_mut().push(event);
+        Ok(())
+    }
+
+    fn update(&mut self, _message: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _limit: usize,
+        _lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn delete_published_before(
+        &mut self,
+        _cutoff: SystemTime,
+        _limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn replay(&mut self, _from: SystemTime, _to: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct StoredOrder {
+    id: OrderId,
+    courier_id: Option<CourierId>,
+    pickup_location: Option<Location>,
+    location: Location,
+    volume: Volume,
+    status: OrderStatus,
+    delivery_window: Option<DeliveryWindow>,
+    delivery_window_missed: bool,
+    created_at: SystemTime,
+    version: u64,
+}
+
+impl Display for StoredOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl StoredOrder {
+    fn from_order(order: &Order) -> Self {
+        Self {
+            id: order.id(),
+            courier_id: *order.courier_id(),
+            pickup_location: order.pickup_location().cloned(),
+            location: order.location().clone(),
+            volume: Volume::new(order.volume()).expect("volume must be positive"),
+            status: copy_status(order.status()),
+            delivery_window: order.delivery_window(),
+            delivery_window_missed: order.is_delivery_window_missed(),
+            created_at: order.created_at(),
+            version: order.version(),
+        }
+    }
+
+    fn to_order(&self) -> Order {
+        Order::restore(
+            self.id,
+            self.courier_id,
+            self.pickup_location.clone(),
+            self.location.clone(),
+            self.volume,
+            copy_status(&self.status),
+            self.delivery_window,
+            self.delivery_window_missed,
+            self.created_at,
+            self.version,
+        )
+    }
+
+    fn update_from(&mut self, order: &Order) {
+        self.courier_id = *order.courier_id();
+        self.location = order.location().clone();
+        self.volume = Volume::new(order.volume()).expect("volume must be positive");
+        self.status = copy_status(order.status());
+        self.delivery_window = order.delivery_window();
+        self.delivery_window_missed = order.is_delivery_window_missed();
+        self.version = order.version() + 1;
+    }
+}
+
+fn copy_status(status: &OrderStatus) -> OrderStatus {
+    match status {
+        OrderStatus::Created => OrderStatus::Created,
+        OrderStatus::Assigned => OrderStatus::Assigned,
+        OrderStatus::PickedUp => OrderStatus::PickedUp,
+        OrderStatus::Completed => OrderStatus::Completed,
+        OrderStatus::Cancelled => OrderStatus::Cancelled,
+    }
+}
+
+struct TestOrderRepository {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+}
+
+impl OrderRepositoryPort for TestOrderRepository {
+    fn add(&mut self, _order: &Order) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        let mut orders = self.orders.borrow_mut();
+        if let Some(stored) = orders.iter_mut().find(|o| o.id == order.id()) {
+            stored.update_from(order);
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("order not found".into()))
+    }
+
+    fn get_by_id(&mut self, _id: OrderId) -> Result<Order, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_any_new(&mut self) -> Result<Order, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        let orders = self.orders.borrow();
+        Ok(criteria.apply(orders.iter().map(StoredOrder::to_order)))
+    }
+
+    fn count(&mut self, _filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_history(&mut self, _id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct TestCourierRepository {
+    couriers: Rc<RefCell<Vec<Courier>>>,
+}
+
+impl CourierRepositoryPort for TestCourierRepository {
+    fn add(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        self.couriers.borrow_mut().push(courier);
+        Ok(())
+    }
+
+    fn update(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        let mut couriers = self.couriers.borrow_mut();
+        if let Some(existing) = couriers
+            .iter_mut()
+            .find(|stored| stored.id() == courier.id())
+        {
+            *existing = courier;
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_by_id(&mut self, _id: CourierId) -> Result<Courier, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(self.couriers.borrow().clone())
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(self.couriers.borrow().clone())
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct TestUnitOfWork {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+    couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
+}
+
+impl std::fmt::Debug for TestUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestUnitOfWork")
+            .field("orders", &self.orders)
+            .field("couriers", &self.couriers)
+            .field("outbox", &self.outbox)
+            .finish()
+    }
+}
+
+impl TestUnitOfWork {
+    fn from_state(
+        orders: Rc<RefCell<Vec<StoredOrder>>>,
+        couriers: Rc<RefCell<Vec<Courier>>>,
+        outbox: Rc<RefCell<Vec<Events>>>,
+    ) -> Self {
+        Self {
+            orders,
+            couriers,
+            outbox,
+        }
+    }
+}
+
+struct TestUnitOfWorkTx {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+    couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
+}
+
+impl UnitOfWorkPort for TestUnitOfWork {
+    type Uow = TestUnitOfWorkTx;
+    type CourierRepo = TestCourierRepository;
+    type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        let mut tx = TestUnitOfWorkTx {
+            orders: Rc::clone(&self.orders),
+            couriers: Rc::clone(&self.couriers),
+            outbox: Rc::clone(&self.outbox),
+        };
+        f(&mut tx)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        TestCourierRepository {
+            couriers: Rc::clone(&self.couriers),
+        }
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        TestOrderRepository {
+            orders: Rc::clone(&self.orders),
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
+}
+
+impl UnitOfWorkPort for TestUnitOfWorkTx {
+    type Uow = TestUnitOfWorkTx;
+    type CourierRepo = TestCourierRepository;
+    type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        f(self)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        TestCourierRepository {
+            couriers: Rc::clone(&self.couriers),
+        }
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        TestOrderRepository {
+            orders: Rc::clone(&self.orders),
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
+}
+
+fn courier_at(name: &str, x: u16, y: u16) -> Courier {
+    Courier::new(
+        CourierName(name.into()),
+        CourierSpeed(1),
+        Location::new(x, y).unwrap(),
+    )
+    .unwrap()
+}
+
+fn order_at(x: u16, y: u16) -> Order {
+    Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(x, y).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn handle_assigns_every_order_in_one_run() {
+    let orders = [order_at(3, 1), order_at(1, 1), order_at(9, 9)];
+    let couriers = vec![courier_at("Bob", 1, 1), courier_at("Rick", 5, 1)];
+    let bob_id = *couriers[0].id();
+    let rick_id = *couriers[1].id();
+    let orders_state = Rc::new(RefCell::new(
+        orders
+            .iter()
+            .map(StoredOrder::from_order)
+            .collect::<Vec<_>>(),
+    ));
+    let couriers_state = Rc::new(RefCell::new(couriers));
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = AssignOrdersBatchHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::clone(&events),
+        ),
+        CityMap::default(),
+    );
+
+    handler
+        .execute(AssignOrdersBatchCommand::new().unwrap())
+        .await
+        .expect("handler should finish successfully");
+
+    let orders = orders_state.borrow();
+    assert_eq!(orders[0].courier_id, Some(rick_id));
+    assert_eq!(orders[1].courier_id, Some(bob_id));
+    assert!(matches!(orders[2].status, OrderStatus::Created));
+    assert!(
+        couriers_state
+            .borrow()
+            .iter()
+            .all(|courier| courier.occupied_places() == 1)
+    );
+}
+
+#[tokio::test]
+async fn handle_does_nothing_without_orders() {
+    let orders_state = Rc::new(RefCell::new(Vec::new()));
+    let couriers_state = Rc::new(RefCell::new(vec![courier_at("Bob", 1, 1)]));
+
+    let mut handler = AssignOrdersBatchHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::new(RefCell::new(Vec::new())),
+        ),
+        CityMap::default(),
+    );
+
+    handler
+        .execute(AssignOrdersBatchCommand::new().unwrap())
+        .await
+        .expect("handler should finish successfully");
+
+    assert_eq!(couriers_state.borrow()[0].occupied_places(), 0);
+}
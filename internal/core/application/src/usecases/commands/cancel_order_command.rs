@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use domain::model::kernel::event::EventId;
+use domain::model::order::order_aggregate::OrderId;
+
+use crate::errors::command_errors::CommandError;
+
+#[derive(Clone)]
+pub struct CancelOrderCommand {
+    order_id: OrderId,
+    event_id: Option<EventId>,
+}
+
+impl CancelOrderCommand {
+    pub fn new(order_id: Uuid) -> Result<Self, CommandError> {
+        if order_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "order_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            order_id: OrderId::new(order_id),
+            event_id: None,
+        })
+    }
+
+    /// Ties the command to the integration event it came from, so a
+    /// redelivery of that event is recognised and skipped.
+    pub fn with_event_id(mut self, event_id: EventId) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    pub fn order_id(&self) -> OrderId {
+        self.order_id
+    }
+
+    pub fn event_id(&self) -> Option<EventId> {
+        self.event_id
+    }
+}
@@ -0,0 +1,79 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::errors::RepositoryError;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use std::fmt::Debug;
+use tracing::debug;
+use tracing::info;
+use tracing::instrument;
+use tracing::warn;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::cancel_order_command::CancelOrderCommand;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::outbox_events::stage_events;
+
+pub struct CancelOrderHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    uow: UOW,
+}
+
+impl<UOW> CancelOrderHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    pub fn new(uow: UOW) -> Self {
+        Self { uow }
+    }
+}
+
+impl<UOW> CommandHandler<CancelOrderCommand, ()> for CancelOrderHandler<UOW>
+where
+    UOW: UnitOfWorkPort + Debug,
+{
+    type Error = CommandError;
+
+    #[instrument(skip_all, fields(order_id = %command.order_id().0))]
+    async fn execute(&mut self, command: CancelOrderCommand) -> Result<(), Self::Error> {
+        let uow = &mut self.uow;
+        retry_on_conflict(|| {
+            uow.transaction(|tx| {
+                if let Some(event_id) = command.event_id()
+                    && !tx.inbox_repo().try_record(event_id)?
+                {
+                    info!(event_id = %event_id.0, "skipping already processed basket event");
+                    return Ok(());
+                }
+
+                let mut order = tx.order_repo().get_by_id(command.order_id())?;
+
+                order
+                    .cancel()
+                    .map_err(|err| RepositoryError::from(err.to_string()))?;
+
+                if let Some(courier_id) = *order.courier_id() {
+                    let mut courier_repo = tx.courier_repo();
+                    match courier_repo.get_by_id(courier_id) {
+                        Ok(mut courier) => {
+                            debug!("releasing storage place of courier {}", &courier_id.0);
+                            courier.release_order(order.id());
+                            courier_repo.update(courier)?;
+                        }
+                        Err(RepositoryError::NotFound(_)) => {
+                            warn!("courier by id {} not found", &courier_id.0);
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                tx.order_repo().update(&order)?;
+                stage_events(tx, order.pop_domain_events())
+            })
+            .map_err(CommandError::from)
+        })
+    }
+}
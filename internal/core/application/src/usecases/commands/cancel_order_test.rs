@@ -0,0 +1,524 @@
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::event::EventId;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::delivery_window::DeliveryWindow;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
+use domain::model::order::order_events::OrderEvent;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
+use ports::errors::RepositoryError;
+use ports::events_producer_port::Events;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use uuid::Uuid;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::cancel_order_command::CancelOrderCommand;
+use crate::usecases::commands::cancel_order_handler::CancelOrderHandler;
+
+struct TestInboxRepository {
+    event_ids: Rc<RefCell<Vec<EventId>>>,
+}
+
+impl InboxRepositoryPort for TestInboxRepository {
+    fn try_record(&mut self, event_id: EventId) -> Result<bool, RepositoryError> {
+        let mut event_ids = self.event_ids.borrow_mut();
+        if event_ids.contains(&event_id) {
+            return Ok(false);
+        }
+        event_ids.push(event_id);
+        Ok(true)
+    }
+}
+
+struct TestOutboxRepository {
+    events: Rc<RefCell<Vec<Events>>>,
+}
+
+impl OutboxRepositoryPort for TestOutboxRepository {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let event =
+            Events::try_from(message).map_err(|err| RepositoryError::MapError(err.to_string()))?;
+        self.events.borrow_mut().push(event);
+        Ok(())
+    }
+
+    fn update(&mut self, _message: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _limit: usize,
+        _lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn delete_published_before(
+        &mut self,
+        _cutoff: SystemTime,
+        _limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn replay(&mut self, _from: SystemTime, _to: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct StoredOrder {
+    id: OrderId,
+    courier_id: Option<CourierId>,
+    pickup_location: Option<Location>,
+    location: Location,
+    volume: Volume,
+    status: OrderStatus,
+    delivery_window: Option<DeliveryWindow>,
+    delivery_window_missed: bool,
+    created_at: SystemTime,
+    version: u64,
+}
+
+impl Display for StoredOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl StoredOrder {
+    fn from_order(order: &Order) -> Self {
+        Self {
+            id: order.id(),
+            courier_id: *order.courier_id(),
+            pickup_location: order.pickup_location().cloned(),
+            location: order.location().clone(),
+            volume: Volume::new(order.volume()).expect("volume must be positive"),
+            status: copy_status(order.status()),
+            delivery_window: order.delivery_window(),
+            delivery_window_missed: order.is_delivery_window_missed(),
+            created_at: order.created_at(),
+            version: order.version(),
+        }
+    }
+
+    fn to_order(&self) -> Order {
+        Order::restore(
+            self.id,
+            self.courier_id,
+            self.pickup_location.clone(),
+            self.location.clone(),
+            self.volume,
+            copy_status(&self.status),
+            self.delivery_window,
+            self.delivery_window_missed,
+            self.created_at,
+            self.version,
+        )
+    }
+
+    fn update_from(&mut self, order: &Order) {
+        self.courier_id = *order.courier_id();
+        self.location = order.location().clone();
+        self.volume = Volume::new(order.volume()).expect("volume must be positive");
+        self.status = copy_status(order.status());
+        self.delivery_window = order.delivery_window();
+        self.delivery_window_missed = order.is_delivery_window_missed();
+        self.version = order.version() + 1;
+    }
+}
+
+fn copy_status(status: &OrderStatus) -> OrderStatus {
+    match status {
+        OrderStatus::Created => OrderStatus::Created,
+        OrderStatus::Assigned => OrderStatus::Assigned,
+        OrderStatus::PickedUp => OrderStatus::PickedUp,
+        OrderStatus::Completed => OrderStatus::Completed,
+        OrderStatus::Cancelled => OrderStatus::Cancelled,
+    }
+}
+
+struct TestOrderRepository {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+}
+
+impl OrderRepositoryPort for TestOrderRepository {
+    fn add(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.orders
+            .borrow_mut()
+            .push(StoredOrder::from_order(order));
+        Ok(())
+    }
+
+    fn update(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        let mut orders = self.orders.borrow_mut();
+        if let Some(stored) = orders.iter_mut().find(|o| o.id == order.id()) {
+            stored.update_from(order);
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("order not found".into()))
+    }
+
+    fn get_by_id(&mut self, id: OrderId) -> Result<Order, RepositoryError> {
+        self.orders
+            .borrow()
+            .iter()
+            .find(|order| order.id == id)
+            .map(StoredOrder::to_order)
+            .ok_or_else(|| RepositoryError::NotFound("order not found".into()))
+    }
+
+    fn get_any_new(&mut self) -> Result<Order, RepositoryError> {
+        self.orders
+            .borrow()
+            .iter()
+            .find(|order| matches!(order.status, OrderStatus::Created))
+            .map(StoredOrder::to_order)
+            .ok_or_else(|| RepositoryError::NotFound("no new orders available".into()))
+    }
+
+    fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError> {
+        Ok(self
+            .orders
+            .borrow()
+            .iter()
+            .filter(|order| matches!(order.status, OrderStatus::Assigned | OrderStatus::PickedUp))
+            .map(StoredOrder::to_order)
+            .collect())
+    }
+
+    fn find(&mut self, _criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn count(&mut self, _filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_history(&mut self, _id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct TestCourierRepository {
+    couriers: Rc<RefCell<Vec<Courier>>>,
+}
+
+impl CourierRepositoryPort for TestCourierRepository {
+    fn add(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        self.couriers.borrow_mut().push(courier);
+        Ok(())
+    }
+
+    fn update(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        let mut couriers = self.couriers.borrow_mut();
+        if let Some(existing) = couriers
+            .iter_mut()
+            .find(|stored| stored.id() == courier.id())
+        {
+            *existing = courier;
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_by_id(&mut self, id: CourierId) -> Result<Courier, RepositoryError> {
+        self.couriers
+            .borrow()
+            .iter()
+            .find(|courier| courier.id() == &id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(vec![])
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+struct TestUnitOfWork {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+    couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
+    inbox: Rc<RefCell<Vec<EventId>>>,
+}
+
+impl std::fmt::Debug for TestUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestUnitOfWork")
+            .field("orders", &self.orders)
+            .field("couriers", &self.couriers)
+            .field("outbox", &self.outbox)
+            .field("inbox", &self.inbox)
+            .finish()
+    }
+}
+
+impl TestUnitOfWork {
+    fn from_state(
+        orders: Rc<RefCell<Vec<StoredOrder>>>,
+        couriers: Rc<RefCell<Vec<Courier>>>,
+        outbox: Rc<RefCell<Vec<Events>>>,
+    ) -> Self {
+        Self {
+            orders,
+            couriers,
+            outbox,
+            inbox: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+}
+
+struct TestUnitOfWorkTx {
+    orders: Rc<RefCell<Vec<StoredOrder>>>,
+    couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
+    inbox: Rc<RefCell<Vec<EventId>>>,
+}
+
+impl UnitOfWorkPort for TestUnitOfWork {
+    type Uow = TestUnitOfWorkTx;
+    type CourierRepo = TestCourierRepository;
+    type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        let mut tx = TestUnitOfWorkTx {
+            orders: Rc::clone(&self.orders),
+            couriers: Rc::clone(&self.couriers),
+            outbox: Rc::clone(&self.outbox),
+            inbox: Rc::clone(&self.inbox),
+        };
+        f(&mut tx)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        TestCourierRepository {
+            couriers: Rc::clone(&self.couriers),
+        }
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        TestOrderRepository {
+            orders: Rc::clone(&self.orders),
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository {
+            event_ids: Rc::clone(&self.inbox),
+        }
+    }
+}
+
+impl UnitOfWorkPort for TestUnitOfWorkTx {
+    type Uow = TestUnitOfWorkTx;
+    type CourierRepo = TestCourierRepository;
+    type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        f(self)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        TestCourierRepository {
+            couriers: Rc::clone(&self.couriers),
+        }
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        TestOrderRepository {
+            orders: Rc::clone(&self.orders),
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository {
+            event_ids: Rc::clone(&self.inbox),
+        }
+    }
+}
+
+fn initial_state() -> (Vec<StoredOrder>, Vec<Courier>, OrderId) {
+    let mut courier = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(1),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(9, 9).unwrap(),
+        Volume::new(10).unwrap(),
+    )
+    .unwrap();
+    courier
+        .take_order(
+            order.id(),
+            Volume::new(10).unwrap(),
+            None,
+            order.location(),
+            &CityMap::default(),
+        )
+        .expect("courier should take order");
+    order
+        .assign(courier.id())
+        .expect("order assignment should succeed");
+
+    let order_id = order.id();
+    (
+        vec![StoredOrder::from_order(&order)],
+        vec![courier],
+        order_id,
+    )
+}
+
+#[tokio::test]
+async fn handle_cancels_order_and_frees_storage_place() {
+    let (orders, couriers, order_id) = initial_state();
+    let orders_state = Rc::new(RefCell::new(orders));
+    let couriers_state = Rc::new(RefCell::new(couriers));
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = CancelOrderHandler::new(TestUnitOfWork::from_state(
+        Rc::clone(&orders_state),
+        Rc::clone(&couriers_state),
+        Rc::clone(&observed_events),
+    ));
+    let command = CancelOrderCommand::new(order_id.0).expect("command should be valid");
+
+    handler
+        .execute(command)
+        .await
+        .expect("handler should finish successfully");
+
+    let orders = orders_state.borrow();
+    assert!(matches!(orders[0].status, OrderStatus::Cancelled));
+
+    let couriers = couriers_state.borrow();
+    assert!(
+        couriers[0]
+            .storage_places()
+            .iter()
+            .all(|sp| sp.order_id().is_none()),
+        "cancelling should free the courier storage place"
+    );
+
+    let events = observed_events.borrow();
+    assert!(matches!(
+        events.as_slice(),
+        [Events::Order(OrderEvent::Cancelled(_))]
+    ));
+}
+
+#[tokio::test]
+async fn handle_fails_for_completed_order() {
+    let (mut orders, couriers, order_id) = initial_state();
+    orders[0].status = OrderStatus::Completed;
+    let orders_state = Rc::new(RefCell::new(orders));
+    let couriers_state = Rc::new(RefCell::new(couriers));
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = CancelOrderHandler::new(TestUnitOfWork::from_state(
+        Rc::clone(&orders_state),
+        Rc::clone(&couriers_state),
+        Rc::clone(&observed_events),
+    ));
+    let command = CancelOrderCommand::new(order_id.0).expect("command should be valid");
+
+    let result = handler.execute(command).await;
+
+    assert!(
+        matches!(result, Err(CommandError::DomainError(_))),
+        "completed orders cannot be cancelled"
+    );
+    assert!(matches!(
+        orders_state.borrow()[0].status,
+        OrderStatus::Completed
+    ));
+    assert!(observed_events.borrow().is_empty());
+}
+
+#[tokio::test]
+async fn handle_acknowledges_redelivered_event_without_side_effects() {
+    let (orders, couriers, order_id) = initial_state();
+    let orders_state = Rc::new(RefCell::new(orders));
+    let couriers_state = Rc::new(RefCell::new(couriers));
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = CancelOrderHandler::new(TestUnitOfWork::from_state(
+        Rc::clone(&orders_state),
+        Rc::clone(&couriers_state),
+        Rc::clone(&observed_events),
+    ));
+    let event_id = EventId::default();
+    for _ in 0..2 {
+        let command = CancelOrderCommand::new(order_id.0)
+            .expect("command should be valid")
+            .with_event_id(event_id);
+        handler
+            .execute(command)
+            .await
+            .expect("a redelivered event should be acknowledged");
+    }
+
+    assert!(matches!(
+        orders_state.borrow()[0].status,
+        OrderStatus::Cancelled
+    ));
+    assert_eq!(observed_events.borrow().len(), 1);
+}
@@ -0,0 +1,22 @@
+use tracing::warn;
+
+use crate::errors::command_errors::CommandError;
+
+pub const CONFLICT_ATTEMPTS: usize = 3;
+
+/// Runs `attempt` again from scratch while it loses an optimistic concurrency
+/// race, so every retry reloads the aggregates it changes.
+pub fn retry_on_conflict<T>(
+    mut attempt: impl FnMut() -> Result<T, CommandError>,
+) -> Result<T, CommandError> {
+    let mut tries = 1;
+    loop {
+        match attempt() {
+            Err(CommandError::Conflict(msg)) if tries < CONFLICT_ATTEMPTS => {
+                warn!(attempt = tries, "retrying after conflict: {}", msg);
+                tries += 1;
+            }
+            result => return result,
+        }
+    }
+}
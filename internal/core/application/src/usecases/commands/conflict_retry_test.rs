@@ -0,0 +1,41 @@
+use crate::errors::command_errors::CommandError;
+use crate::usecases::commands::conflict_retry::CONFLICT_ATTEMPTS;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+
+#[test]
+fn retries_until_attempt_succeeds() {
+    let mut calls = 0;
+    let result = retry_on_conflict(|| {
+        calls += 1;
+        if calls < CONFLICT_ATTEMPTS {
+            return Err(CommandError::Conflict("stale".into()));
+        }
+        Ok(calls)
+    });
+
+    assert_eq!(result.unwrap(), CONFLICT_ATTEMPTS);
+}
+
+#[test]
+fn gives_up_after_last_attempt() {
+    let mut calls = 0;
+    let result: Result<(), CommandError> = retry_on_conflict(|| {
+        calls += 1;
+        Err(CommandError::Conflict("stale".into()))
+    });
+
+    assert!(matches!(result, Err(CommandError::Conflict(_))));
+    assert_eq!(calls, CONFLICT_ATTEMPTS);
+}
+
+#[test]
+fn does_not_retry_other_errors() {
+    let mut calls = 0;
+    let result: Result<(), CommandError> = retry_on_conflict(|| {
+        calls += 1;
+        Err(CommandError::NotFound("courier".into()))
+    });
+
+    assert!(matches!(result, Err(CommandError::NotFound(_))));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::courier::shift::CourierStatus;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::OrderId;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
+use ports::errors::RepositoryError;
+use uuid::Uuid;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::add_storage_place_command::AddStoragePlaceCommand;
+use crate::usecases::commands::add_storage_place_handler::AddStoragePlaceHandler;
+use crate::usecases::commands::deactivate_courier_command::DeactivateCourierCommand;
+use crate::usecases::commands::deactivate_courier_handler::DeactivateCourierHandler;
+use crate::usecases::commands::remove_storage_place_command::RemoveStoragePlaceCommand;
+use crate::usecases::commands::remove_storage_place_handler::RemoveStoragePlaceHandler;
+use crate::usecases::commands::update_courier_command::UpdateCourierCommand;
+use crate::usecases::commands::update_courier_handler::UpdateCourierHandler;
+
+#[derive(Clone)]
+struct TestCourierRepository {
+    couriers: Rc<RefCell<Vec<Courier>>>,
+}
+
+impl CourierRepositoryPort for TestCourierRepository {
+    fn add(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        self.couriers.borrow_mut().push(courier);
+        Ok(())
+    }
+
+    fn update(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        let mut couriers = self.couriers.borrow_mut();
+        if let Some(existing) = couriers
+            .iter_mut()
+            .find(|stored| stored.id() == courier.id())
+        {
+            *existing = courier;
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_by_id(&mut self, id: CourierId) -> Result<Courier, RepositoryError> {
+        self.couriers
+            .borrow()
+            .iter()
+            .find(|courier| courier.id() == &id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(self
+            .couriers
+            .borrow()
+            .iter()
+            .filter(|courier| courier.shift().is_some())
+            .cloned()
+            .collect())
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+fn repository_with_courier() -> (TestCourierRepository, CourierId) {
+    let courier = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(2),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    let courier_id = *courier.id();
+
+    (
+        TestCourierRepository {
+            couriers: Rc::new(RefCell::new(vec![courier])),
+        },
+        courier_id,
+    )
+}
+
+#[tokio::test]
+async fn updates_name_and_speed() {
+    let (mut repo, courier_id) = repository_with_courier();
+
+    UpdateCourierHandler::new(repo.clone())
+        .execute(
+            UpdateCourierCommand::new(
+                courier_id.0,
+                Some(CourierName("Rick".into())),
+                Some(CourierSpeed(4)),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let courier = repo.get_by_id(courier_id).unwrap();
+    assert_eq!(courier.name(), "Rick");
+    assert_eq!(*courier.speed(), 4);
+}
+
+#[test]
+fn rejects_empty_update() {
+    assert!(UpdateCourierCommand::new(Uuid::new_v4(), None, None).is_err());
+}
+
+#[tokio::test]
+async fn reports_missing_courier() {
+    let (repo, _) = repository_with_courier();
+
+    let result = DeactivateCourierHandler::new(repo)
+        .execute(DeactivateCourierCommand::new(Uuid::new_v4()).unwrap())
+        .await;
+
+    assert!(matches!(result, Err(CommandError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn adds_and_removes_storage_place() {
+    let (mut repo, courier_id) = repository_with_courier();
+
+    let storage_place_id = AddStoragePlaceHandler::new(repo.clone())
+        .execute(AddStoragePlaceCommand::new(courier_id.0, "trunk".into(), 20).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().storage_places().len(),
+        2
+    );
+
+    RemoveStoragePlaceHandler::new(repo.clone())
+        .execute(RemoveStoragePlaceCommand::new(courier_id.0, storage_place_id).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().storage_places().len(),
+        1
+    );
+
+    let result = RemoveStoragePlaceHandler::new(repo.clone())
+        .execute(RemoveStoragePlaceCommand::new(courier_id.0, storage_place_id).unwrap())
+        .await;
+    assert!(matches!(result, Err(CommandError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn refuses_to_remove_occupied_storage_place() {
+    let (mut repo, courier_id) = repository_with_courier();
+    let storage_place_id = AddStoragePlaceHandler::new(repo.clone())
+        .execute(AddStoragePlaceCommand::new(courier_id.0, "trunk".into(), 20).unwrap())
+        .await
+        .unwrap();
+
+    let mut courier = repo.get_by_id(courier_id).unwrap();
+    courier
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(15).unwrap(),
+            None,
+            &Location::new(3, 3).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    repo.update(courier).unwrap();
+
+    let result = RemoveStoragePlaceHandler::new(repo.clone())
+        .execute(RemoveStoragePlaceCommand::new(courier_id.0, storage_place_id).unwrap())
+        .await;
+    assert!(matches!(result, Err(CommandError::ExecutionError(_))));
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().storage_places().len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn deactivates_courier() {
+    let (mut repo, courier_id) = repository_with_courier();
+
+    DeactivateCourierHandler::new(repo.clone())
+        .execute(DeactivateCourierCommand::new(courier_id.0).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().status(),
+        CourierStatus::Deactivated
+    );
+}
@@ -0,0 +1,211 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::courier::shift::CourierStatus;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::OrderId;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
+use ports::errors::RepositoryError;
+use uuid::Uuid;
+
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::apply_shift_schedules_command::ApplyShiftSchedulesCommand;
+use crate::usecases::commands::apply_shift_schedules_handler::ApplyShiftSchedulesHandler;
+use crate::usecases::commands::end_shift_command::EndShiftCommand;
+use crate::usecases::commands::end_shift_handler::EndShiftHandler;
+use crate::usecases::commands::schedule_shift_command::ScheduleShiftCommand;
+use crate::usecases::commands::schedule_shift_handler::ScheduleShiftHandler;
+use crate::usecases::commands::start_shift_command::StartShiftCommand;
+use crate::usecases::commands::start_shift_handler::StartShiftHandler;
+use crate::usecases::commands::take_break_command::TakeBreakCommand;
+use crate::usecases::commands::take_break_handler::TakeBreakHandler;
+
+#[derive(Clone)]
+struct TestCourierRepository {
+    couriers: Rc<RefCell<Vec<Courier>>>,
+}
+
+impl CourierRepositoryPort for TestCourierRepository {
+    fn add(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        self.couriers.borrow_mut().push(courier);
+        Ok(())
+    }
+
+    fn update(&mut self, courier: Courier) -> Result<(), RepositoryError> {
+        let mut couriers = self.couriers.borrow_mut();
+        if let Some(existing) = couriers
+            .iter_mut()
+            .find(|stored| stored.id() == courier.id())
+        {
+            *existing = courier;
+            return Ok(());
+        }
+
+        Err(RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_by_id(&mut self, id: CourierId) -> Result<Courier, RepositoryError> {
+        self.couriers
+            .borrow()
+            .iter()
+            .find(|courier| courier.id() == &id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound("courier not found".into()))
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(self
+            .couriers
+            .borrow()
+            .iter()
+            .filter(|courier| courier.shift().is_some())
+            .cloned()
+            .collect())
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!()
+    }
+}
+
+fn repository_with_courier() -> (TestCourierRepository, CourierId) {
+    let courier = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(2),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    let courier_id = *courier.id();
+
+    (
+        TestCourierRepository {
+            couriers: Rc::new(RefCell::new(vec![courier])),
+        },
+        courier_id,
+    )
+}
+
+#[tokio::test]
+async fn ends_and_starts_shift() {
+    let (mut repo, courier_id) = repository_with_courier();
+
+    EndShiftHandler::new(repo.clone())
+        .execute(EndShiftCommand::new(courier_id.0).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().status(),
+        CourierStatus::OffDuty
+    );
+
+    StartShiftHandler::new(repo.clone())
+        .execute(StartShiftCommand::new(courier_id.0).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().status(),
+        CourierStatus::Available
+    );
+}
+
+#[tokio::test]
+async fn courier_with_orders_finishes_deliveries_before_going_off_duty() {
+    let (mut repo, courier_id) = repository_with_courier();
+    let order_id = OrderId::new(Uuid::new_v4());
+    let mut courier = repo.get_by_id(courier_id).unwrap();
+    courier
+        .take_order(
+            order_id,
+            Volume::new(5).unwrap(),
+            None,
+            &Location::new(3, 3).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    repo.update(courier).unwrap();
+
+    EndShiftHandler::new(repo.clone())
+        .execute(EndShiftCommand::new(courier_id.0).unwrap())
+        .await
+        .unwrap();
+
+    let mut courier = repo.get_by_id(courier_id).unwrap();
+    assert_eq!(courier.status(), CourierStatus::Busy);
+    assert!(courier.is_ending_shift());
+
+    courier.complete_order(order_id);
+    assert_eq!(courier.status(), CourierStatus::OffDuty);
+}
+
+#[tokio::test]
+async fn takes_and_ends_break() {
+    let (mut repo, courier_id) = repository_with_courier();
+
+    TakeBreakHandler::new(repo.clone())
+        .execute(TakeBreakCommand::new(courier_id.0, true).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().status(),
+        CourierStatus::OnBreak
+    );
+
+    TakeBreakHandler::new(repo.clone())
+        .execute(TakeBreakCommand::new(courier_id.0, false).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_by_id(courier_id).unwrap().status(),
+        CourierStatus::Available
+    );
+}
+
+#[tokio::test]
+async fn applies_scheduled_shift_once_it_starts() {
+    let (mut repo, courier_id) = repository_with_courier();
+    let now = SystemTime::now();
+
+    EndShiftHandler::new(repo.clone())
+        .execute(EndShiftCommand::new(courier_id.0).unwrap())
+        .await
+        .unwrap();
+    ScheduleShiftHandler::new(repo.clone())
+        .execute(
+            ScheduleShiftCommand::new(
+                courier_id.0,
+                now - Duration::from_secs(60),
+                now + Duration::from_secs(3600),
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    ApplyShiftSchedulesHandler::new(repo.clone())
+        .execute(ApplyShiftSchedulesCommand::new().unwrap())
+        .await
+        .unwrap();
+
+    let courier = repo.get_by_id(courier_id).unwrap();
+    assert_eq!(courier.status(), CourierStatus::Available);
+    assert!(courier.is_dispatchable(SystemTime::now()));
+}
+
+#[test]
+fn rejects_shift_ending_before_it_starts() {
+    let now = SystemTime::now();
+    assert!(ScheduleShiftCommand::new(Uuid::new_v4(), now, now).is_err());
+}
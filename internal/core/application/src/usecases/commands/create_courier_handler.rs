@@ -1,4 +1,5 @@
 use domain::model::courier::courier_aggregate::Courier;
+use domain::model::kernel::coordinate_system::CoordinateSystem;
 use domain::model::kernel::location::Location;
 use ports::courier_repository_port::CourierRepositoryPort;
 
@@ -11,14 +12,19 @@ where
     CR: CourierRepositoryPort,
 {
     courier_repository: CR,
+    coordinate_system: CoordinateSystem,
 }
 
 impl<CR> CreateCourierHandler<CR>
 where
     CR: CourierRepositoryPort,
 {
-    pub fn new(courier_repository: CR) -> Self {
-        Self { courier_repository }
+    /// New couriers start at a random location of `coordinate_system`.
+    pub fn new(courier_repository: CR, coordinate_system: CoordinateSystem) -> Self {
+        Self {
+            courier_repository,
+            coordinate_system,
+        }
     }
 }
 
@@ -32,7 +38,7 @@ where
         let courier = Courier::new(
             command.name().to_owned(),
             command.speed().to_owned(),
-            Location::new_random(),
+            Location::new_random(&self.coordinate_system),
         )?;
         self.courier_repository
             .add(courier)
@@ -1,24 +1,41 @@
 use uuid::Uuid;
 
+use domain::model::kernel::event::EventId;
 use domain::model::kernel::volume::Volume;
 use domain::model::order::order_aggregate::OrderId;
 
 use crate::errors::command_errors::CommandError;
 
+#[derive(Clone)]
 pub struct CreateOrderCommand {
     order_id: OrderId,
     street: String,
     volume: Volume,
+    delivery_period: Option<(u8, u8)>,
+    event_id: Option<EventId>,
 }
 
 impl CreateOrderCommand {
-    pub fn new(order_id: Uuid, street: String, volume: u16) -> Result<Self, CommandError> {
+    pub fn new(
+        order_id: Uuid,
+        street: String,
+        volume: u16,
+        delivery_period: Option<(u8, u8)>,
+    ) -> Result<Self, CommandError> {
         if street.is_empty() {
             return Err(CommandError::ArgumentError(format!(
                 "Found empty street: {}",
                 street
             )));
         }
+        if let Some((from, to)) = delivery_period
+            && (from >= to || to > 24)
+        {
+            return Err(CommandError::ArgumentError(format!(
+                "Invalid delivery period: {}-{}",
+                from, to
+            )));
+        }
         let volume = Volume::new(volume).map_err(CommandError::from)?;
         let order_id = OrderId::new(order_id);
 
@@ -26,9 +43,18 @@ impl CreateOrderCommand {
             order_id,
             volume,
             street,
+            delivery_period,
+            event_id: None,
         })
     }
 
+    /// Ties the command to the integration event it came from, so a
+    /// redelivery of that event is recognised and skipped.
+    pub fn with_event_id(mut self, event_id: EventId) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
     pub fn order_id(&self) -> OrderId {
         self.order_id
     }
@@ -40,4 +66,12 @@ impl CreateOrderCommand {
     pub fn volume(&self) -> Volume {
         self.volume
     }
+
+    pub fn delivery_period(&self) -> Option<(u8, u8)> {
+        self.delivery_period
+    }
+
+    pub fn event_id(&self) -> Option<EventId> {
+        self.event_id
+    }
 }
@@ -1,65 +1,74 @@
+use domain::model::order::delivery_window::DeliveryWindow;
 use domain::model::order::order_aggregate::Order;
-use ports::events_producer_port::Events;
 use ports::geo_service_port::GeoServicePort;
+use ports::inbox_repository_port::InboxRepositoryPort;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use std::time::SystemTime;
+use tracing::info;
 
 use crate::errors::command_errors::CommandError;
 use crate::usecases::CommandHandler;
 use crate::usecases::commands::create_order_command::CreateOrderCommand;
-use crate::usecases::events::event_bus::EventBus;
+use crate::usecases::commands::outbox_events::stage_events;
 
-pub struct CreateOrderHandler<OR, GS, EB>
+pub struct CreateOrderHandler<UOW, GS>
 where
-    OR: OrderRepositoryPort,
+    UOW: UnitOfWorkPort,
     GS: GeoServicePort,
-    EB: EventBus,
 {
-    order_repository: OR,
+    uow: UOW,
     geo_service: GS,
-    event_bus: EB,
+    pickup_street: Option<String>,
 }
 
-impl<OR, GS, EB> CreateOrderHandler<OR, GS, EB>
+impl<UOW, GS> CreateOrderHandler<UOW, GS>
 where
-    OR: OrderRepositoryPort,
+    UOW: UnitOfWorkPort,
     GS: GeoServicePort,
-    EB: EventBus,
 {
-    pub fn new(order_repository: OR, geo_service: GS, event_bus: EB) -> Self {
+    /// Orders get `pickup_street`, when set, as the point couriers collect
+    /// them from before heading to the drop-off.
+    pub fn new(uow: UOW, geo_service: GS, pickup_street: Option<String>) -> Self {
         Self {
-            order_repository,
+            uow,
             geo_service,
-            event_bus,
+            pickup_street,
         }
     }
 }
 
-impl<OR, GS, EB> CommandHandler<CreateOrderCommand, ()> for CreateOrderHandler<OR, GS, EB>
+impl<UOW, GS> CommandHandler<CreateOrderCommand, ()> for CreateOrderHandler<UOW, GS>
 where
-    OR: OrderRepositoryPort,
+    UOW: UnitOfWorkPort,
     GS: GeoServicePort,
-    EB: EventBus,
 {
     type Error = CommandError;
 
     async fn execute(&mut self, command: CreateOrderCommand) -> Result<(), Self::Error> {
-        let location = self
-            .geo_service
-            .get_location(command.street())
-            .await
-            .map_err(|e| CommandError::ExecutionError(e.to_string()))?;
-        let mut order = Order::new(command.order_id(), location, command.volume())
-            .map_err(|e| CommandError::ExecutionError(e.to_string()))?;
-
-        self.order_repository
-            .add(&order)
-            .map_err(|e| CommandError::ExecutionError(e.to_string()))?;
-
-        let events: Vec<Events> = order.pop_domain_events().into_iter().map(Events::from).collect();
-        for event in events {
-            self.event_bus.commit(event).await?;
+        let location = self.geo_service.get_location(command.street()).await?;
+        let mut order = Order::new(command.order_id(), location, command.volume())?;
+        if let Some(street) = self.pickup_street.clone() {
+            let pickup = self.geo_service.get_location(street).await?;
+            order.set_pickup_location(pickup)?;
         }
+        if let Some((from, to)) = command.delivery_period() {
+            let window = DeliveryWindow::from_hours(SystemTime::now(), from, to)?;
+            order.set_delivery_window(window)?;
+        }
+
+        self.uow
+            .transaction(|tx| {
+                if let Some(event_id) = command.event_id()
+                    && !tx.inbox_repo().try_record(event_id)?
+                {
+                    info!(event_id = %event_id.0, "skipping already processed basket event");
+                    return Ok(());
+                }
 
-        Ok(())
+                tx.order_repo().add(&order)?;
+                stage_events(tx, order.pop_domain_events())
+            })
+            .map_err(CommandError::from)
     }
 }
@@ -1,19 +1,31 @@
 use async_trait::async_trait;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::kernel::event::EventId;
 use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::courier_repository_port::GetAllCouriersResponse;
 use ports::errors::GeoClientError;
 use ports::errors::RepositoryError;
 use ports::geo_service_port::GeoServicePort;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 use crate::errors::command_errors::CommandError;
 use crate::usecases::CommandHandler;
-use crate::usecases::Handler;
-use crate::usecases::events::event_bus::EventBus;
 
 use super::create_order_command::CreateOrderCommand;
 use super::create_order_handler::CreateOrderHandler;
@@ -66,9 +78,17 @@ impl OrderRepositoryPort for MockOrderRepository {
         unimplemented!("not required for this test");
     }
 
-    fn raw(&mut self, _: String) -> Result<Vec<Order>, RepositoryError> {
+    fn find(&mut self, _criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
         unimplemented!("not required for this test");
     }
+
+    fn count(&mut self, _filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn get_history(&mut self, _id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -83,41 +103,161 @@ impl GeoServicePort for GeoServiceMock {
     }
 }
 
+struct MockCourierRepository;
+
+impl CourierRepositoryPort for MockCourierRepository {
+    fn add(&mut self, _: Courier) -> Result<(), RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn update(&mut self, _: Courier) -> Result<(), RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn get_by_id(&mut self, _: CourierId) -> Result<Courier, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+}
+
 #[derive(Clone)]
-struct RecordingEventBus {
-    events: Arc<Mutex<Vec<Events>>>,
+struct RecordingOutboxRepository {
+    names: Arc<Mutex<Vec<String>>>,
 }
 
-impl RecordingEventBus {
-    fn new(events: Arc<Mutex<Vec<Events>>>) -> Self {
-        Self { events }
+impl OutboxRepositoryPort for RecordingOutboxRepository {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let mut names = self.names.lock().expect("outbox poisoned");
+        names.push(message.name.clone());
+        Ok(())
+    }
+
+    fn update(&mut self, _: &Message) -> Result<(), RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _limit: usize,
+        _lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!("not required for this test");
+    }
+
+    fn delete_published_before(
+        &mut self,
+        _cutoff: SystemTime,
+        _limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        unimplemented!("not required for this test")
+    }
+
+    fn replay(&mut self, _from: SystemTime, _to: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!("not required for this test")
     }
 }
 
-#[async_trait]
-impl EventBus for RecordingEventBus {
-    fn register_order_created(&mut self, _subscriber: impl Handler + 'static) {}
+#[derive(Clone, Default)]
+struct RecordingInboxRepository {
+    event_ids: Arc<Mutex<Vec<EventId>>>,
+}
+
+impl InboxRepositoryPort for RecordingInboxRepository {
+    fn try_record(&mut self, event_id: EventId) -> Result<bool, RepositoryError> {
+        let mut event_ids = self.event_ids.lock().expect("inbox poisoned");
+        if event_ids.contains(&event_id) {
+            return Ok(false);
+        }
+        event_ids.push(event_id);
+        Ok(true)
+    }
+}
 
-    fn register_order_completed(&mut self, _subscriber: impl Handler + 'static) {}
+struct MockUnitOfWork {
+    added_order_id: Arc<Mutex<Option<OrderId>>>,
+    fail_on_add: bool,
+    outbox: RecordingOutboxRepository,
+    inbox: RecordingInboxRepository,
+}
 
-    async fn commit(&self, event: Events) -> Result<(), CommandError> {
-        let mut events = self.events.lock().expect("event log poisoned");
-        events.push(event);
-        Ok(())
+impl MockUnitOfWork {
+    fn new(added_order_id: Arc<Mutex<Option<OrderId>>>, outbox: Arc<Mutex<Vec<String>>>) -> Self {
+        Self {
+            added_order_id,
+            fail_on_add: false,
+            outbox: RecordingOutboxRepository { names: outbox },
+            inbox: RecordingInboxRepository::default(),
+        }
+    }
+
+    fn new_failing(
+        added_order_id: Arc<Mutex<Option<OrderId>>>,
+        outbox: Arc<Mutex<Vec<String>>>,
+    ) -> Self {
+        Self {
+            fail_on_add: true,
+            ..Self::new(added_order_id, outbox)
+        }
+    }
+}
+
+impl UnitOfWorkPort for MockUnitOfWork {
+    type Uow = MockUnitOfWork;
+    type CourierRepo = MockCourierRepository;
+    type OrderRepo = MockOrderRepository;
+    type OutboxRepo = RecordingOutboxRepository;
+    type InboxRepo = RecordingInboxRepository;
+
+    fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
+    where
+        F: for<'tx> FnOnce(&mut Self::Uow) -> Result<T, RepositoryError>,
+    {
+        f(self)
+    }
+
+    fn courier_repo(&mut self) -> Self::CourierRepo {
+        MockCourierRepository
+    }
+
+    fn order_repo(&mut self) -> Self::OrderRepo {
+        if self.fail_on_add {
+            MockOrderRepository::new_failing(self.added_order_id.clone())
+        } else {
+            MockOrderRepository::new(self.added_order_id.clone())
+        }
+    }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        self.outbox.clone()
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        self.inbox.clone()
     }
 }
 
 #[tokio::test]
 async fn handle_persists_order_via_repository() {
     let stored_id = Arc::new(Mutex::new(None));
-    let repo = MockOrderRepository::new(stored_id.clone());
+    let outbox = Arc::new(Mutex::new(Vec::new()));
+    let uow = MockUnitOfWork::new(stored_id.clone(), outbox.clone());
     let geo_service = GeoServiceMock;
-    let observed_events = Arc::new(Mutex::new(Vec::new()));
-    let event_bus = RecordingEventBus::new(observed_events.clone());
 
-    let mut handler = CreateOrderHandler::new(repo, geo_service, event_bus);
-    let command = CreateOrderCommand::new(Uuid::new_v4(), "Tverskaya street 1".to_string(), 10)
-        .expect("command should be valid");
+    let mut handler = CreateOrderHandler::new(uow, geo_service, None);
+    let command =
+        CreateOrderCommand::new(Uuid::new_v4(), "Tverskaya street 1".to_string(), 10, None)
+            .expect("command should be valid");
 
     handler
         .execute(command)
@@ -130,22 +270,60 @@ async fn handle_persists_order_via_repository() {
         "order id should be recorded in repository"
     );
 
-    let events = observed_events.lock().expect("event log poisoned");
-    assert_eq!(events.len(), 1, "order creation should emit event");
+    let names = outbox.lock().expect("outbox poisoned");
+    assert_eq!(
+        names.as_slice(),
+        ["created"],
+        "order creation should stage its event in the outbox"
+    );
 }
 
 #[tokio::test]
 async fn handle_propagates_repository_error() {
     let stored_id = Arc::new(Mutex::new(None));
-    let repo = MockOrderRepository::new_failing(stored_id);
+    let outbox = Arc::new(Mutex::new(Vec::new()));
+    let uow = MockUnitOfWork::new_failing(stored_id, outbox.clone());
     let geo_service = GeoServiceMock;
-    let event_bus = RecordingEventBus::new(Arc::new(Mutex::new(Vec::new())));
 
-    let mut handler = CreateOrderHandler::new(repo, geo_service, event_bus);
-    let command = CreateOrderCommand::new(Uuid::new_v4(), "Nevsky prospect 10".to_string(), 5)
-        .expect("command should be valid");
+    let mut handler = CreateOrderHandler::new(uow, geo_service, None);
+    let command =
+        CreateOrderCommand::new(Uuid::new_v4(), "Nevsky prospect 10".to_string(), 5, None)
+            .expect("command should be valid");
 
     let result = handler.execute(command).await;
-    assert!(result.is_err(), "handler must surface repository failures");
+    assert!(
+        matches!(result, Err(CommandError::ExecutionError(_))),
+        "handler must surface repository failures"
+    );
+    assert!(
+        outbox.lock().expect("outbox poisoned").is_empty(),
+        "a failed insert must not stage events"
+    );
+}
+
+#[tokio::test]
+async fn handle_skips_redelivered_event() {
+    let stored_id = Arc::new(Mutex::new(None));
+    let outbox = Arc::new(Mutex::new(Vec::new()));
+    let uow = MockUnitOfWork::new(stored_id, outbox.clone());
+    let event_id = EventId::default();
+    let basket_id = Uuid::new_v4();
+
+    let mut handler = CreateOrderHandler::new(uow, GeoServiceMock, None);
+    for _ in 0..2 {
+        let command =
+            CreateOrderCommand::new(basket_id, "Tverskaya street 1".to_string(), 10, None)
+                .expect("command should be valid")
+                .with_event_id(event_id);
+        handler
+            .execute(command)
+            .await
+            .expect("a redelivered event should be acknowledged");
+    }
+
+    assert_eq!(
+        outbox.lock().expect("outbox poisoned").as_slice(),
+        ["created"],
+        "a redelivered event must not create the order again"
+    );
 }
-use ports::events_producer_port::Events;
@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+
+use crate::errors::command_errors::CommandError;
+
+pub struct EndShiftCommand {
+    courier_id: CourierId,
+}
+
+impl EndShiftCommand {
+    pub fn new(courier_id: Uuid) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+}
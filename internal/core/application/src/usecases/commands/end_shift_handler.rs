@@ -0,0 +1,40 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::end_shift_command::EndShiftCommand;
+
+pub struct EndShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> EndShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<EndShiftCommand, ()> for EndShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: EndShiftCommand) -> Result<(), Self::Error> {
+        let courier_repository = &mut self.courier_repository;
+        retry_on_conflict(|| {
+            let mut courier = courier_repository.get_by_id(command.courier_id())?;
+            courier.end_shift()?;
+            courier_repository.update(courier)?;
+
+            Ok(())
+        })
+    }
+}
@@ -3,6 +3,11 @@ pub mod assign_order_handler;
 #[cfg(test)]
 pub mod assign_order_test;
 
+pub mod assign_orders_batch_command;
+pub mod assign_orders_batch_handler;
+#[cfg(test)]
+pub mod assign_orders_batch_test;
+
 pub mod create_order_command;
 pub mod create_order_handler;
 #[cfg(test)]
@@ -13,5 +18,50 @@ pub mod move_couriers_handler;
 #[cfg(test)]
 pub mod move_couriers_test;
 
+pub mod cancel_order_command;
+pub mod cancel_order_handler;
+#[cfg(test)]
+pub mod cancel_order_test;
+
 pub mod create_courier_command;
 pub mod create_courier_handler;
+
+pub mod start_shift_command;
+pub mod start_shift_handler;
+
+pub mod end_shift_command;
+pub mod end_shift_handler;
+
+pub mod schedule_shift_command;
+pub mod schedule_shift_handler;
+
+pub mod take_break_command;
+pub mod take_break_handler;
+
+pub mod apply_shift_schedules_command;
+pub mod apply_shift_schedules_handler;
+#[cfg(test)]
+pub mod courier_shift_test;
+
+pub mod update_courier_command;
+pub mod update_courier_handler;
+
+pub mod add_storage_place_command;
+pub mod add_storage_place_handler;
+
+pub mod remove_storage_place_command;
+pub mod remove_storage_place_handler;
+
+#[cfg(test)]
+pub mod courier_management_test;
+pub mod deactivate_courier_command;
+pub mod deactivate_courier_handler;
+
+pub mod conflict_retry;
+#[cfg(test)]
+pub mod conflict_retry_test;
+
+pub mod outbox_events;
+
+pub mod replay_outbox_command;
+pub mod replay_outbox_handler;
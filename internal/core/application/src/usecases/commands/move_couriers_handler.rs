@@ -1,73 +1,75 @@
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::route_stop::StopKind;
+use domain::model::order::order_aggregate::Order;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::errors::RepositoryError;
-use ports::events_producer_port::Events;
 use ports::order_repository_port::OrderRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use std::fmt::Debug;
+use std::time::SystemTime;
 use tracing::debug;
 use tracing::instrument;
 use tracing::warn;
 
 use crate::errors::command_errors::CommandError;
 use crate::usecases::CommandHandler;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
 use crate::usecases::commands::move_couriers_command::MoveCouriersCommand;
-use crate::usecases::events::event_bus::EventBus;
+use crate::usecases::commands::outbox_events::stage_events;
 
-pub struct MoveCouriersHandler<UOW, EB>
+pub struct MoveCouriersHandler<UOW>
 where
     UOW: UnitOfWorkPort + Debug,
-    EB: EventBus,
 {
     uow: UOW,
-    event_bus: EB,
+    map: CityMap,
 }
 
-impl<UOW, EB> MoveCouriersHandler<UOW, EB>
+impl<UOW> MoveCouriersHandler<UOW>
 where
     UOW: UnitOfWorkPort + Debug,
-    EB: EventBus,
 {
-    pub fn new(uow: UOW, event_bus: EB) -> Self {
-        Self { uow, event_bus }
+    pub fn new(uow: UOW, map: CityMap) -> Self {
+        Self { uow, map }
     }
 }
 
-impl<UOW, EB> CommandHandler<MoveCouriersCommand, ()> for MoveCouriersHandler<UOW, EB>
+impl<UOW> CommandHandler<MoveCouriersCommand, ()> for MoveCouriersHandler<UOW>
 where
     UOW: UnitOfWorkPort + Debug,
-    EB: EventBus,
 {
     type Error = CommandError;
 
     #[instrument(skip_all)]
     async fn execute(&mut self, _c: MoveCouriersCommand) -> Result<(), Self::Error> {
-        let events = self
-            .uow
-            .transaction(|tx| {
+        let map = &self.map;
+        let uow = &mut self.uow;
+        retry_on_conflict(|| {
+            uow.transaction(|tx| {
                 let mut order_repo = tx.order_repo();
                 let mut assigned_orders = order_repo.get_all_assigned()?;
 
                 if assigned_orders.is_empty() {
                     debug!("no assigned orders found");
-                    return Ok(Vec::<Events>::new());
+                    return Ok(());
                 }
 
                 let mut courier_repo = tx.courier_repo();
                 let mut events = Vec::new();
+                let now = SystemTime::now();
 
-                for order in &mut assigned_orders {
-                    let courier_id = match order.courier_id() {
-                        Some(courier_id) => *courier_id,
-                        None => continue,
-                    };
+                let mut courier_ids: Vec<CourierId> = Vec::new();
+                for courier_id in assigned_orders.iter().filter_map(|o| *o.courier_id()) {
+                    if !courier_ids.contains(&courier_id) {
+                        courier_ids.push(courier_id);
+                    }
+                }
 
-                    tracing::debug!(
-                        "moving courier {} from order {}",
-                        &order.id().0,
-                        &courier_id.0
-                    );
+                for courier_id in courier_ids {
+                    tracing::debug!("moving courier {} along its route", &courier_id.0);
 
-                    let order_events = (|| {
+                    let courier_events = (|| {
                         let mut courier = match courier_repo.get_by_id(courier_id) {
                             Ok(courier) => {
                                 debug!("found courier {}", &courier_id.0);
@@ -75,57 +77,90 @@ where
                             }
                             Err(RepositoryError::NotFound(_)) => {
                                 warn!("courier by id {} not found", &courier_id.0);
-                                return Ok(Vec::<Events>::new());
+                                return Ok(Vec::new());
                             }
                             Err(err) => return Err(err),
                         };
 
-                        courier
-                            .move_to_location(order.location())
+                        let mut orders: Vec<&mut Order> = assigned_orders
+                            .iter_mut()
+                            .filter(|o| o.courier_id() == &Some(courier_id))
+                            .collect();
+
+                        for order in orders.iter_mut() {
+                            courier.plan_stops(order.id(), order.pending_pickup(), order.location(), map);
+                            if order.check_delivery_window(now) {
+                                warn!("order {} missed its delivery window", &order.id().0);
+                            }
+                        }
+
+                        let reached = courier
+                            .move_along_route(map)
                             .map_err(|err| RepositoryError::from(err.to_string()))?;
 
-                        if courier.location() == order.location() {
-                            debug!(
-                                "courier {} is at order {} location, completing the order",
-                                &courier_id.0,
-                                &order.id().0
-                            );
-                            order
-                                .complete()
-                                .map_err(|err| RepositoryError::from(err.to_string()))?;
-                            courier.complete_order(order.id());
+                        let mut courier_events = Vec::new();
+                        for order in orders.iter_mut() {
+                            courier_events.extend(order.pop_domain_events());
+                        }
+
+                        for stop in reached {
+                            let order_id = stop.order_id();
+                            let Some(order) = orders.iter_mut().find(|o| o.id() == order_id) else {
+                                warn!("order {} is not assigned to courier {}", &order_id.0, &courier_id.0);
+                                continue;
+                            };
+
+                            match stop.kind() {
+                                StopKind::Pickup => {
+                                    debug!(
+                                        "courier {} is at order {} pickup location, picking the order up",
+                                        &courier_id.0,
+                                        &order_id.0
+                                    );
+                                    order
+                                        .pick_up()
+                                        .map_err(|err| RepositoryError::from(err.to_string()))?;
+                                }
+                                StopKind::DropOff => {
+                                    debug!(
+                                        "courier {} is at order {} location, completing the order",
+                                        &courier_id.0,
+                                        &order_id.0
+                                    );
+                                    order
+                                        .complete()
+                                        .map_err(|err| RepositoryError::from(err.to_string()))?;
+                                    courier.complete_order(order_id);
+                                }
+                            }
+                            courier_events.extend(order.pop_domain_events());
                         }
 
                         courier_repo.update(courier)?;
-                        order_repo.update(order)?;
+                        for order in orders {
+                            order_repo.update(order)?;
+                        }
 
-                        Ok(order
-                            .pop_domain_events()
-                            .into_iter()
-                            .map(Events::from)
-                            .collect::<Vec<Events>>())
+                        Ok(courier_events)
                     })();
 
-                    match order_events {
-                        Ok(order_events) => events.extend(order_events),
+                    match courier_events {
+                        Ok(courier_events) => events.extend(courier_events),
+                        // Part of the courier may already be saved: retry the whole tick.
+                        Err(err @ RepositoryError::Conflict(_)) => return Err(err),
                         Err(err) => {
                             warn!(
                                 error = ?err,
-                                "failed to process order {}, continuing",
-                                &order.id().0
+                                "failed to move courier {}, continuing",
+                                &courier_id.0
                             );
                         }
                     }
                 }
                 debug!("finished moving courier and adjusting order");
-                Ok(events)
+                stage_events(tx, events)
             })
-            .map_err(CommandError::from)?;
-
-        for event in events {
-            self.event_bus.commit(event).await?;
-        }
-
-        Ok(())
+            .map_err(CommandError::from)
+        })
     }
 }
@@ -1,65 +1,97 @@
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
-use std::sync::Arc;
-use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
 
-use async_trait::async_trait;
+use domain::model::courier::city_map::CityMap;
 use domain::model::courier::courier_aggregate::Courier;
 use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::courier::courier_aggregate::CourierName;
 use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::event::EventId;
 use domain::model::kernel::location::Location;
+use domain::model::kernel::message::Message;
 use domain::model::kernel::volume::Volume;
+use domain::model::order::delivery_window::DeliveryWindow;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
 use domain::model::order::order_aggregate::OrderStatus;
+use domain::model::order::order_events::OrderEvent;
 use ports::courier_repository_port::CourierRepositoryPort;
 use ports::courier_repository_port::GetAllCouriersResponse;
 use ports::errors::RepositoryError;
 use ports::events_producer_port::Events;
+use ports::inbox_repository_port::InboxRepositoryPort;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
 use ports::order_repository_port::OrderRepositoryPort;
+use ports::outbox_repository::OutboxRepositoryPort;
 use ports::unit_of_work_port::UnitOfWorkPort;
 use uuid::Uuid;
 
-use crate::errors::command_errors::CommandError;
 use crate::usecases::CommandHandler;
-use crate::usecases::Handler;
 use crate::usecases::commands::move_couriers_command::MoveCouriersCommand;
 use crate::usecases::commands::move_couriers_handler::MoveCouriersHandler;
-use crate::usecases::events::event_bus::EventBus;
 
-#[derive(Clone)]
-struct RecordingEventBus {
-    events: Arc<Mutex<Vec<Events>>>,
-}
+struct TestInboxRepository;
 
-impl RecordingEventBus {
-    fn new(events: Arc<Mutex<Vec<Events>>>) -> Self {
-        Self { events }
+impl InboxRepositoryPort for TestInboxRepository {
+    fn try_record(&mut self, _event_id: EventId) -> Result<bool, RepositoryError> {
+        Ok(true)
     }
 }
 
-#[async_trait]
-impl EventBus for RecordingEventBus {
-    fn register_order_created(&mut self, _subscriber: impl Handler + 'static) {}
-
-    fn register_order_completed(&mut self, _subscriber: impl Handler + 'static) {}
+struct TestOutboxRepository {
+    events: Rc<RefCell<Vec<Events>>>,
+}
 
-    async fn commit(&self, event: Events) -> Result<(), CommandError> {
-        let mut events = self.events.lock().expect("event log poisoned");
-        events.push(event);
+impl OutboxRepositoryPort for TestOutboxRepository {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let event =
+            Events::try_from(message).map_err(|err| RepositoryError::MapError(err.to_string()))?;
+        self.events.borrow_mut().push(event);
         Ok(())
     }
+
+    fn update(&mut self, _message: &Message) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        _limit: usize,
+        _lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn delete_published_before(
+        &mut self,
+        _cutoff: SystemTime,
+        _limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn replay(&mut self, _from: SystemTime, _to: SystemTime) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone, Debug)]
 struct StoredOrder {
     id: OrderId,
     courier_id: Option<CourierId>,
+    pickup_location: Option<Location>,
     location: Location,
     volume: Volume,
     status: OrderStatus,
+    delivery_window: Option<DeliveryWindow>,
+    delivery_window_missed: bool,
+    created_at: SystemTime,
+    version: u64,
 }
 
 impl Display for StoredOrder {
@@ -73,9 +105,14 @@ impl StoredOrder {
         Self {
             id: order.id(),
             courier_id: *order.courier_id(),
+            pickup_location: order.pickup_location().cloned(),
             location: order.location().clone(),
             volume: Volume::new(order.volume()).expect("volume must be positive"),
             status: copy_status(order.status()),
+            delivery_window: order.delivery_window(),
+            delivery_window_missed: order.is_delivery_window_missed(),
+            created_at: order.created_at(),
+            version: order.version(),
         }
     }
 
@@ -83,9 +120,14 @@ impl StoredOrder {
         Order::restore(
             self.id,
             self.courier_id,
+            self.pickup_location.clone(),
             self.location.clone(),
             self.volume,
             copy_status(&self.status),
+            self.delivery_window,
+            self.delivery_window_missed,
+            self.created_at,
+            self.version,
         )
     }
 
@@ -94,6 +136,9 @@ impl StoredOrder {
         self.location = order.location().clone();
         self.volume = Volume::new(order.volume()).expect("volume must be positive");
         self.status = copy_status(order.status());
+        self.delivery_window = order.delivery_window();
+        self.delivery_window_missed = order.is_delivery_window_missed();
+        self.version = order.version() + 1;
     }
 }
 
@@ -101,7 +146,9 @@ fn copy_status(status: &OrderStatus) -> OrderStatus {
     match status {
         OrderStatus::Created => OrderStatus::Created,
         OrderStatus::Assigned => OrderStatus::Assigned,
+        OrderStatus::PickedUp => OrderStatus::PickedUp,
         OrderStatus::Completed => OrderStatus::Completed,
+        OrderStatus::Cancelled => OrderStatus::Cancelled,
     }
 }
 
@@ -150,12 +197,20 @@ impl OrderRepositoryPort for TestOrderRepository {
             .orders
             .borrow()
             .iter()
-            .filter(|order| matches!(order.status, OrderStatus::Assigned))
+            .filter(|order| matches!(order.status, OrderStatus::Assigned | OrderStatus::PickedUp))
             .map(StoredOrder::to_order)
             .collect())
     }
 
-    fn raw(&mut self, _query: String) -> Result<Vec<Order>, RepositoryError> {
+    fn find(&mut self, _criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn count(&mut self, _filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_history(&mut self, _id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
         unimplemented!()
     }
 }
@@ -196,6 +251,10 @@ impl CourierRepositoryPort for TestCourierRepository {
         Ok(vec![])
     }
 
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError> {
+        Ok(vec![])
+    }
+
     fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError> {
         unimplemented!()
     }
@@ -204,6 +263,7 @@ impl CourierRepositoryPort for TestCourierRepository {
 struct TestUnitOfWork {
     orders: Rc<RefCell<Vec<StoredOrder>>>,
     couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
 }
 
 impl std::fmt::Debug for TestUnitOfWork {
@@ -211,6 +271,7 @@ impl std::fmt::Debug for TestUnitOfWork {
         f.debug_struct("TestUnitOfWork")
             .field("orders", &self.orders)
             .field("couriers", &self.couriers)
+            .field("outbox", &self.outbox)
             .finish()
     }
 }
@@ -219,20 +280,28 @@ impl TestUnitOfWork {
     fn from_state(
         orders: Rc<RefCell<Vec<StoredOrder>>>,
         couriers: Rc<RefCell<Vec<Courier>>>,
+        outbox: Rc<RefCell<Vec<Events>>>,
     ) -> Self {
-        Self { orders, couriers }
+        Self {
+            orders,
+            couriers,
+            outbox,
+        }
     }
 }
 
 struct TestUnitOfWorkTx {
     orders: Rc<RefCell<Vec<StoredOrder>>>,
     couriers: Rc<RefCell<Vec<Courier>>>,
+    outbox: Rc<RefCell<Vec<Events>>>,
 }
 
 impl UnitOfWorkPort for TestUnitOfWork {
     type Uow = TestUnitOfWorkTx;
     type CourierRepo = TestCourierRepository;
     type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
 
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
@@ -241,6 +310,7 @@ impl UnitOfWorkPort for TestUnitOfWork {
         let mut tx = TestUnitOfWorkTx {
             orders: Rc::clone(&self.orders),
             couriers: Rc::clone(&self.couriers),
+            outbox: Rc::clone(&self.outbox),
         };
         f(&mut tx)
     }
@@ -256,12 +326,24 @@ impl UnitOfWorkPort for TestUnitOfWork {
             orders: Rc::clone(&self.orders),
         }
     }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
 }
 
 impl UnitOfWorkPort for TestUnitOfWorkTx {
     type Uow = TestUnitOfWorkTx;
     type CourierRepo = TestCourierRepository;
     type OrderRepo = TestOrderRepository;
+    type OutboxRepo = TestOutboxRepository;
+    type InboxRepo = TestInboxRepository;
 
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
@@ -281,6 +363,16 @@ impl UnitOfWorkPort for TestUnitOfWorkTx {
             orders: Rc::clone(&self.orders),
         }
     }
+
+    fn outbox_repo(&mut self) -> Self::OutboxRepo {
+        TestOutboxRepository {
+            events: Rc::clone(&self.outbox),
+        }
+    }
+
+    fn inbox_repo(&mut self) -> Self::InboxRepo {
+        TestInboxRepository
+    }
 }
 
 fn initial_state() -> (Vec<StoredOrder>, Vec<Courier>) {
@@ -352,12 +444,15 @@ async fn handle_completes_assigned_orders() {
     let (orders, couriers) = initial_state();
     let orders_state = Rc::new(RefCell::new(orders));
     let couriers_state = Rc::new(RefCell::new(couriers));
-    let observed_events = Arc::new(Mutex::new(Vec::new()));
-    let event_bus = RecordingEventBus::new(observed_events.clone());
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
 
     let mut handler = MoveCouriersHandler::new(
-        TestUnitOfWork::from_state(Rc::clone(&orders_state), Rc::clone(&couriers_state)),
-        event_bus,
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::clone(&observed_events),
+        ),
+        CityMap::default(),
     );
     let command = MoveCouriersCommand::new().expect("command should be valid");
 
@@ -379,10 +474,142 @@ async fn handle_completes_assigned_orders() {
     );
 
     assert!(
-        !observed_events
-            .lock()
-            .expect("event log poisoned")
-            .is_empty(),
+        !observed_events.borrow().is_empty(),
         "completing orders should emit events"
     );
 }
+
+#[tokio::test]
+async fn handle_completes_batched_orders_in_route_order() {
+    let mut courier = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(3),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    courier
+        .add_storage_place("trunk".into(), Volume::new(10).unwrap())
+        .unwrap();
+
+    let mut far = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 7).unwrap(),
+        Volume::new(5).unwrap(),
+    )
+    .unwrap();
+    let mut near = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 4).unwrap(),
+        Volume::new(5).unwrap(),
+    )
+    .unwrap();
+    for order in [&mut far, &mut near] {
+        courier
+            .take_order(
+                order.id(),
+                Volume::new(5).unwrap(),
+                None,
+                order.location(),
+                &CityMap::default(),
+            )
+            .unwrap();
+        order.assign(courier.id()).unwrap();
+        order.clear_domain_events();
+    }
+
+    let orders_state = Rc::new(RefCell::new(vec![
+        StoredOrder::from_order(&far),
+        StoredOrder::from_order(&near),
+    ]));
+    let couriers_state = Rc::new(RefCell::new(vec![courier]));
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = MoveCouriersHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::clone(&observed_events),
+        ),
+        CityMap::default(),
+    );
+
+    for _ in 0..2 {
+        handler
+            .execute(MoveCouriersCommand::new().unwrap())
+            .await
+            .expect("handler should finish successfully");
+    }
+
+    let completed: Vec<OrderId> = observed_events
+        .borrow()
+        .iter()
+        .filter_map(|event| match event {
+            Events::Order(OrderEvent::Completed(e)) => Some(e.order_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(completed, vec![near.id(), far.id()]);
+    assert!(!couriers_state.borrow()[0].is_en_route());
+}
+
+#[tokio::test]
+async fn handle_picks_order_up_before_delivering_it() {
+    let courier = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(3),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(4, 1).unwrap(),
+        Volume::new(5).unwrap(),
+    )
+    .unwrap();
+    order
+        .set_pickup_location(Location::new(1, 4).unwrap())
+        .unwrap();
+    order.assign(courier.id()).unwrap();
+    order.clear_domain_events();
+
+    let orders_state = Rc::new(RefCell::new(vec![StoredOrder::from_order(&order)]));
+    let couriers_state = Rc::new(RefCell::new(vec![courier]));
+    let observed_events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut handler = MoveCouriersHandler::new(
+        TestUnitOfWork::from_state(
+            Rc::clone(&orders_state),
+            Rc::clone(&couriers_state),
+            Rc::clone(&observed_events),
+        ),
+        CityMap::default(),
+    );
+
+    handler
+        .execute(MoveCouriersCommand::new().unwrap())
+        .await
+        .expect("handler should finish successfully");
+    assert!(matches!(
+        orders_state.borrow()[0].status,
+        OrderStatus::PickedUp
+    ));
+
+    for _ in 0..2 {
+        handler
+            .execute(MoveCouriersCommand::new().unwrap())
+            .await
+            .expect("handler should finish successfully");
+    }
+
+    assert!(matches!(
+        orders_state.borrow()[0].status,
+        OrderStatus::Completed
+    ));
+    assert!(matches!(
+        observed_events.borrow().as_slice(),
+        [
+            Events::Order(OrderEvent::PickedUp(_)),
+            Events::Order(OrderEvent::Completed(_))
+        ]
+    ));
+}
@@ -0,0 +1,23 @@
+use domain::model::kernel::message::Message;
+use domain::model::order::order_events::OrderEvent;
+use ports::errors::RepositoryError;
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+
+/// Adds `events` to the outbox of the transaction `tx`, so they are only
+/// published once the aggregate changes that raised them commit.
+pub fn stage_events<UOW>(
+    tx: &mut UOW,
+    events: impl IntoIterator<Item = OrderEvent>,
+) -> Result<(), RepositoryError>
+where
+    UOW: UnitOfWorkPort,
+{
+    let mut outbox_repo = tx.outbox_repo();
+    for event in events {
+        let message = Message::try_from(&event).map_err(RepositoryError::MapError)?;
+        outbox_repo.add(&message)?;
+    }
+
+    Ok(())
+}
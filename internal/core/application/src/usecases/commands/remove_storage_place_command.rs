@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+
+use crate::errors::command_errors::CommandError;
+
+pub struct RemoveStoragePlaceCommand {
+    courier_id: CourierId,
+    storage_place_id: Uuid,
+}
+
+impl RemoveStoragePlaceCommand {
+    pub fn new(courier_id: Uuid, storage_place_id: Uuid) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+        if storage_place_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "storage_place_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+            storage_place_id,
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+
+    pub fn storage_place_id(&self) -> Uuid {
+        self.storage_place_id
+    }
+}
@@ -0,0 +1,53 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::remove_storage_place_command::RemoveStoragePlaceCommand;
+
+pub struct RemoveStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> RemoveStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<RemoveStoragePlaceCommand, ()> for RemoveStoragePlaceHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: RemoveStoragePlaceCommand) -> Result<(), Self::Error> {
+        let courier_repository = &mut self.courier_repository;
+        retry_on_conflict(|| {
+            let mut courier = courier_repository.get_by_id(command.courier_id())?;
+            if !courier
+                .storage_places()
+                .iter()
+                .any(|sp| sp.id() == &command.storage_place_id())
+            {
+                return Err(CommandError::NotFound(format!(
+                    "storage place {}",
+                    command.storage_place_id()
+                )));
+            }
+
+            courier
+                .remove_storage_place(command.storage_place_id())
+                .map_err(|err| CommandError::ExecutionError(err.to_string()))?;
+            courier_repository.update(courier)?;
+
+            Ok(())
+        })
+    }
+}
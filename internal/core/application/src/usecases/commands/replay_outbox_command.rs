@@ -0,0 +1,29 @@
+use std::time::SystemTime;
+
+use crate::errors::command_errors::CommandError;
+
+/// Republishes the outbox messages that occurred in `[from, to)`.
+pub struct ReplayOutboxCommand {
+    from: SystemTime,
+    to: SystemTime,
+}
+
+impl ReplayOutboxCommand {
+    pub fn new(from: SystemTime, to: SystemTime) -> Result<Self, CommandError> {
+        if from >= to {
+            return Err(CommandError::ArgumentError(
+                "replay range start must be before its end".to_string(),
+            ));
+        }
+
+        Ok(Self { from, to })
+    }
+
+    pub fn from(&self) -> SystemTime {
+        self.from
+    }
+
+    pub fn to(&self) -> SystemTime {
+        self.to
+    }
+}
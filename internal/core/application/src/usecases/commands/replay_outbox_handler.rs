@@ -0,0 +1,40 @@
+use ports::outbox_repository::OutboxRepositoryPort;
+use ports::unit_of_work_port::UnitOfWorkPort;
+use tracing::info;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::replay_outbox_command::ReplayOutboxCommand;
+
+pub struct ReplayOutboxHandler<UOW>
+where
+    UOW: UnitOfWorkPort,
+{
+    uow: UOW,
+}
+
+impl<UOW> ReplayOutboxHandler<UOW>
+where
+    UOW: UnitOfWorkPort,
+{
+    pub fn new(uow: UOW) -> Self {
+        Self { uow }
+    }
+}
+
+impl<UOW> CommandHandler<ReplayOutboxCommand, u64> for ReplayOutboxHandler<UOW>
+where
+    UOW: UnitOfWorkPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: ReplayOutboxCommand) -> Result<u64, Self::Error> {
+        let replayed = self
+            .uow
+            .transaction(|tx| tx.outbox_repo().replay(command.from(), command.to()))?;
+
+        info!("queued outbox messages for replay: {}", replayed);
+
+        Ok(replayed)
+    }
+}
@@ -0,0 +1,36 @@
+use std::time::SystemTime;
+
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::shift::Shift;
+
+use crate::errors::command_errors::CommandError;
+
+pub struct ScheduleShiftCommand {
+    courier_id: CourierId,
+    shift: Shift,
+}
+
+impl ScheduleShiftCommand {
+    pub fn new(courier_id: Uuid, start: SystemTime, end: SystemTime) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+            shift: Shift::new(start, end)?,
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+
+    pub fn shift(&self) -> Shift {
+        self.shift
+    }
+}
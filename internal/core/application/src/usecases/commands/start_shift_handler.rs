@@ -0,0 +1,42 @@
+use std::time::SystemTime;
+
+use ports::courier_repository_port::CourierRepositoryPort;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::start_shift_command::StartShiftCommand;
+
+pub struct StartShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> StartShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<StartShiftCommand, ()> for StartShiftHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: StartShiftCommand) -> Result<(), Self::Error> {
+        let courier_repository = &mut self.courier_repository;
+        retry_on_conflict(|| {
+            let mut courier = courier_repository.get_by_id(command.courier_id())?;
+            courier.start_shift(SystemTime::now())?;
+            courier_repository.update(courier)?;
+
+            Ok(())
+        })
+    }
+}
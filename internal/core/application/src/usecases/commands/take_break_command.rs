@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+
+use crate::errors::command_errors::CommandError;
+
+/// Sends an available courier on a break, or back from one.
+pub struct TakeBreakCommand {
+    courier_id: CourierId,
+    on_break: bool,
+}
+
+impl TakeBreakCommand {
+    pub fn new(courier_id: Uuid, on_break: bool) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+            on_break,
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+
+    pub fn on_break(&self) -> bool {
+        self.on_break
+    }
+}
@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+
+use crate::errors::command_errors::CommandError;
+
+/// Renames a courier and/or changes their speed; fields left `None` stay as
+/// they are.
+pub struct UpdateCourierCommand {
+    courier_id: CourierId,
+    name: Option<CourierName>,
+    speed: Option<CourierSpeed>,
+}
+
+impl UpdateCourierCommand {
+    pub fn new(
+        courier_id: Uuid,
+        name: Option<CourierName>,
+        speed: Option<CourierSpeed>,
+    ) -> Result<Self, CommandError> {
+        if courier_id.is_nil() {
+            return Err(CommandError::ArgumentError(
+                "courier_id cannot be nil".to_string(),
+            ));
+        }
+        if name.is_none() && speed.is_none() {
+            return Err(CommandError::ArgumentError("nothing to update".to_string()));
+        }
+
+        Ok(Self {
+            courier_id: CourierId(courier_id),
+            name,
+            speed,
+        })
+    }
+
+    pub fn courier_id(&self) -> CourierId {
+        self.courier_id
+    }
+
+    pub fn name(&self) -> Option<&CourierName> {
+        self.name.as_ref()
+    }
+
+    pub fn speed(&self) -> Option<&CourierSpeed> {
+        self.speed.as_ref()
+    }
+}
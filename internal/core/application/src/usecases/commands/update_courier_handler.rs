@@ -0,0 +1,45 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::CommandHandler;
+use crate::usecases::commands::conflict_retry::retry_on_conflict;
+use crate::usecases::commands::update_courier_command::UpdateCourierCommand;
+
+pub struct UpdateCourierHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    courier_repository: CR,
+}
+
+impl<CR> UpdateCourierHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    pub fn new(courier_repository: CR) -> Self {
+        Self { courier_repository }
+    }
+}
+
+impl<CR> CommandHandler<UpdateCourierCommand, ()> for UpdateCourierHandler<CR>
+where
+    CR: CourierRepositoryPort,
+{
+    type Error = CommandError;
+
+    async fn execute(&mut self, command: UpdateCourierCommand) -> Result<(), Self::Error> {
+        let courier_repository = &mut self.courier_repository;
+        retry_on_conflict(|| {
+            let mut courier = courier_repository.get_by_id(command.courier_id())?;
+            if let Some(name) = command.name() {
+                courier.rename(name.clone())?;
+            }
+            if let Some(speed) = command.speed() {
+                courier.change_speed(speed.clone())?;
+            }
+            courier_repository.update(courier)?;
+
+            Ok(())
+        })
+    }
+}
@@ -1,5 +0,0 @@
-pub mod event_bus;
-#[cfg(test)]
-mod event_bus_test;
-pub mod order_completed_event_handler;
-pub mod order_created_event_handler;
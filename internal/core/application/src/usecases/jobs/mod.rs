@@ -1 +1,4 @@
 pub mod outbox_job;
+#[cfg(test)]
+pub mod outbox_job_test;
+pub mod purge_outbox_job;
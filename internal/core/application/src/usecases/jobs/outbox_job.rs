@@ -1,70 +1,247 @@
+use std::collections::HashMap;
+use std::time::Duration;
 use std::time::SystemTime;
 
 use domain::model::kernel::message::Message;
+use futures::future::join_all;
+use ports::errors::ProducerError;
 use ports::events_producer_port::Events;
 use ports::events_producer_port::EventsProducerPort;
 use ports::outbox_repository::OutboxRepositoryPort;
 use tracing::debug;
+use tracing::error;
 use tracing::warn;
+use uuid::Uuid;
 
 use crate::errors::command_errors::CommandError;
 use crate::usecases::JobHandler;
 
+/// Exponential backoff between delivery attempts, capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboxRetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl OutboxRetryPolicy {
+    pub const DEFAULT: Self = Self {
+        max_attempts: 5,
+        base_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(60),
+    };
+
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay: max_delay.max(base_delay),
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before the next try once `attempts` deliveries have failed.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for OutboxRetryPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+/// Long enough for a batch to be acknowledged; a crashed relay's claim expires after it.
+pub const DEFAULT_LEASE: Duration = Duration::from_secs(30);
+
 pub struct OutboxJob<OR, EP>
 where
-    OR: OutboxRepositoryPort + Send + Sync,
+    OR: OutboxRepositoryPort + Send,
     EP: EventsProducerPort + Send + Sync,
 {
     outbox_repo: OR,
     event_producer: EP,
+    retry_policy: OutboxRetryPolicy,
+    batch_size: usize,
+    lease: Duration,
 }
 
 impl<OR, EP> OutboxJob<OR, EP>
 where
-    OR: OutboxRepositoryPort + Send + Sync,
+    OR: OutboxRepositoryPort + Send,
     EP: EventsProducerPort + Send + Sync,
 {
     pub fn new(outbox_repo: OR, event_producer: EP) -> Self {
         Self {
             outbox_repo,
             event_producer,
+            retry_policy: OutboxRetryPolicy::DEFAULT,
+            batch_size: DEFAULT_BATCH_SIZE,
+            lease: DEFAULT_LEASE,
         }
     }
-}
 
-#[async_trait::async_trait]
-impl<OR, EP> JobHandler for OutboxJob<OR, EP>
-where
-    OR: OutboxRepositoryPort + Send + Sync,
-    EP: EventsProducerPort + Send + Sync,
-{
-    async fn execute(&mut self) -> Result<(), CommandError> {
-        debug!("looking for unprocessed events");
+    pub fn with_retry_policy(mut self, retry_policy: OutboxRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
 
-        let messages = self
+    /// Publishes one batch and returns how many messages it claimed. Messages
+    /// of one order are sent one after another in `occured_at` order, and a
+    /// failed one holds back the rest of its order until its retry. Different
+    /// orders are sent side by side, so the producer can pipeline them instead
+    /// of waiting for each acknowledgement in turn.
+    pub async fn relay_batch(&mut self) -> Result<usize, CommandError> {
+        let mut messages = self
             .outbox_repo
-            .get_not_published_messages()
+            .claim_not_published_messages(self.batch_size, self.lease)
             .map_err(CommandError::from)?;
 
         if messages.is_empty() {
             debug!("no unprocessed messages");
-            return Ok(());
+            return Ok(0);
         }
 
         debug!("unprocessed messages: {}", messages.len());
 
-        for mut message in messages {
-            debug!("publishing message: {:?}", message);
-            let event = Events::try_from(&message).ok();
-            if let Some(e) = event {
-                self.event_producer.publish(e);
-                message.processed_at = Some(SystemTime::now());
-                if let Err(e) = self.outbox_repo.update(&message) {
-                    warn!("error while updating outbox repo: {}", e);
+        let event_producer = &self.event_producer;
+        let retry_policy = self.retry_policy;
+        join_all(
+            by_order(&mut messages)
+                .into_iter()
+                .map(|messages| deliver_in_order(event_producer, retry_policy, messages)),
+        )
+        .await;
+
+        for message in &messages {
+            if let Err(e) = self.outbox_repo.update(message) {
+                warn!("error while updating outbox repo: {}", e);
+            }
+        }
+
+        Ok(messages.len())
+    }
+}
+
+/// Splits a batch into the messages of each order, keeping their order.
+/// Messages that can't be decoded get a group of their own.
+fn by_order(messages: &mut [Message]) -> Vec<Vec<&mut Message>> {
+    let mut groups: Vec<Vec<&mut Message>> = Vec::new();
+    let mut group_of_order: HashMap<Uuid, usize> = HashMap::new();
+
+    for message in messages {
+        let order_id = match Events::try_from(&*message) {
+            Ok(Events::Order(event)) => Some(event.order_id().0),
+            Err(_) => None,
+        };
+        match order_id.and_then(|order_id| group_of_order.get(&order_id)) {
+            Some(&group) => groups[group].push(message),
+            None => {
+                if let Some(order_id) = order_id {
+                    group_of_order.insert(order_id, groups.len());
                 }
+                groups.push(vec![message]);
             }
         }
+    }
+
+    groups
+}
+
+async fn deliver_in_order<EP: EventsProducerPort>(
+    event_producer: &EP,
+    retry_policy: OutboxRetryPolicy,
+    messages: Vec<&mut Message>,
+) {
+    let mut held_until = None;
+    for message in messages {
+        if let Some(retry_at) = held_until {
+            debug!(id = %message.id, "holding message back behind a failed one of its order");
+            message.defer(retry_at);
+            continue;
+        }
+
+        deliver(event_producer, retry_policy, message).await;
+        if message.processed_at.is_none() && !message.is_dead_lettered() {
+            held_until = message.next_attempt_at;
+        }
+    }
+}
 
-        Ok(())
+async fn deliver<EP: EventsProducerPort>(
+    event_producer: &EP,
+    retry_policy: OutboxRetryPolicy,
+    message: &mut Message,
+) {
+    debug!("publishing message: {:?}", message);
+    let event = match Events::try_from(&*message) {
+        Ok(event) => event,
+        Err(err) => {
+            error!(id = %message.id, "dead-lettering undecodable message: {}", err);
+            message.dead_letter(err.to_string());
+            return;
+        }
+    };
+
+    match event_producer.publish(event, message.occured_at).await {
+        Ok(()) => message.mark_published(),
+        Err(err @ ProducerError::EncodeError(_)) => {
+            error!(id = %message.id, "dead-lettering unencodable message: {}", err);
+            message.dead_letter(err.to_string());
+        }
+        Err(err @ ProducerError::DeliveryError(_)) => {
+            if message.attempts + 1 >= retry_policy.max_attempts() {
+                error!(
+                    id = %message.id,
+                    attempts = message.attempts + 1,
+                    "dead-lettering message after failed deliveries: {}",
+                    err
+                );
+                message.dead_letter(err.to_string());
+            } else {
+                let retry_at = SystemTime::now() + retry_policy.backoff(message.attempts + 1);
+                warn!(
+                    id = %message.id,
+                    attempts = message.attempts + 1,
+                    "message delivery failed, will retry: {}",
+                    err
+                );
+                message.record_failure(err.to_string(), retry_at);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<OR, EP> JobHandler for OutboxJob<OR, EP>
+where
+    OR: OutboxRepositoryPort + Send,
+    EP: EventsProducerPort + Send + Sync,
+{
+    async fn execute(&mut self) -> Result<(), CommandError> {
+        debug!("looking for unprocessed events");
+        self.relay_batch().await.map(|_| ())
     }
 }
@@ -0,0 +1,415 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use domain::model::kernel::message::Message;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_events::OrderEvent;
+use ports::errors::ProducerError;
+use ports::errors::RepositoryError;
+use ports::events_producer_port::Events;
+use ports::events_producer_port::EventsProducerPort;
+use ports::outbox_repository::OutboxRepositoryPort;
+use uuid::Uuid;
+
+use crate::usecases::JobHandler;
+use crate::usecases::jobs::outbox_job::OutboxJob;
+use crate::usecases::jobs::outbox_job::OutboxRetryPolicy;
+use crate::usecases::jobs::purge_outbox_job::PurgeOutboxJob;
+
+#[derive(Clone, Default)]
+struct InMemoryOutbox {
+    messages: Arc<Mutex<Vec<Message>>>,
+}
+
+impl InMemoryOutbox {
+    fn with(messages: Vec<Message>) -> Self {
+        Self {
+            messages: Arc::new(Mutex::new(messages)),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Message> {
+        self.messages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(copy_message)
+            .collect()
+    }
+}
+
+impl OutboxRepositoryPort for InMemoryOutbox {
+    fn add(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        self.messages.lock().unwrap().push(copy_message(message));
+        Ok(())
+    }
+
+    fn update(&mut self, message: &Message) -> Result<(), RepositoryError> {
+        let mut messages = self.messages.lock().unwrap();
+        let stored = messages
+            .iter_mut()
+            .find(|m| m.id == message.id)
+            .ok_or_else(|| RepositoryError::NotFound(message.id.to_string()))?;
+        *stored = copy_message(message);
+        Ok(())
+    }
+
+    fn claim_not_published_messages(
+        &mut self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError> {
+        let now = SystemTime::now();
+        let mut messages = self.messages.lock().unwrap();
+        messages.sort_by_key(|m| m.occured_at);
+        Ok(messages
+            .iter_mut()
+            .filter(|m| m.processed_at.is_none() && !m.is_dead_lettered())
+            .filter(|m| m.next_attempt_at.is_none_or(|at| at <= now))
+            .take(limit)
+            .map(|m| {
+                m.next_attempt_at = Some(now + lease);
+                copy_message(m)
+            })
+            .collect())
+    }
+
+    fn delete_published_before(
+        &mut self,
+        cutoff: SystemTime,
+        limit: usize,
+    ) -> Result<u64, RepositoryError> {
+        let mut messages = self.messages.lock().unwrap();
+        let mut deleted = 0;
+        messages.retain(|m| {
+            let expired = deleted < limit && m.processed_at.is_some_and(|at| at < cutoff);
+            if expired {
+                deleted += 1;
+            }
+            !expired
+        });
+        Ok(deleted as u64)
+    }
+
+    fn replay(&mut self, from: SystemTime, to: SystemTime) -> Result<u64, RepositoryError> {
+        let mut replayed = 0;
+        for m in self.messages.lock().unwrap().iter_mut() {
+            let in_range = m.occured_at >= from && m.occured_at < to;
+            if in_range && (m.processed_at.is_some() || m.is_dead_lettered()) {
+                m.processed_at = None;
+                m.dead_lettered_at = None;
+                m.next_attempt_at = None;
+                m.last_error = None;
+                m.attempts = 0;
+                replayed += 1;
+            }
+        }
+        Ok(replayed)
+    }
+}
+
+fn copy_message(m: &Message) -> Message {
+    Message {
+        id: m.id,
+        name: m.name.clone(),
+        payload: m.payload.clone(),
+        occured_at: m.occured_at,
+        processed_at: m.processed_at,
+        attempts: m.attempts,
+        last_error: m.last_error.clone(),
+        next_attempt_at: m.next_attempt_at,
+        dead_lettered_at: m.dead_lettered_at,
+    }
+}
+
+/// Rejects the first `failures` deliveries, then acknowledges everything.
+#[derive(Clone, Default)]
+struct FlakyProducer {
+    failures: u32,
+    calls: Arc<AtomicU32>,
+    published: Arc<Mutex<Vec<Uuid>>>,
+    occurred: Arc<Mutex<Vec<SystemTime>>>,
+}
+
+#[async_trait::async_trait]
+impl EventsProducerPort for FlakyProducer {
+    async fn publish(&self, e: Events, occurred_at: SystemTime) -> Result<(), ProducerError> {
+        let Events::Order(event) = e;
+        self.published.lock().unwrap().push(event.event_id().0);
+        self.occurred.lock().unwrap().push(occurred_at);
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        if call < self.failures {
+            return Err(ProducerError::DeliveryError("broker unavailable".into()));
+        }
+        Ok(())
+    }
+}
+
+fn created_message() -> Message {
+    let event = OrderEvent::created(OrderId::new(Uuid::new_v4()));
+    Message::try_from(&event).unwrap()
+}
+
+fn immediate_retries(max_attempts: u32) -> OutboxRetryPolicy {
+    OutboxRetryPolicy::new(max_attempts, Duration::ZERO, Duration::ZERO)
+}
+
+#[tokio::test]
+async fn marks_message_processed_after_acknowledged_delivery() {
+    let outbox = InMemoryOutbox::with(vec![created_message()]);
+    let mut job = OutboxJob::new(outbox.clone(), FlakyProducer::default());
+
+    job.execute().await.unwrap();
+
+    let stored = outbox.snapshot();
+    assert!(stored[0].processed_at.is_some());
+    assert_eq!(stored[0].attempts, 0);
+}
+
+#[tokio::test]
+async fn failed_delivery_is_retried_later_instead_of_marked_processed() {
+    let outbox = InMemoryOutbox::with(vec![created_message()]);
+    let producer = FlakyProducer {
+        failures: 1,
+        ..Default::default()
+    };
+    let mut job = OutboxJob::new(outbox.clone(), producer.clone()).with_retry_policy(
+        OutboxRetryPolicy::new(5, Duration::from_secs(30), Duration::from_secs(60)),
+    );
+
+    job.execute().await.unwrap();
+
+    let stored = outbox.snapshot();
+    assert!(stored[0].processed_at.is_none());
+    assert_eq!(stored[0].attempts, 1);
+    assert_eq!(
+        stored[0].last_error.as_deref(),
+        Some("Producer delivery error: broker unavailable")
+    );
+    assert!(stored[0].next_attempt_at.unwrap() > SystemTime::now());
+
+    job.execute().await.unwrap();
+    assert_eq!(
+        producer.calls.load(Ordering::SeqCst),
+        1,
+        "message must wait for its backoff"
+    );
+}
+
+#[tokio::test]
+async fn message_is_published_once_the_broker_recovers() {
+    let outbox = InMemoryOutbox::with(vec![created_message()]);
+    let producer = FlakyProducer {
+        failures: 2,
+        ..Default::default()
+    };
+    let mut job = OutboxJob::new(outbox.clone(), producer).with_retry_policy(immediate_retries(5));
+
+    for _ in 0..3 {
+        job.execute().await.unwrap();
+    }
+
+    let stored = outbox.snapshot();
+    assert!(stored[0].processed_at.is_some());
+    assert_eq!(stored[0].attempts, 2);
+}
+
+#[tokio::test]
+async fn message_is_dead_lettered_after_max_attempts() {
+    let outbox = InMemoryOutbox::with(vec![created_message()]);
+    let producer = FlakyProducer {
+        failures: u32::MAX,
+        ..Default::default()
+    };
+    let mut job =
+        OutboxJob::new(outbox.clone(), producer.clone()).with_retry_policy(immediate_retries(3));
+
+    for _ in 0..5 {
+        job.execute().await.unwrap();
+    }
+
+    let stored = outbox.snapshot();
+    assert!(stored[0].is_dead_lettered());
+    assert!(stored[0].processed_at.is_none());
+    assert_eq!(stored[0].attempts, 3);
+    assert_eq!(producer.calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn undecodable_message_is_dead_lettered_without_publishing() {
+    let outbox = InMemoryOutbox::with(vec![Message::new("unknown".into(), "{}".into())]);
+    let producer = FlakyProducer::default();
+    let mut job = OutboxJob::new(outbox.clone(), producer.clone());
+
+    job.execute().await.unwrap();
+
+    let stored = outbox.snapshot();
+    assert!(stored[0].is_dead_lettered());
+    assert_eq!(producer.calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn relays_oldest_messages_first_in_batches() {
+    let start = SystemTime::now();
+    let messages: Vec<Message> = (0..5u64)
+        .map(|offset| {
+            let mut message = created_message();
+            message.occured_at = start + Duration::from_secs(offset);
+            message
+        })
+        .collect();
+    let oldest_first: Vec<Uuid> = messages.iter().map(|m| m.id).collect();
+    let outbox = InMemoryOutbox::with(messages.into_iter().rev().collect());
+    let producer = FlakyProducer::default();
+    let mut job = OutboxJob::new(outbox.clone(), producer.clone()).with_batch_size(2);
+
+    assert_eq!(job.relay_batch().await.unwrap(), 2);
+    assert_eq!(job.relay_batch().await.unwrap(), 2);
+    assert_eq!(job.relay_batch().await.unwrap(), 1);
+    assert_eq!(job.relay_batch().await.unwrap(), 0);
+
+    assert_eq!(*producer.published.lock().unwrap(), oldest_first);
+    assert!(outbox.snapshot().iter().all(|m| m.processed_at.is_some()));
+}
+
+#[tokio::test]
+async fn failed_message_holds_back_later_messages_of_its_order() {
+    let start = SystemTime::now();
+    let order_id = OrderId::new(Uuid::new_v4());
+    let mut created = Message::try_from(&OrderEvent::created(order_id)).unwrap();
+    created.occured_at = start;
+    let mut cancelled = Message::try_from(&OrderEvent::cancelled(order_id, None)).unwrap();
+    cancelled.occured_at = start + Duration::from_secs(1);
+    let mut other = created_message();
+    other.occured_at = start + Duration::from_secs(2);
+    let (created_id, cancelled_id, other_id) = (created.id, cancelled.id, other.id);
+    let outbox = InMemoryOutbox::with(vec![created, cancelled, other]);
+    let producer = FlakyProducer {
+        failures: 1,
+        ..Default::default()
+    };
+    let mut job = OutboxJob::new(outbox.clone(), producer.clone()).with_retry_policy(
+        OutboxRetryPolicy::new(5, Duration::from_secs(30), Duration::from_secs(60)),
+    );
+
+    assert_eq!(job.relay_batch().await.unwrap(), 3);
+
+    let stored = outbox.snapshot();
+    let find = |id: Uuid| stored.iter().find(|m| m.id == id).unwrap();
+    assert_eq!(find(created_id).attempts, 1);
+    assert!(find(cancelled_id).processed_at.is_none());
+    assert_eq!(find(cancelled_id).attempts, 0);
+    assert_eq!(
+        find(cancelled_id).next_attempt_at,
+        find(created_id).next_attempt_at
+    );
+    assert!(find(other_id).processed_at.is_some());
+    assert_eq!(
+        *producer.published.lock().unwrap(),
+        vec![created_id, other_id]
+    );
+}
+
+#[tokio::test]
+async fn messages_of_an_order_follow_a_retried_one() {
+    let start = SystemTime::now();
+    let order_id = OrderId::new(Uuid::new_v4());
+    let mut created = Message::try_from(&OrderEvent::created(order_id)).unwrap();
+    created.occured_at = start;
+    let mut cancelled = Message::try_from(&OrderEvent::cancelled(order_id, None)).unwrap();
+    cancelled.occured_at = start + Duration::from_secs(1);
+    let in_order = vec![created.id, created.id, cancelled.id];
+    let outbox = InMemoryOutbox::with(vec![created, cancelled]);
+    let producer = FlakyProducer {
+        failures: 1,
+        ..Default::default()
+    };
+    let mut job =
+        OutboxJob::new(outbox.clone(), producer.clone()).with_retry_policy(immediate_retries(5));
+
+    job.execute().await.unwrap();
+    job.execute().await.unwrap();
+
+    assert_eq!(*producer.published.lock().unwrap(), in_order);
+    assert!(outbox.snapshot().iter().all(|m| m.processed_at.is_some()));
+}
+
+#[tokio::test]
+async fn claimed_messages_are_hidden_from_other_relays() {
+    let outbox = InMemoryOutbox::with(vec![created_message(), created_message()]);
+    let mut claimer = outbox.clone();
+
+    let claimed = claimer
+        .claim_not_published_messages(1, Duration::from_secs(30))
+        .unwrap();
+    let mut job = OutboxJob::new(outbox.clone(), FlakyProducer::default());
+
+    assert_eq!(job.relay_batch().await.unwrap(), 1);
+    let stored = outbox.snapshot();
+    let leased = stored.iter().find(|m| m.id == claimed[0].id).unwrap();
+    assert!(leased.processed_at.is_none());
+}
+
+#[tokio::test]
+async fn replayed_message_is_republished_with_its_original_event_id_and_time() {
+    let mut message = created_message();
+    message.occured_at -= Duration::from_secs(30);
+    let event_id = message.id;
+    let occurred_at = message.occured_at;
+    let outbox = InMemoryOutbox::with(vec![message]);
+    let producer = FlakyProducer::default();
+    let mut job = OutboxJob::new(outbox.clone(), producer.clone());
+    job.execute().await.unwrap();
+
+    let now = SystemTime::now();
+    let replayed = outbox
+        .clone()
+        .replay(now - Duration::from_secs(60), now + Duration::from_secs(60))
+        .unwrap();
+    job.execute().await.unwrap();
+
+    assert_eq!(replayed, 1);
+    assert_eq!(
+        *producer.published.lock().unwrap(),
+        vec![event_id, event_id]
+    );
+    assert_eq!(
+        *producer.occurred.lock().unwrap(),
+        vec![occurred_at, occurred_at]
+    );
+}
+
+#[tokio::test]
+async fn purge_removes_only_published_messages_past_retention() {
+    let now = SystemTime::now();
+    let mut expired = created_message();
+    expired.processed_at = Some(now - Duration::from_secs(7200));
+    let mut recent = created_message();
+    recent.processed_at = Some(now - Duration::from_secs(60));
+    let pending = created_message();
+    let mut dead = created_message();
+    dead.dead_letter("broker unavailable".into());
+    let kept = [recent.id, pending.id, dead.id];
+    let outbox = InMemoryOutbox::with(vec![expired, recent, pending, dead]);
+
+    let mut job = PurgeOutboxJob::new(outbox.clone(), Duration::from_secs(3600));
+    job.execute().await.unwrap();
+
+    let remaining: Vec<Uuid> = outbox.snapshot().iter().map(|m| m.id).collect();
+    assert_eq!(remaining, kept);
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let policy = OutboxRetryPolicy::new(10, Duration::from_secs(1), Duration::from_secs(10));
+
+    assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    assert_eq!(policy.backoff(2), Duration::from_secs(2));
+    assert_eq!(policy.backoff(4), Duration::from_secs(8));
+    assert_eq!(policy.backoff(5), Duration::from_secs(10));
+    assert_eq!(policy.backoff(40), Duration::from_secs(10));
+}
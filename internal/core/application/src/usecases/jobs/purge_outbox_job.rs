@@ -0,0 +1,61 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use ports::outbox_repository::OutboxRepositoryPort;
+use tracing::debug;
+
+use crate::errors::command_errors::CommandError;
+use crate::usecases::JobHandler;
+
+/// Rows deleted per statement, so a large backlog never holds long locks.
+const PURGE_BATCH_SIZE: usize = 1_000;
+
+/// Deletes published outbox messages once they are older than the retention period.
+pub struct PurgeOutboxJob<OR>
+where
+    OR: OutboxRepositoryPort + Send,
+{
+    outbox_repo: OR,
+    retention: Duration,
+}
+
+impl<OR> PurgeOutboxJob<OR>
+where
+    OR: OutboxRepositoryPort + Send,
+{
+    pub fn new(outbox_repo: OR, retention: Duration) -> Self {
+        Self {
+            outbox_repo,
+            retention,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<OR> JobHandler for PurgeOutboxJob<OR>
+where
+    OR: OutboxRepositoryPort + Send,
+{
+    async fn execute(&mut self) -> Result<(), CommandError> {
+        let Some(cutoff) = SystemTime::now().checked_sub(self.retention) else {
+            return Ok(());
+        };
+
+        let mut purged = 0;
+        loop {
+            let deleted = self
+                .outbox_repo
+                .delete_published_before(cutoff, PURGE_BATCH_SIZE)
+                .map_err(CommandError::from)?;
+            purged += deleted;
+
+            if deleted < PURGE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+
+        debug!("purged outbox messages: {}", purged);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,8 @@
 use async_trait::async_trait;
-use domain::model::order::order_events::OrderEvent;
 
 use crate::errors::command_errors::CommandError;
 
 pub mod commands;
-pub mod events;
 pub mod jobs;
 pub mod queries;
 
@@ -16,11 +14,6 @@ pub trait CommandHandler<C, R> {
 }
 
 #[async_trait]
-pub trait Handler: Send + Sync {
-    async fn execute(&mut self, event: OrderEvent) -> Result<(), CommandError>;
-}
-
-#[async_trait]
-pub trait JobHandler: Send + Sync {
+pub trait JobHandler: Send {
     async fn execute(&mut self) -> Result<(), CommandError>;
 }
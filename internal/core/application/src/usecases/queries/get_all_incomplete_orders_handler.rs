@@ -1,4 +1,7 @@
 use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderStatus;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
 use ports::order_repository_port::OrderRepositoryPort;
 
 use crate::errors::query_errors::QueryError;
@@ -31,8 +34,14 @@ where
         &mut self,
         _command: GetAllIncompleteOrders,
     ) -> Result<Vec<Order>, Self::Error> {
+        let incomplete = OrderFilter::with_statuses([
+            OrderStatus::Created,
+            OrderStatus::Assigned,
+            OrderStatus::PickedUp,
+        ]);
+
         self.order_repository
-            .raw("SELECT * FROM orders WHERE status != 'completed';".into())
+            .find(&OrderCriteria::matching(incomplete))
             .map_err(Self::Error::from)
     }
 }
@@ -0,0 +1,50 @@
+use ports::courier_repository_port::CourierRepositoryPort;
+use ports::order_repository_port::OrderRepositoryPort;
+
+use crate::errors::query_errors::QueryError;
+use crate::usecases::CommandHandler;
+use crate::usecases::queries::get_courier_query::GetCourier;
+use crate::usecases::queries::get_courier_query::GetCourierResponse;
+
+/// Loads a courier together with the orders in their storage places.
+pub struct GetCourierHandler<CR, OR>
+where
+    CR: CourierRepositoryPort,
+    OR: OrderRepositoryPort,
+{
+    courier_repository: CR,
+    order_repository: OR,
+}
+
+impl<CR, OR> GetCourierHandler<CR, OR>
+where
+    CR: CourierRepositoryPort,
+    OR: OrderRepositoryPort,
+{
+    pub fn new(courier_repository: CR, order_repository: OR) -> Self {
+        Self {
+            courier_repository,
+            order_repository,
+        }
+    }
+}
+
+impl<CR, OR> CommandHandler<GetCourier, GetCourierResponse> for GetCourierHandler<CR, OR>
+where
+    CR: CourierRepositoryPort,
+    OR: OrderRepositoryPort,
+{
+    type Error = QueryError;
+
+    async fn execute(&mut self, query: GetCourier) -> Result<GetCourierResponse, Self::Error> {
+        let courier = self.courier_repository.get_by_id(query.courier_id)?;
+        let orders = courier
+            .storage_places()
+            .iter()
+            .filter_map(|sp| *sp.order_id())
+            .map(|order_id| self.order_repository.get_by_id(order_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(GetCourierResponse { courier, orders })
+    }
+}
@@ -0,0 +1,12 @@
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::order::order_aggregate::Order;
+
+pub struct GetCourier {
+    pub courier_id: CourierId,
+}
+
+pub struct GetCourierResponse {
+    pub courier: Courier,
+    pub orders: Vec<Order>,
+}
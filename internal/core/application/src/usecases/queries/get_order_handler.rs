@@ -0,0 +1,37 @@
+use ports::order_repository_port::OrderRepositoryPort;
+
+use crate::errors::query_errors::QueryError;
+use crate::usecases::CommandHandler;
+use crate::usecases::queries::get_order_query::GetOrder;
+use crate::usecases::queries::get_order_query::GetOrderResponse;
+
+/// Loads an order together with its status history.
+pub struct GetOrderHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    order_repository: OR,
+}
+
+impl<OR> GetOrderHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    pub fn new(order_repository: OR) -> Self {
+        Self { order_repository }
+    }
+}
+
+impl<OR> CommandHandler<GetOrder, GetOrderResponse> for GetOrderHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    type Error = QueryError;
+
+    async fn execute(&mut self, query: GetOrder) -> Result<GetOrderResponse, Self::Error> {
+        let order = self.order_repository.get_by_id(query.order_id)?;
+        let history = self.order_repository.get_history(query.order_id)?;
+
+        Ok(GetOrderResponse { order, history })
+    }
+}
@@ -0,0 +1,12 @@
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use ports::order_repository_port::OrderHistoryEntry;
+
+pub struct GetOrder {
+    pub order_id: OrderId,
+}
+
+pub struct GetOrderResponse {
+    pub order: Order,
+    pub history: Vec<OrderHistoryEntry>,
+}
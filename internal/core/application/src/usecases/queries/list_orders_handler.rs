@@ -0,0 +1,44 @@
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderRepositoryPort;
+use ports::order_repository_port::OrderSort;
+
+use crate::errors::query_errors::QueryError;
+use crate::usecases::CommandHandler;
+use crate::usecases::queries::list_orders_query::ListOrders;
+use crate::usecases::queries::list_orders_query::OrdersPage;
+
+pub struct ListOrdersHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    order_repository: OR,
+}
+
+impl<OR> ListOrdersHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    pub fn new(order_repository: OR) -> Self {
+        Self { order_repository }
+    }
+}
+
+impl<OR> CommandHandler<ListOrders, OrdersPage> for ListOrdersHandler<OR>
+where
+    OR: OrderRepositoryPort,
+{
+    type Error = QueryError;
+
+    async fn execute(&mut self, query: ListOrders) -> Result<OrdersPage, Self::Error> {
+        let criteria = OrderCriteria {
+            filter: query.filter().clone(),
+            sort: OrderSort::NewestFirst,
+            limit: Some(query.limit()),
+            offset: query.offset(),
+        };
+        let orders = self.order_repository.find(&criteria)?;
+        let total = self.order_repository.count(&criteria.filter)?;
+
+        Ok(OrdersPage { orders, total })
+    }
+}
@@ -0,0 +1,59 @@
+use domain::model::order::order_aggregate::Order;
+use ports::order_repository_port::OrderFilter;
+
+use crate::errors::query_errors::QueryError;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+pub struct OrdersPage {
+    pub orders: Vec<Order>,
+    pub total: u64,
+}
+
+pub struct ListOrders {
+    filter: OrderFilter,
+    limit: u32,
+    offset: u32,
+}
+
+impl ListOrders {
+    pub fn new(
+        filter: OrderFilter,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Self, QueryError> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(QueryError::ArgumentError(format!(
+                "limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+        if let (Some(from), Some(to)) = (filter.created_from, filter.created_before)
+            && to <= from
+        {
+            return Err(QueryError::ArgumentError(
+                "created_before must be after created_from".into(),
+            ));
+        }
+
+        Ok(Self {
+            filter,
+            limit,
+            offset: offset.unwrap_or(0),
+        })
+    }
+
+    pub fn filter(&self) -> &OrderFilter {
+        &self.filter
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
@@ -2,3 +2,11 @@ pub mod get_all_couriers_handler;
 pub mod get_all_couriers_query;
 pub mod get_all_incomplete_orders_handler;
 pub mod get_all_incomplete_orders_query;
+pub mod get_courier_handler;
+pub mod get_courier_query;
+pub mod get_order_handler;
+pub mod get_order_query;
+pub mod list_orders_handler;
+pub mod list_orders_query;
+#[cfg(test)]
+pub mod order_queries_test;
@@ -0,0 +1,220 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use domain::model::courier::courier_aggregate::CourierId;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
+use ports::errors::RepositoryError;
+use ports::order_repository_port::OrderCriteria;
+use ports::order_repository_port::OrderFilter;
+use ports::order_repository_port::OrderHistoryEntry;
+use ports::order_repository_port::OrderRepositoryPort;
+use uuid::Uuid;
+
+use crate::errors::query_errors::QueryError;
+use crate::usecases::CommandHandler;
+use crate::usecases::queries::get_all_incomplete_orders_handler::GetAllIncompleteOrdersHandler;
+use crate::usecases::queries::get_all_incomplete_orders_query::GetAllIncompleteOrders;
+use crate::usecases::queries::get_order_handler::GetOrderHandler;
+use crate::usecases::queries::get_order_query::GetOrder;
+use crate::usecases::queries::list_orders_handler::ListOrdersHandler;
+use crate::usecases::queries::list_orders_query::ListOrders;
+
+struct TestOrderRepository {
+    orders: Vec<Order>,
+    history: Vec<(OrderId, OrderHistoryEntry)>,
+}
+
+impl OrderRepositoryPort for TestOrderRepository {
+    fn add(&mut self, order: &Order) -> Result<(), RepositoryError> {
+        self.orders.push(order.clone());
+        Ok(())
+    }
+
+    fn update(&mut self, _order: &Order) -> Result<(), RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_by_id(&mut self, id: OrderId) -> Result<Order, RepositoryError> {
+        self.orders
+            .iter()
+            .find(|order| order.id() == id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound("order not found".into()))
+    }
+
+    fn get_any_new(&mut self) -> Result<Order, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError> {
+        unimplemented!()
+    }
+
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError> {
+        Ok(criteria.apply(self.orders.clone()))
+    }
+
+    fn count(&mut self, filter: &OrderFilter) -> Result<u64, RepositoryError> {
+        Ok(self.orders.iter().filter(|o| filter.matches(o)).count() as u64)
+    }
+
+    fn get_history(&mut self, id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError> {
+        Ok(self
+            .history
+            .iter()
+            .filter(|(order_id, _)| *order_id == id)
+            .map(|(_, entry)| entry.clone())
+            .collect())
+    }
+}
+
+fn order_created_at(created_at: SystemTime, status: OrderStatus) -> Order {
+    Order::restore(
+        OrderId::new(Uuid::new_v4()),
+        None,
+        None,
+        Location::new(1, 1).unwrap(),
+        Volume::new(1).unwrap(),
+        status,
+        None,
+        false,
+        created_at,
+        0,
+    )
+}
+
+#[tokio::test]
+async fn get_order_returns_history() {
+    let now = SystemTime::now();
+    let order = order_created_at(now, OrderStatus::Assigned);
+    let courier_id = CourierId(Uuid::new_v4());
+    let history = vec![
+        (
+            order.id(),
+            OrderHistoryEntry {
+                status: OrderStatus::Created,
+                courier_id: None,
+                occurred_at: now,
+            },
+        ),
+        (
+            order.id(),
+            OrderHistoryEntry {
+                status: OrderStatus::Assigned,
+                courier_id: Some(courier_id),
+                occurred_at: now + Duration::from_secs(5),
+            },
+        ),
+    ];
+    let repo = TestOrderRepository {
+        orders: vec![order.clone()],
+        history,
+    };
+
+    let response = GetOrderHandler::new(repo)
+        .execute(GetOrder {
+            order_id: order.id(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(response.order.id(), order.id());
+    assert_eq!(response.history.len(), 2);
+    assert_eq!(response.history[1].courier_id, Some(courier_id));
+}
+
+#[tokio::test]
+async fn get_order_reports_missing_order() {
+    let repo = TestOrderRepository {
+        orders: vec![],
+        history: vec![],
+    };
+
+    let result = GetOrderHandler::new(repo)
+        .execute(GetOrder {
+            order_id: OrderId::new(Uuid::new_v4()),
+        })
+        .await;
+
+    assert!(matches!(result, Err(QueryError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn list_orders_filters_and_pages_newest_first() {
+    let now = SystemTime::now();
+    let orders: Vec<Order> = (0..5)
+        .map(|i| order_created_at(now + Duration::from_secs(i), OrderStatus::Created))
+        .chain([order_created_at(now, OrderStatus::Completed)])
+        .collect();
+    let newest = orders[4].id();
+    let repo = TestOrderRepository {
+        orders,
+        history: vec![],
+    };
+
+    let filter = OrderFilter {
+        statuses: vec![OrderStatus::Created],
+        ..OrderFilter::default()
+    };
+    let page = ListOrdersHandler::new(repo)
+        .execute(ListOrders::new(filter, Some(2), None).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 5);
+    assert_eq!(page.orders.len(), 2);
+    assert_eq!(page.orders[0].id(), newest);
+}
+
+#[tokio::test]
+async fn get_all_incomplete_orders_skips_finished_orders() {
+    let now = SystemTime::now();
+    let repo = TestOrderRepository {
+        orders: vec![
+            order_created_at(now + Duration::from_secs(2), OrderStatus::PickedUp),
+            order_created_at(now, OrderStatus::Created),
+            order_created_at(now, OrderStatus::Completed),
+            order_created_at(now, OrderStatus::Cancelled),
+        ],
+        history: vec![],
+    };
+
+    let orders = GetAllIncompleteOrdersHandler::new(repo)
+        .execute(GetAllIncompleteOrders)
+        .await
+        .unwrap();
+
+    let statuses: Vec<&OrderStatus> = orders.iter().map(Order::status).collect();
+    assert_eq!(statuses, [&OrderStatus::Created, &OrderStatus::PickedUp]);
+}
+
+#[test]
+fn list_orders_rejects_invalid_arguments() {
+    assert!(matches!(
+        ListOrders::new(OrderFilter::default(), Some(0), None),
+        Err(QueryError::ArgumentError(_))
+    ));
+    assert!(matches!(
+        ListOrders::new(OrderFilter::default(), Some(101), None),
+        Err(QueryError::ArgumentError(_))
+    ));
+
+    let now = SystemTime::now();
+    let filter = OrderFilter {
+        created_from: Some(now),
+        created_before: Some(now - Duration::from_secs(1)),
+        ..OrderFilter::default()
+    };
+    assert!(matches!(
+        ListOrders::new(filter, None, None),
+        Err(QueryError::ArgumentError(_))
+    ));
+
+    let query = ListOrders::new(OrderFilter::default(), None, None).unwrap();
+    assert_eq!(query.limit(), 20);
+    assert_eq!(query.offset(), 0);
+}
@@ -0,0 +1,137 @@
+//! Greedy dispatching against optimal batch assignment on a burst of orders.
+//!
+//! Run with `cargo bench -p domain --bench assignment`.
+
+use std::time::Instant;
+use std::time::SystemTime;
+
+use domain::model::courier::city_map::CityMap;
+use domain::model::courier::courier_aggregate::Courier;
+use domain::model::courier::courier_aggregate::CourierName;
+use domain::model::courier::courier_aggregate::CourierSpeed;
+use domain::model::kernel::location::Location;
+use domain::model::kernel::volume::Volume;
+use domain::model::order::order_aggregate::Order;
+use domain::model::order::order_aggregate::OrderId;
+use domain::model::services::optimal_assignment::OptimalAssignmentService;
+use domain::model::services::order_dispatcher::OrderDispatcher;
+use domain::model::services::order_dispatcher::OrderDispatcherService;
+use uuid::Uuid;
+
+const ROUNDS: u32 = 5;
+
+/// Small deterministic generator so every run sees the same burst.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+
+    fn location(&mut self) -> Location {
+        Location::new(self.next(10) as u16 + 1, self.next(10) as u16 + 1).unwrap()
+    }
+}
+
+fn burst(orders: usize, couriers: usize) -> (Vec<Order>, Vec<Courier>) {
+    let mut rng = Lcg(42);
+    let orders = (0..orders)
+        .map(|_| {
+            Order::new(
+                OrderId::new(Uuid::new_v4()),
+                rng.location(),
+                Volume::new(rng.next(20) as u16 + 1).unwrap(),
+            )
+            .unwrap()
+        })
+        .collect();
+    let couriers = (0..couriers)
+        .map(|i| {
+            let mut courier = Courier::new(
+                CourierName(format!("courier-{}", i)),
+                CourierSpeed(rng.next(3) as u8 + 1),
+                rng.location(),
+            )
+            .unwrap();
+            courier
+                .add_storage_place("trunk".into(), Volume::new(20).unwrap())
+                .unwrap();
+            courier
+        })
+        .collect();
+    (orders, couriers)
+}
+
+fn total_travel(orders: &[Order], couriers: &[Courier], pairs: &[(usize, usize)]) -> u64 {
+    pairs
+        .iter()
+        .map(|&(o, c)| couriers[c].location().get_distance(orders[o].location()) as u64)
+        .sum()
+}
+
+fn greedy(orders: &mut [Order], couriers: &mut [Courier]) -> Vec<(usize, usize)> {
+    let now = SystemTime::now();
+    let mut pairs = Vec::new();
+    for order_idx in 0..orders.len() {
+        let Ok((_, courier)) = OrderDispatcherService.dispatch(
+            &mut orders[order_idx..=order_idx],
+            couriers,
+            now,
+            &CityMap::default(),
+        ) else {
+            continue;
+        };
+        let courier_id = *courier.id();
+        let courier_idx = couriers.iter().position(|c| c.id() == &courier_id).unwrap();
+        pairs.push((order_idx, courier_idx));
+    }
+    pairs
+}
+
+fn main() {
+    for (order_count, courier_count) in [(50, 30), (200, 120), (500, 300)] {
+        let (orders, couriers) = burst(order_count, courier_count);
+
+        let mut greedy_time = 0u128;
+        let mut optimal_time = 0u128;
+        let mut greedy_result = (0, 0);
+        let mut optimal_result = (0, 0);
+
+        for _ in 0..ROUNDS {
+            let (mut o, mut c) = (orders.clone(), couriers.clone());
+            let started = Instant::now();
+            let pairs = greedy(&mut o, &mut c);
+            greedy_time += started.elapsed().as_micros();
+            greedy_result = (pairs.len(), total_travel(&orders, &couriers, &pairs));
+
+            let (mut o, mut c) = (orders.clone(), couriers.clone());
+            let started = Instant::now();
+            let pairs = OptimalAssignmentService::assign(
+                &mut o,
+                &mut c,
+                SystemTime::now(),
+                &CityMap::default(),
+            )
+            .expect("assignment should succeed");
+            optimal_time += started.elapsed().as_micros();
+            optimal_result = (pairs.len(), total_travel(&orders, &couriers, &pairs));
+        }
+
+        println!(
+            "{} orders / {} couriers: greedy assigned {} travelling {} in {} us, \
+             optimal assigned {} travelling {} in {} us",
+            order_count,
+            courier_count,
+            greedy_result.0,
+            greedy_result.1,
+            greedy_time / ROUNDS as u128,
+            optimal_result.0,
+            optimal_result.1,
+            optimal_time / ROUNDS as u128,
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::model::courier::movement::AxisStepping;
+use crate::model::courier::movement::MovementModel;
+use crate::model::kernel::coordinate_system::CoordinateSystem;
+
+/// The city couriers drive through. Built once on startup and handed to
+/// whatever plans routes or moves couriers.
+#[derive(Clone)]
+pub struct CityMap {
+    system: CoordinateSystem,
+    movement: Arc<dyn MovementModel>,
+}
+
+impl CityMap {
+    pub fn new(system: CoordinateSystem, movement: Arc<dyn MovementModel>) -> Self {
+        Self { system, movement }
+    }
+
+    pub fn system(&self) -> &CoordinateSystem {
+        &self.system
+    }
+
+    /// How couriers get from one grid cell to another.
+    pub fn movement(&self) -> &dyn MovementModel {
+        self.movement.as_ref()
+    }
+}
+
+/// The default grid, crossed by axis stepping.
+impl Default for CityMap {
+    fn default() -> Self {
+        Self::new(CoordinateSystem::default(), Arc::new(AxisStepping))
+    }
+}
+
+impl fmt::Debug for CityMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CityMap")
+            .field("system", &self.system)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,9 +1,19 @@
+use std::time::SystemTime;
+
 use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
 use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::city_map::CityMap;
+use crate::model::courier::route_stop::RouteStop;
+use crate::model::courier::route_stop::StopKind;
+use crate::model::courier::shift::CourierStatus;
+use crate::model::courier::shift::Shift;
 use crate::model::courier::storage_place::StoragePlace;
+use crate::model::kernel::coordinate_system::GeoArea;
+use crate::model::kernel::great_circle;
+use crate::model::kernel::location::Coordinates;
 use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
 use crate::model::order::order_aggregate::OrderId;
@@ -24,6 +34,11 @@ pub struct Courier {
     speed: CourierSpeed,
     location: Location,
     storage_places: Vec<StoragePlace>,
+    route: Vec<RouteStop>,
+    status: CourierStatus,
+    shift: Option<Shift>,
+    ending_shift: bool,
+    version: u64,
 }
 
 impl PartialEq for Courier {
@@ -48,15 +63,26 @@ impl Courier {
             name,
             speed,
             storage_places,
+            route: Vec::new(),
+            status: CourierStatus::Available,
+            shift: None,
+            ending_shift: false,
+            version: 0,
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn restore(
         id: CourierId,
         name: CourierName,
         speed: CourierSpeed,
         location: Location,
         storage_places: Vec<StoragePlace>,
+        route: Vec<RouteStop>,
+        status: CourierStatus,
+        shift: Option<Shift>,
+        ending_shift: bool,
+        version: u64,
     ) -> Self {
         Self {
             id,
@@ -64,6 +90,11 @@ impl Courier {
             speed,
             location,
             storage_places,
+            route,
+            status,
+            shift,
+            ending_shift,
+            version,
         }
     }
 
@@ -82,14 +113,232 @@ impl Courier {
     pub fn storage_places(&self) -> &Vec<StoragePlace> {
         &self.storage_places
     }
+    pub fn route(&self) -> &Vec<RouteStop> {
+        &self.route
+    }
+    pub fn next_stop(&self) -> Option<&RouteStop> {
+        self.route.first()
+    }
+    pub fn is_en_route(&self) -> bool {
+        !self.route.is_empty()
+    }
+    pub fn status(&self) -> CourierStatus {
+        self.status
+    }
+    pub fn shift(&self) -> Option<&Shift> {
+        self.shift.as_ref()
+    }
+    pub fn is_ending_shift(&self) -> bool {
+        self.ending_shift
+    }
+
+    /// Version the courier was loaded at; updates only apply on top of it.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn accepts_orders(&self) -> bool {
+        matches!(self.status, CourierStatus::Available | CourierStatus::Busy) && !self.ending_shift
+    }
+
+    /// Whether new orders may be handed to the courier: on duty, not on a
+    /// break, not winding down and within the scheduled shift, if any.
+    pub fn is_dispatchable(&self, now: SystemTime) -> bool {
+        self.accepts_orders() && self.shift.is_none_or(|shift| shift.contains(now))
+    }
+
+    pub fn schedule_shift(&mut self, shift: Shift) -> Result<(), DomainModelError> {
+        self.ensure_active()?;
+        self.shift = Some(shift);
+        Ok(())
+    }
+
+    pub fn start_shift(&mut self, now: SystemTime) -> Result<(), DomainModelError> {
+        self.ensure_active()?;
+        if self.status != CourierStatus::OffDuty {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} is already on duty",
+                self.id.0
+            )));
+        }
+        if let Some(shift) = self.shift {
+            if !shift.has_started(now) {
+                return Err(DomainModelError::UnmetRequirement(format!(
+                    "shift of courier {} has not started yet",
+                    self.id.0
+                )));
+            }
+            if shift.has_ended(now) {
+                self.shift = None;
+            }
+        }
+
+        self.status = CourierStatus::Available;
+        self.ending_shift = false;
+        Ok(())
+    }
+
+    /// Goes off duty right away when the bag is empty. A courier with orders
+    /// on board stops taking new ones and goes off duty after the last one is
+    /// delivered or released.
+    pub fn end_shift(&mut self) -> Result<(), DomainModelError> {
+        self.ensure_active()?;
+        if self.status == CourierStatus::OffDuty {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} is already off duty",
+                self.id.0
+            )));
+        }
+
+        self.ending_shift = true;
+        self.settle_status();
+        Ok(())
+    }
+
+    pub fn start_break(&mut self) -> Result<(), DomainModelError> {
+        if self.status != CourierStatus::Available || self.ending_shift {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} can only take a break when available",
+                self.id.0
+            )));
+        }
+
+        self.status = CourierStatus::OnBreak;
+        Ok(())
+    }
+
+    pub fn end_break(&mut self) -> Result<(), DomainModelError> {
+        if self.status != CourierStatus::OnBreak {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} is not on a break",
+                self.id.0
+            )));
+        }
+
+        self.status = CourierStatus::Available;
+        Ok(())
+    }
+
+    /// Starts or ends the scheduled shift when its time has come. Returns
+    /// whether anything changed.
+    pub fn apply_shift_schedule(&mut self, now: SystemTime) -> Result<bool, DomainModelError> {
+        let Some(shift) = self.shift else {
+            return Ok(false);
+        };
+
+        match self.status {
+            CourierStatus::OffDuty if shift.contains(now) => self.start_shift(now).map(|_| true),
+            CourierStatus::OffDuty if shift.has_ended(now) => {
+                self.shift = None;
+                Ok(true)
+            }
+            CourierStatus::OffDuty => Ok(false),
+            _ if shift.has_ended(now) && !self.ending_shift => self.end_shift().map(|_| true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Takes the courier out of service for good. Refused while orders are
+    /// on board.
+    pub fn deactivate(&mut self) -> Result<(), DomainModelError> {
+        self.ensure_active()?;
+        if self.occupied_places() > 0 {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} still carries orders",
+                self.id.0
+            )));
+        }
+
+        self.status = CourierStatus::Deactivated;
+        self.shift = None;
+        self.ending_shift = false;
+        Ok(())
+    }
+
+    fn ensure_active(&self) -> Result<(), DomainModelError> {
+        if self.status == CourierStatus::Deactivated {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} is deactivated",
+                self.id.0
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn settle_status(&mut self) {
+        if self.occupied_places() > 0 {
+            if self.status == CourierStatus::Available {
+                self.status = CourierStatus::Busy;
+            }
+            return;
+        }
+
+        if self.ending_shift {
+            self.status = CourierStatus::OffDuty;
+            self.shift = None;
+            self.ending_shift = false;
+        } else if self.status == CourierStatus::Busy {
+            self.status = CourierStatus::Available;
+        }
+    }
+
+    pub fn rename(&mut self, name: CourierName) -> Result<(), DomainModelError> {
+        if name.0.trim().is_empty() {
+            return Err(DomainModelError::ArgumentCannotBeEmpty("name".to_string()));
+        }
+
+        self.name = name;
+        Ok(())
+    }
+
+    pub fn change_speed(&mut self, speed: CourierSpeed) -> Result<(), DomainModelError> {
+        if speed.0 == 0 {
+            return Err(DomainModelError::ArgumentCannotBeZero("speed".to_string()));
+        }
+
+        self.speed = speed;
+        Ok(())
+    }
 
     pub fn add_storage_place(
         &mut self,
         name: String,
         volume: Volume,
-    ) -> Result<(), DomainModelError> {
+    ) -> Result<Uuid, DomainModelError> {
         let new_storage_place = StoragePlace::new(name, volume, None)?;
+        let id = *new_storage_place.id();
         self.storage_places.push(new_storage_place);
+        Ok(id)
+    }
+
+    /// Removes an empty storage place. The last one can't go, a courier
+    /// always has somewhere to put an order.
+    pub fn remove_storage_place(&mut self, storage_place_id: Uuid) -> Result<(), DomainModelError> {
+        let index = self
+            .storage_places
+            .iter()
+            .position(|sp| sp.id() == &storage_place_id)
+            .ok_or_else(|| {
+                DomainModelError::UnmetRequirement(format!(
+                    "storage place {} not found",
+                    storage_place_id
+                ))
+            })?;
+
+        if self.storage_places[index].order_id().is_some() {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "storage place {} is occupied",
+                storage_place_id
+            )));
+        }
+        if self.storage_places.len() == 1 {
+            return Err(DomainModelError::UnmetRequirement(
+                "courier must keep at least one storage place".to_string(),
+            ));
+        }
+
+        self.storage_places.remove(index);
         Ok(())
     }
 
@@ -102,15 +351,41 @@ impl Courier {
             .map(|(index, _)| index)
     }
 
+    /// Volume left over in the storage place the order would go to.
+    pub fn spare_volume_for(&self, order_volume: &Volume) -> Option<u16> {
+        self.can_take_order(order_volume)
+            .map(|index| self.storage_places[index].total_volume() - order_volume.value())
+    }
+
+    pub fn occupied_places(&self) -> usize {
+        self.storage_places
+            .iter()
+            .filter(|sp| sp.order_id().is_some())
+            .count()
+    }
+
     pub fn take_order(
         &mut self,
         order_id: OrderId,
         order_volume: Volume,
+        pickup: Option<&Location>,
+        destination: &Location,
+        map: &CityMap,
     ) -> Result<(), DomainModelError> {
+        if !self.accepts_orders() {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "courier {} does not accept orders while {}",
+                self.id.0,
+                String::from(self.status)
+            )));
+        }
+
         if let Some(index) = self.can_take_order(&order_volume)
             && let Some(storage) = self.storage_places.get_mut(index)
         {
             storage.place_order(order_id, order_volume);
+            self.plan_stops(order_id, pickup, destination, map);
+            self.settle_status();
             return Ok(());
         }
 
@@ -121,6 +396,10 @@ impl Courier {
     }
 
     pub fn complete_order(&mut self, order_id: OrderId) {
+        self.release_order(order_id);
+    }
+
+    pub fn release_order(&mut self, order_id: OrderId) {
         if let Some(storage) = self
             .storage_places
             .iter_mut()
@@ -128,60 +407,218 @@ impl Courier {
         {
             storage.remove_order();
         }
+        self.route.retain(|stop| stop.order_id() != order_id);
+        self.settle_status();
     }
 
-    pub fn get_traverse_length(&self, destination: &Location) -> u8 {
-        let distance = self.location.get_distance(destination) as f64;
-        let speed = self.speed.0 as f64;
+    /// Puts the stops of an already loaded order on the route, unless they
+    /// are there. A pickup stop always comes before its drop-off.
+    pub fn plan_stops(
+        &mut self,
+        order_id: OrderId,
+        pickup: Option<&Location>,
+        destination: &Location,
+        map: &CityMap,
+    ) {
+        if self.route.iter().any(|stop| stop.order_id() == order_id) {
+            return;
+        }
+
+        self.route = self.planned_route(order_id, pickup, destination, map);
+    }
 
-        (distance / speed).ceil() as u8
+    /// Extra distance the route grows by when the order's stops are inserted
+    /// at their cheapest positions; the plain distance for a courier with no
+    /// route.
+    pub fn route_detour(
+        &self,
+        pickup: Option<&Location>,
+        destination: &Location,
+        map: &CityMap,
+    ) -> u32 {
+        let planned = self.planned_route(OrderId::new(Uuid::nil()), pickup, destination, map);
+        self.route_length(map, &planned)
+            .saturating_sub(self.route_length(map, &self.route))
     }
 
-    pub fn move_to_location(&mut self, location: &Location) -> Result<&Location, DomainModelError> {
-        let speed = self.speed.0;
-        let x_distance = self.location.x().abs_diff(location.x());
-        let y_distance = self.location.y().abs_diff(location.y());
+    /// Extra ticks the route takes once the order's stops are on it.
+    pub fn get_detour_length(
+        &self,
+        pickup: Option<&Location>,
+        destination: &Location,
+        map: &CityMap,
+    ) -> u32 {
+        let planned = self.planned_route(OrderId::new(Uuid::nil()), pickup, destination, map);
+        self.route_ticks(map, &planned)
+            .saturating_sub(self.route_ticks(map, &self.route))
+    }
 
-        if x_distance >= y_distance {
-            if self.location.x() < location.x() {
-                let preliminary_action = self.location.x() + speed;
-                let x = if preliminary_action > location.x() {
-                    location.x()
-                } else {
-                    preliminary_action
+    fn planned_route(
+        &self,
+        order_id: OrderId,
+        pickup: Option<&Location>,
+        destination: &Location,
+        map: &CityMap,
+    ) -> Vec<RouteStop> {
+        let mut route = self.route.clone();
+        let mut earliest = 0;
+        if let Some(pickup) = pickup {
+            let position = self.cheapest_position(&route, pickup, 0, map);
+            route.insert(
+                position,
+                RouteStop::new(order_id, StopKind::Pickup, pickup.clone()),
+            );
+            earliest = position + 1;
+        }
+
+        let position = self.cheapest_position(&route, destination, earliest, map);
+        route.insert(
+            position,
+            RouteStop::new(order_id, StopKind::DropOff, destination.clone()),
+        );
+        route
+    }
+
+    fn cheapest_position(
+        &self,
+        route: &[RouteStop],
+        location: &Location,
+        earliest: usize,
+        map: &CityMap,
+    ) -> usize {
+        let distance = |a: &Location, b: &Location| leg_distance(map, a, b);
+
+        (earliest..=route.len())
+            .min_by_key(|&position| {
+                let previous = match position {
+                    0 => &self.location,
+                    _ => route[position - 1].location(),
                 };
-                self.location = Location::new(x, self.location.y())?
+                match route.get(position) {
+                    Some(next) => distance(previous, location)
+                        .saturating_add(distance(location, next.location()))
+                        .saturating_sub(distance(previous, next.location())),
+                    None => distance(previous, location),
+                }
+            })
+            .unwrap_or(earliest)
+    }
+
+    fn route_length(&self, map: &CityMap, route: &[RouteStop]) -> u32 {
+        route
+            .iter()
+            .fold((&self.location, 0u32), |(previous, length), stop| {
+                (
+                    stop.location(),
+                    length.saturating_add(leg_distance(map, previous, stop.location())),
+                )
+            })
+            .1
+    }
+
+    /// Ticks to drive the route stop by stop; speed left over when a stop is
+    /// reached is not carried on to the next leg.
+    fn route_ticks(&self, map: &CityMap, route: &[RouteStop]) -> u32 {
+        route
+            .iter()
+            .fold((&self.location, 0u32), |(previous, ticks), stop| {
+                (
+                    stop.location(),
+                    ticks.saturating_add(self.leg_ticks(map, previous, stop.location())),
+                )
+            })
+            .1
+    }
+
+    fn leg_ticks(&self, map: &CityMap, from: &Location, to: &Location) -> u32 {
+        match (from.coordinates(), to.coordinates()) {
+            (Coordinates::Grid { .. }, Coordinates::Grid { .. }) => {
+                map.movement().ticks(from, to, self.speed.0)
             }
-            if self.location.x() > location.x() {
-                let preliminary_action = self.location.x().saturating_sub(speed);
-                let x = if preliminary_action < location.x() {
-                    location.x()
-                } else {
-                    preliminary_action
-                };
-                self.location = Location::new(x, self.location.y())?
+            (Coordinates::Geographic { .. }, Coordinates::Geographic { .. }) => {
+                let settings = map.system().geo_settings();
+                let distance = from.get_distance_by(to, settings.formula());
+                (f64::from(distance) / settings.step_meters(self.speed.0)).ceil() as u32
             }
-        } else {
-            if self.location.y() < location.y() {
-                let preliminary_action = self.location.y() + speed;
-                let y = if preliminary_action > location.y() {
-                    location.y()
-                } else {
-                    preliminary_action
-                };
-                self.location = Location::new(self.location.x(), y)?
+            _ => u32::MAX,
+        }
+    }
+
+    /// Steps towards the next stop and returns the stops that were reached,
+    /// in route order.
+    pub fn move_along_route(&mut self, map: &CityMap) -> Result<Vec<RouteStop>, DomainModelError> {
+        let Some(next) = self.next_stop().map(|stop| stop.location().clone()) else {
+            return Ok(Vec::new());
+        };
+
+        self.move_to_location(&next, map)?;
+
+        let reached = self
+            .route
+            .iter()
+            .take_while(|stop| stop.location() == &self.location)
+            .count();
+
+        Ok(self.route.drain(..reached).collect())
+    }
+
+    pub fn get_traverse_length(&self, destination: &Location, map: &CityMap) -> u32 {
+        self.leg_ticks(map, &self.location, destination)
+    }
+
+    /// Moves one tick towards the location: with the map's movement model on
+    /// the grid, along the great circle between geographic locations.
+    pub fn move_to_location(
+        &mut self,
+        location: &Location,
+        map: &CityMap,
+    ) -> Result<&Location, DomainModelError> {
+        match (self.location.coordinates(), location.coordinates()) {
+            (Coordinates::Grid { .. }, Coordinates::Grid { .. }) => {
+                self.location = map
+                    .movement()
+                    .step(&self.location, location, self.speed.0)?;
+                Ok(&self.location)
             }
-            if self.location.y() > location.y() {
-                let preliminary_action = self.location.y().saturating_sub(speed);
-                let y = if preliminary_action < location.y() {
-                    location.y()
+            (
+                Coordinates::Geographic {
+                    latitude: from_lat,
+                    longitude: from_lon,
+                },
+                Coordinates::Geographic {
+                    latitude: to_lat,
+                    longitude: to_lon,
+                },
+            ) => {
+                let settings = map.system().geo_settings();
+                let step = settings.step_meters(self.speed.0);
+                let distance = settings
+                    .formula()
+                    .distance((from_lat, from_lon), (to_lat, to_lon));
+
+                self.location = if distance <= step {
+                    location.clone()
                 } else {
-                    preliminary_action
+                    let (lat, lon) = great_circle::interpolate(
+                        (from_lat, from_lon),
+                        (to_lat, to_lon),
+                        step / distance,
+                    );
+                    Location::new_geographic_within(lat, lon, &GeoArea::WORLD)?
                 };
-                self.location = Location::new(self.location.x(), y)?
+
+                Ok(&self.location)
             }
+            _ => Err(DomainModelError::UnmetRequirement(
+                "cannot move between grid and geographic locations".to_string(),
+            )),
         }
+    }
+}
 
-        Ok(&self.location)
+fn leg_distance(map: &CityMap, from: &Location, to: &Location) -> u32 {
+    match (from.coordinates(), to.coordinates()) {
+        (Coordinates::Grid { .. }, Coordinates::Grid { .. }) => map.movement().distance(from, to),
+        _ => from.get_distance_by(to, map.system().geo_settings().formula()),
     }
 }
@@ -1,13 +1,23 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
 use uuid::Uuid;
 
+use crate::model::courier::city_map::CityMap;
 use crate::model::courier::courier_aggregate::Courier;
 use crate::model::courier::courier_aggregate::CourierName;
 use crate::model::courier::courier_aggregate::CourierSpeed;
+use crate::model::courier::movement::ManhattanStepping;
+use crate::model::courier::route_stop::StopKind;
+use crate::model::courier::shift::CourierStatus;
+use crate::model::courier::shift::Shift;
+use crate::model::kernel::coordinate_system::CoordinateSystem;
 use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
 use crate::model::order::order_aggregate::OrderId;
 
-fn make_courier_at(x: u8, y: u8) -> Courier {
+fn make_courier_at(x: u16, y: u16) -> Courier {
     Courier::new(
         CourierName("Bob".to_string()),
         CourierSpeed(5),
@@ -49,7 +59,13 @@ fn takes_and_completes_order() {
 
     assert!(courier.can_take_order(&volume).is_some());
 
-    let _ = courier.take_order(order_id, volume);
+    let _ = courier.take_order(
+        order_id,
+        volume,
+        None,
+        &Location::new(5, 5).unwrap(),
+        &CityMap::default(),
+    );
     assert!(
         courier
             .storage_places()
@@ -70,7 +86,7 @@ fn takes_and_completes_order() {
 fn get_traverse_length_ceil_correctly() {
     let courier = make_courier_at(1, 1);
     let destination = Location::new(9, 1).unwrap();
-    let t = courier.get_traverse_length(&destination);
+    let t = courier.get_traverse_length(&destination, &CityMap::default());
     assert_eq!(t, 2);
 }
 
@@ -79,15 +95,21 @@ fn move_to_location_moves_by_speed() {
     let mut courier = make_courier_at(1, 1);
     let destination = Location::new(8, 1).unwrap();
 
-    let loc = courier.move_to_location(&destination).unwrap();
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap();
     assert_eq!(loc.x(), 6);
     assert_eq!(loc.y(), 1);
 
-    let loc = courier.move_to_location(&destination).unwrap();
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap();
     assert_eq!(loc.x(), 8);
     assert_eq!(loc.y(), 1);
 
-    let loc = courier.move_to_location(&destination).unwrap();
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap();
     assert_eq!(loc.x(), 8);
     assert_eq!(loc.y(), 1);
 }
@@ -96,7 +118,373 @@ fn move_to_location_moves_by_speed() {
 fn move_to_location_works_on_y_axis() {
     let mut courier = make_courier_at(1, 1);
     let destination = Location::new(1, 8).unwrap();
-    let loc = courier.move_to_location(&destination).unwrap();
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap();
     assert_eq!(loc.x(), 1);
     assert_eq!(loc.y(), 6);
 }
+
+#[test]
+fn move_to_location_uses_movement_model_of_map() {
+    let map = CityMap::new(CoordinateSystem::default(), Arc::new(ManhattanStepping));
+    let mut courier = make_courier_at(1, 1);
+    let destination = Location::new(4, 4).unwrap();
+
+    let loc = courier.move_to_location(&destination, &map).unwrap();
+    assert_eq!(loc.x(), 4);
+    assert_eq!(loc.y(), 3);
+}
+
+#[test]
+fn move_to_location_follows_great_circle_between_geographic_locations() {
+    // 36 km/h covers 600 m in a default 60 second tick.
+    let start = Location::new_geographic(55.7558, 37.6173).unwrap();
+    let destination = Location::new_geographic(55.7558, 37.6333).unwrap();
+    let mut courier = Courier::new(
+        CourierName("Bob".to_string()),
+        CourierSpeed(36),
+        start.clone(),
+    )
+    .unwrap();
+    let distance = start.get_distance(&destination);
+    assert_eq!(
+        courier.get_traverse_length(&destination, &CityMap::default()),
+        2
+    );
+
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap()
+        .clone();
+    assert_eq!(start.get_distance(&loc), 600);
+    assert_eq!(loc.get_distance(&destination), distance - 600);
+
+    let loc = courier
+        .move_to_location(&destination, &CityMap::default())
+        .unwrap();
+    assert_eq!(loc, &destination);
+}
+
+#[test]
+fn cannot_move_between_grid_and_geographic_locations() {
+    let mut courier = make_courier_at(1, 1);
+    let destination = Location::new_geographic(55.7558, 37.6173).unwrap();
+
+    assert!(
+        courier
+            .move_to_location(&destination, &CityMap::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn releases_order() {
+    let mut courier = make_courier_at(1, 1);
+    let order_id = OrderId::new(Uuid::new_v4());
+    courier
+        .take_order(
+            order_id,
+            Volume::new(5).unwrap(),
+            None,
+            &Location::new(5, 5).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+
+    courier.release_order(order_id);
+
+    assert!(courier.can_take_order(&Volume::new(5).unwrap()).is_some());
+    assert!(!courier.is_en_route());
+}
+
+fn courier_with_bags(bags: usize) -> Courier {
+    let mut courier = make_courier_at(1, 1);
+    for i in 1..bags {
+        courier
+            .add_storage_place(format!("bag_{}", i), Volume::new(10).unwrap())
+            .unwrap();
+    }
+    courier
+}
+
+#[test]
+fn inserts_stop_at_cheapest_route_position() {
+    let mut courier = courier_with_bags(2);
+    let far = OrderId::new(Uuid::new_v4());
+    let near = OrderId::new(Uuid::new_v4());
+
+    courier
+        .take_order(
+            far,
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        courier.route_detour(None, &Location::new(1, 4).unwrap(), &CityMap::default()),
+        0
+    );
+    courier
+        .take_order(
+            near,
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 4).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+
+    let route: Vec<OrderId> = courier.route().iter().map(|s| s.order_id()).collect();
+    assert_eq!(route, vec![near, far]);
+}
+
+fn reached_orders(courier: &mut Courier) -> Vec<OrderId> {
+    courier
+        .move_along_route(&CityMap::default())
+        .unwrap()
+        .iter()
+        .map(|stop| stop.order_id())
+        .collect()
+}
+
+#[test]
+fn move_along_route_reaches_stops_in_order() {
+    let mut courier = courier_with_bags(3);
+    let first = OrderId::new(Uuid::new_v4());
+    let second = OrderId::new(Uuid::new_v4());
+    let third = OrderId::new(Uuid::new_v4());
+    courier
+        .take_order(
+            first,
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 4).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    courier
+        .take_order(
+            second,
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    courier
+        .take_order(
+            third,
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+
+    let reached: Vec<OrderId> = reached_orders(&mut courier);
+    assert_eq!(reached, vec![first]);
+    assert_eq!(courier.location(), &Location::new(1, 4).unwrap());
+
+    let reached = reached_orders(&mut courier);
+    assert_eq!(reached.len(), 2);
+    assert!(reached.contains(&second) && reached.contains(&third));
+    assert!(!courier.is_en_route());
+    assert!(
+        courier
+            .move_along_route(&CityMap::default())
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn plans_pickup_before_drop_off() {
+    let mut courier = make_courier_at(1, 1);
+    let order_id = OrderId::new(Uuid::new_v4());
+    courier
+        .take_order(
+            order_id,
+            Volume::new(1).unwrap(),
+            Some(&Location::new(1, 9).unwrap()),
+            &Location::new(1, 3).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+
+    let kinds: Vec<StopKind> = courier.route().iter().map(|s| s.kind()).collect();
+    assert_eq!(kinds, vec![StopKind::Pickup, StopKind::DropOff]);
+    assert_eq!(
+        courier.route_detour(
+            Some(&Location::new(1, 9).unwrap()),
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default()
+        ),
+        0
+    );
+}
+
+#[test]
+fn becomes_busy_while_carrying_orders() {
+    let mut courier = make_courier_at(1, 1);
+    let order_id = OrderId::new(Uuid::new_v4());
+    assert_eq!(courier.status(), CourierStatus::Available);
+
+    courier
+        .take_order(
+            order_id,
+            Volume::new(5).unwrap(),
+            None,
+            &Location::new(5, 5).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert_eq!(courier.status(), CourierStatus::Busy);
+
+    courier.complete_order(order_id);
+    assert_eq!(courier.status(), CourierStatus::Available);
+}
+
+#[test]
+fn ends_shift_after_last_order_is_delivered() {
+    let mut courier = make_courier_at(1, 1);
+    let order_id = OrderId::new(Uuid::new_v4());
+    courier
+        .take_order(
+            order_id,
+            Volume::new(5).unwrap(),
+            None,
+            &Location::new(5, 5).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+
+    courier.end_shift().unwrap();
+    assert_eq!(courier.status(), CourierStatus::Busy);
+    assert!(courier.is_ending_shift());
+    assert!(!courier.is_dispatchable(SystemTime::now()));
+    assert!(
+        courier
+            .take_order(
+                OrderId::new(Uuid::new_v4()),
+                Volume::new(5).unwrap(),
+                None,
+                &Location::new(2, 2).unwrap(),
+                &CityMap::default()
+            )
+            .is_err()
+    );
+
+    courier.complete_order(order_id);
+    assert_eq!(courier.status(), CourierStatus::OffDuty);
+    assert!(!courier.is_ending_shift());
+}
+
+#[test]
+fn takes_breaks_only_when_available() {
+    let mut courier = make_courier_at(1, 1);
+    courier.start_break().unwrap();
+    assert_eq!(courier.status(), CourierStatus::OnBreak);
+    assert!(courier.start_break().is_err());
+
+    courier.end_break().unwrap();
+    assert_eq!(courier.status(), CourierStatus::Available);
+    assert!(courier.end_break().is_err());
+}
+
+#[test]
+fn follows_shift_schedule() {
+    let now = SystemTime::now();
+    let mut courier = make_courier_at(1, 1);
+    courier.end_shift().unwrap();
+    courier
+        .schedule_shift(
+            Shift::new(
+                now + Duration::from_secs(60),
+                now + Duration::from_secs(120),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    assert!(courier.start_shift(now).is_err());
+    assert!(!courier.apply_shift_schedule(now).unwrap());
+
+    assert!(
+        courier
+            .apply_shift_schedule(now + Duration::from_secs(60))
+            .unwrap()
+    );
+    assert_eq!(courier.status(), CourierStatus::Available);
+    assert!(courier.is_dispatchable(now + Duration::from_secs(60)));
+
+    assert!(
+        courier
+            .apply_shift_schedule(now + Duration::from_secs(120))
+            .unwrap()
+    );
+    assert_eq!(courier.status(), CourierStatus::OffDuty);
+    assert!(courier.shift().is_none());
+}
+
+#[test]
+fn renames_and_changes_speed() {
+    let mut courier = make_courier_at(1, 1);
+
+    courier.rename(CourierName("Rick".to_string())).unwrap();
+    courier.change_speed(CourierSpeed(3)).unwrap();
+    assert_eq!(courier.name(), "Rick");
+    assert_eq!(*courier.speed(), 3);
+
+    assert!(courier.rename(CourierName(" ".to_string())).is_err());
+    assert!(courier.change_speed(CourierSpeed(0)).is_err());
+}
+
+#[test]
+fn removes_only_empty_storage_places() {
+    let mut courier = make_courier_at(1, 1);
+    let bag = *courier.storage_places()[0].id();
+    assert!(courier.remove_storage_place(bag).is_err());
+
+    let trunk = courier
+        .add_storage_place("trunk".to_string(), Volume::new(20).unwrap())
+        .unwrap();
+    courier
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(15).unwrap(),
+            None,
+            &Location::new(5, 5).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert!(courier.remove_storage_place(trunk).is_err());
+
+    courier.remove_storage_place(bag).unwrap();
+    assert_eq!(courier.storage_places().len(), 1);
+    assert!(courier.remove_storage_place(Uuid::new_v4()).is_err());
+}
+
+#[test]
+fn deactivates_courier_without_orders() {
+    let mut courier = make_courier_at(1, 1);
+    let order_id = OrderId::new(Uuid::new_v4());
+    courier
+        .take_order(
+            order_id,
+            Volume::new(5).unwrap(),
+            None,
+            &Location::new(5, 5).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert!(courier.deactivate().is_err());
+
+    courier.complete_order(order_id);
+    courier.deactivate().unwrap();
+    assert_eq!(courier.status(), CourierStatus::Deactivated);
+    assert!(!courier.is_dispatchable(SystemTime::now()));
+    assert!(courier.start_shift(SystemTime::now()).is_err());
+}
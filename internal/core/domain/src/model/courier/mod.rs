@@ -1,6 +1,17 @@
+pub mod city_map;
 pub mod courier_aggregate;
 #[cfg(test)]
 pub mod courier_aggregate_test;
+pub mod movement;
+#[cfg(test)]
+pub mod movement_test;
+pub mod pathfinding;
+#[cfg(test)]
+pub mod pathfinding_test;
+pub mod route_stop;
+pub mod shift;
+#[cfg(test)]
+pub mod shift_test;
 pub mod storage_place;
 #[cfg(test)]
 pub mod storage_place_test;
@@ -0,0 +1,118 @@
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::kernel::grid::GridBounds;
+use crate::model::kernel::location::Location;
+
+/// How couriers get from one grid cell to another. Distances are in cells,
+/// `speed` in cells per tick.
+pub trait MovementModel: Send + Sync {
+    /// Cell reached after one tick from `from` towards `to`.
+    fn step(&self, from: &Location, to: &Location, speed: u8)
+    -> Result<Location, DomainModelError>;
+
+    /// Cells walked from `from` to `to`; `u32::MAX` when `to` can't be reached.
+    fn distance(&self, from: &Location, to: &Location) -> u32;
+
+    /// Ticks needed to get from `from` to `to`; `u32::MAX` when `to` can't be
+    /// reached.
+    fn ticks(&self, from: &Location, to: &Location, speed: u8) -> u32 {
+        let mut current = from.clone();
+        let mut ticks = 0;
+        while &current != to {
+            match self.step(&current, to, speed) {
+                Ok(next) if next != current => current = next,
+                _ => return u32::MAX,
+            }
+            ticks += 1;
+        }
+        ticks
+    }
+}
+
+pub(crate) fn manhattan(from: &Location, to: &Location) -> u32 {
+    u32::from(from.x().abs_diff(to.x())) + u32::from(from.y().abs_diff(to.y()))
+}
+
+pub(crate) fn ticks_for(distance: u32, speed: u8) -> u32 {
+    match (distance, speed) {
+        (0, _) => 0,
+        (u32::MAX, _) | (_, 0) => u32::MAX,
+        _ => distance.div_ceil(u32::from(speed)),
+    }
+}
+
+/// Steps never leave the rectangle spanned by `from` and `to`, so they stay
+/// within whatever bounds those two were checked against.
+fn cell(x: u16, y: u16) -> Result<Location, DomainModelError> {
+    Location::new_within(x, y, &GridBounds::MAX)
+}
+
+fn towards(from: u16, to: u16, by: u16) -> u16 {
+    if from < to {
+        from.saturating_add(by).min(to)
+    } else {
+        from.saturating_sub(by).max(to)
+    }
+}
+
+/// Moves up to `speed` cells along the axis with the longer way to go; speed
+/// left over once that axis is done is lost.
+pub struct AxisStepping;
+
+impl MovementModel for AxisStepping {
+    fn step(
+        &self,
+        from: &Location,
+        to: &Location,
+        speed: u8,
+    ) -> Result<Location, DomainModelError> {
+        let speed = u16::from(speed);
+        if from.x().abs_diff(to.x()) >= from.y().abs_diff(to.y()) {
+            cell(towards(from.x(), to.x(), speed), from.y())
+        } else {
+            cell(from.x(), towards(from.y(), to.y(), speed))
+        }
+    }
+
+    fn distance(&self, from: &Location, to: &Location) -> u32 {
+        manhattan(from, to)
+    }
+}
+
+/// Moves along the longer axis first and spends speed left over on the other
+/// one, covering `speed` cells every tick.
+pub struct ManhattanStepping;
+
+impl MovementModel for ManhattanStepping {
+    fn step(
+        &self,
+        from: &Location,
+        to: &Location,
+        speed: u8,
+    ) -> Result<Location, DomainModelError> {
+        let speed = u16::from(speed);
+        let x_distance = from.x().abs_diff(to.x());
+        let y_distance = from.y().abs_diff(to.y());
+
+        if x_distance >= y_distance {
+            let left = speed.saturating_sub(x_distance);
+            cell(
+                towards(from.x(), to.x(), speed),
+                towards(from.y(), to.y(), left),
+            )
+        } else {
+            let left = speed.saturating_sub(y_distance);
+            cell(
+                towards(from.x(), to.x(), left),
+                towards(from.y(), to.y(), speed),
+            )
+        }
+    }
+
+    fn distance(&self, from: &Location, to: &Location) -> u32 {
+        manhattan(from, to)
+    }
+
+    fn ticks(&self, from: &Location, to: &Location, speed: u8) -> u32 {
+        ticks_for(manhattan(from, to), speed)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::model::courier::movement::AxisStepping;
+use crate::model::courier::movement::ManhattanStepping;
+use crate::model::courier::movement::MovementModel;
+use crate::model::kernel::location::Location;
+
+fn at(x: u16, y: u16) -> Location {
+    Location::new(x, y).unwrap()
+}
+
+#[test]
+fn axis_stepping_loses_leftover_speed() {
+    let model = AxisStepping;
+
+    assert_eq!(model.step(&at(1, 1), &at(3, 2), 5).unwrap(), at(3, 1));
+    assert_eq!(model.ticks(&at(1, 1), &at(3, 2), 5), 2);
+    assert_eq!(model.distance(&at(1, 1), &at(3, 2)), 3);
+}
+
+#[test]
+fn manhattan_stepping_spends_leftover_speed_on_other_axis() {
+    let model = ManhattanStepping;
+
+    assert_eq!(model.step(&at(1, 1), &at(3, 5), 5).unwrap(), at(2, 5));
+    assert_eq!(model.step(&at(1, 1), &at(4, 2), 5).unwrap(), at(4, 2));
+    assert_eq!(model.ticks(&at(1, 1), &at(4, 2), 5), 1);
+    assert_eq!(model.ticks(&at(1, 1), &at(10, 10), 5), 4);
+}
+
+#[test]
+fn ticks_match_stepping() {
+    let from = at(2, 9);
+    let to = at(8, 1);
+
+    for model in [&AxisStepping as &dyn MovementModel, &ManhattanStepping] {
+        let mut current = from.clone();
+        let mut ticks = 0;
+        while current != to {
+            current = model.step(&current, &to, 3).unwrap();
+            ticks += 1;
+        }
+
+        assert_eq!(model.ticks(&from, &to, 3), ticks);
+    }
+}
@@ -0,0 +1,175 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::movement::MovementModel;
+use crate::model::courier::movement::manhattan;
+use crate::model::courier::movement::ticks_for;
+use crate::model::kernel::grid::GridBounds;
+use crate::model::kernel::location::Location;
+
+type Cell = (u16, u16);
+
+/// Cells the search may expand before it settles for the best partial path.
+pub const DEFAULT_MAX_EXPANSIONS: usize = 10_000;
+
+/// Walks shortest paths around blocked cells such as rivers or closed
+/// streets, one cell at a time, `speed` cells every tick.
+///
+/// A search expands at most `max_expansions` cells, so a destination walled
+/// off in a large grid does not flood all of it. Past that, couriers walk
+/// towards the explored cell closest to the destination and distances fall
+/// back to Manhattan distance.
+pub struct AStarMovement {
+    bounds: GridBounds,
+    blocked: HashSet<Cell>,
+    max_expansions: usize,
+}
+
+enum Search {
+    Found(Vec<Cell>),
+    Unreachable,
+    /// Cells towards the explored one closest to the destination.
+    GaveUp(Vec<Cell>),
+}
+
+impl AStarMovement {
+    pub fn new(bounds: GridBounds, blocked: impl IntoIterator<Item = Cell>) -> Self {
+        Self {
+            bounds,
+            blocked: blocked.into_iter().collect(),
+            max_expansions: DEFAULT_MAX_EXPANSIONS,
+        }
+    }
+
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = max_expansions.max(1);
+        self
+    }
+
+    /// Cells after `from` up to and including `to` along a shortest path, or
+    /// `None` when `to` is walled off or the search gave up before reaching
+    /// it. The destination itself is always enterable so orders on a blocked
+    /// cell can still be handed over.
+    pub fn path(&self, from: &Location, to: &Location) -> Option<Vec<Cell>> {
+        match self.search(from, to) {
+            Search::Found(path) => Some(path),
+            Search::Unreachable | Search::GaveUp(_) => None,
+        }
+    }
+
+    fn search(&self, from: &Location, to: &Location) -> Search {
+        let start = (from.x(), from.y());
+        let goal = (to.x(), to.y());
+        let heuristic =
+            |(x, y): Cell| u32::from(x.abs_diff(goal.0)) + u32::from(y.abs_diff(goal.1));
+
+        let mut open = BinaryHeap::new();
+        let mut cost: HashMap<Cell, u32> = HashMap::new();
+        let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+        let mut closest = (heuristic(start), start);
+        let mut expansions = 0;
+        cost.insert(start, 0);
+        open.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_, walked, cell))) = open.pop() {
+            if cell == goal {
+                return Search::Found(walk_back(&came_from, start, cell));
+            }
+
+            if cost.get(&cell).is_some_and(|&known| walked > known) {
+                continue;
+            }
+
+            if expansions == self.max_expansions {
+                return Search::GaveUp(walk_back(&came_from, start, closest.1));
+            }
+            expansions += 1;
+            closest = closest.min((heuristic(cell), cell));
+
+            for next in self.neighbours(cell, goal) {
+                let next_walked = walked + 1;
+                if cost.get(&next).is_none_or(|&known| next_walked < known) {
+                    cost.insert(next, next_walked);
+                    came_from.insert(next, cell);
+                    open.push(Reverse((next_walked + heuristic(next), next_walked, next)));
+                }
+            }
+        }
+
+        Search::Unreachable
+    }
+
+    fn neighbours(&self, (x, y): Cell, goal: Cell) -> impl Iterator<Item = Cell> + '_ {
+        [
+            (x.checked_sub(1), Some(y)),
+            (x.checked_add(1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), y.checked_add(1)),
+        ]
+        .into_iter()
+        .filter_map(|cell| match cell {
+            (Some(x), Some(y)) => Some((x, y)),
+            _ => None,
+        })
+        .filter(|&(x, y)| self.bounds.contains(x, y))
+        .filter(move |&cell| cell == goal || !self.blocked.contains(&cell))
+    }
+}
+
+fn walk_back(came_from: &HashMap<Cell, Cell>, start: Cell, end: Cell) -> Vec<Cell> {
+    let mut path = Vec::new();
+    let mut current = end;
+    while current != start {
+        path.push(current);
+        current = came_from[&current];
+    }
+    path.reverse();
+    path
+}
+
+impl MovementModel for AStarMovement {
+    fn step(
+        &self,
+        from: &Location,
+        to: &Location,
+        speed: u8,
+    ) -> Result<Location, DomainModelError> {
+        let no_path = || {
+            DomainModelError::UnmetRequirement(format!(
+                "no path from {}:{} to {}:{}",
+                from.x(),
+                from.y(),
+                to.x(),
+                to.y()
+            ))
+        };
+
+        let path = match self.search(from, to) {
+            Search::Found(path) => path,
+            Search::GaveUp(path) if !path.is_empty() => path,
+            Search::Unreachable | Search::GaveUp(_) => return Err(no_path()),
+        };
+
+        if path.is_empty() || speed == 0 {
+            return Ok(from.clone());
+        }
+
+        let (x, y) = path[usize::from(speed).min(path.len()) - 1];
+        Location::new_within(x, y, &self.bounds)
+    }
+
+    fn distance(&self, from: &Location, to: &Location) -> u32 {
+        match self.search(from, to) {
+            Search::Found(path) => path.len() as u32,
+            Search::Unreachable => u32::MAX,
+            Search::GaveUp(_) => manhattan(from, to),
+        }
+    }
+
+    fn ticks(&self, from: &Location, to: &Location, speed: u8) -> u32 {
+        ticks_for(self.distance(from, to), speed)
+    }
+}
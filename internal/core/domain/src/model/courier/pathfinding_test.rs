@@ -0,0 +1,81 @@
+use crate::model::courier::movement::MovementModel;
+use crate::model::courier::pathfinding::AStarMovement;
+use crate::model::kernel::grid::GridBounds;
+use crate::model::kernel::location::Location;
+
+fn at(x: u16, y: u16) -> Location {
+    Location::new(x, y).unwrap()
+}
+
+/// A river along x = 5 with a single bridge at y = 9.
+fn river() -> AStarMovement {
+    let blocked = (1..=10).filter(|&y| y != 9).map(|y| (5, y));
+    AStarMovement::new(GridBounds::DEFAULT, blocked)
+}
+
+#[test]
+fn walks_straight_without_obstacles() {
+    let model = AStarMovement::new(GridBounds::DEFAULT, []);
+
+    assert_eq!(model.distance(&at(1, 1), &at(4, 5)), 7);
+    assert_eq!(model.ticks(&at(1, 1), &at(4, 5), 2), 4);
+    assert_eq!(model.distance(&at(3, 3), &at(3, 3)), 0);
+}
+
+#[test]
+fn goes_around_blocked_cells() {
+    let model = river();
+
+    let path = model.path(&at(4, 1), &at(6, 1)).unwrap();
+
+    assert_eq!(path.len(), 18);
+    assert!(path.contains(&(5, 9)));
+    assert!(path.iter().all(|&(x, y)| x != 5 || y == 9));
+    assert_eq!(model.distance(&at(4, 1), &at(6, 1)), 18);
+}
+
+#[test]
+fn steps_along_path_by_speed() {
+    let model = river();
+    let from = at(4, 1);
+    let to = at(6, 1);
+
+    let next = model.step(&from, &to, 5).unwrap();
+    assert_eq!(next, at(4, 6));
+
+    let mut current = from.clone();
+    let mut ticks = 0;
+    while current != to {
+        current = model.step(&current, &to, 5).unwrap();
+        ticks += 1;
+    }
+    assert_eq!(model.ticks(&from, &to, 5), ticks);
+}
+
+#[test]
+fn reports_walled_off_destination() {
+    let wall = (1..=10).map(|y| (5, y));
+    let model = AStarMovement::new(GridBounds::DEFAULT, wall);
+
+    assert!(model.path(&at(1, 1), &at(9, 1)).is_none());
+    assert_eq!(model.distance(&at(1, 1), &at(9, 1)), u32::MAX);
+    assert_eq!(model.ticks(&at(1, 1), &at(9, 1), 5), u32::MAX);
+    assert!(model.step(&at(1, 1), &at(9, 1), 5).is_err());
+}
+
+#[test]
+fn gives_up_on_walled_off_destination_in_large_grid() {
+    let bounds = GridBounds::new(1000, 1000).unwrap();
+    let walls = [(499, 500), (501, 500), (500, 499), (500, 501)];
+    let model = AStarMovement::new(bounds, walls).with_max_expansions(500);
+    let far = |x, y| Location::new_within(x, y, &bounds).unwrap();
+    let from = far(10, 10);
+    let to = far(500, 500);
+
+    assert!(model.path(&from, &to).is_none());
+    assert_eq!(model.distance(&from, &to), 980);
+    assert_eq!(model.ticks(&from, &to, 5), 196);
+
+    let next = model.step(&from, &to, 5).unwrap();
+    assert_eq!(u32::from(next.x() - 10) + u32::from(next.y() - 10), 5);
+}
@@ -0,0 +1,58 @@
+use crate::model::kernel::location::Location;
+use crate::model::order::order_aggregate::OrderId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopKind {
+    Pickup,
+    DropOff,
+}
+
+impl From<StopKind> for String {
+    fn from(value: StopKind) -> Self {
+        match value {
+            StopKind::Pickup => "pickup".into(),
+            StopKind::DropOff => "drop_off".into(),
+        }
+    }
+}
+
+impl TryFrom<&str> for StopKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "pickup" => Ok(StopKind::Pickup),
+            "drop_off" => Ok(StopKind::DropOff),
+            other => Err(format!("invalid route stop kind: {}", other)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteStop {
+    order_id: OrderId,
+    kind: StopKind,
+    location: Location,
+}
+
+impl RouteStop {
+    pub fn new(order_id: OrderId, kind: StopKind, location: Location) -> Self {
+        Self {
+            order_id,
+            kind,
+            location,
+        }
+    }
+
+    pub fn order_id(&self) -> OrderId {
+        self.order_id
+    }
+
+    pub fn kind(&self) -> StopKind {
+        self.kind
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+}
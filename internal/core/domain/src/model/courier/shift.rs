@@ -0,0 +1,78 @@
+use std::time::SystemTime;
+
+use crate::errors::domain_model_errors::DomainModelError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CourierStatus {
+    OffDuty,
+    Available,
+    Busy,
+    OnBreak,
+    Deactivated,
+}
+
+impl From<CourierStatus> for String {
+    fn from(value: CourierStatus) -> Self {
+        match value {
+            CourierStatus::OffDuty => "off_duty".into(),
+            CourierStatus::Available => "available".into(),
+            CourierStatus::Busy => "busy".into(),
+            CourierStatus::OnBreak => "on_break".into(),
+            CourierStatus::Deactivated => "deactivated".into(),
+        }
+    }
+}
+
+impl TryFrom<&str> for CourierStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "off_duty" => Ok(CourierStatus::OffDuty),
+            "available" => Ok(CourierStatus::Available),
+            "busy" => Ok(CourierStatus::Busy),
+            "on_break" => Ok(CourierStatus::OnBreak),
+            "deactivated" => Ok(CourierStatus::Deactivated),
+            other => Err(format!("invalid courier status: {}", other)),
+        }
+    }
+}
+
+/// Working hours a courier is scheduled for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Shift {
+    start: SystemTime,
+    end: SystemTime,
+}
+
+impl Shift {
+    pub fn new(start: SystemTime, end: SystemTime) -> Result<Self, DomainModelError> {
+        if end <= start {
+            return Err(DomainModelError::UnmetRequirement(
+                "shift must end after it starts".into(),
+            ));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> SystemTime {
+        self.start
+    }
+
+    pub fn end(&self) -> SystemTime {
+        self.end
+    }
+
+    pub fn has_started(&self, now: SystemTime) -> bool {
+        now >= self.start
+    }
+
+    pub fn has_ended(&self, now: SystemTime) -> bool {
+        now >= self.end
+    }
+
+    pub fn contains(&self, now: SystemTime) -> bool {
+        self.has_started(now) && !self.has_ended(now)
+    }
+}
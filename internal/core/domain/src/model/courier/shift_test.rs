@@ -0,0 +1,39 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::model::courier::shift::CourierStatus;
+use crate::model::courier::shift::Shift;
+
+#[test]
+fn rejects_shift_ending_before_it_starts() {
+    let now = SystemTime::now();
+    assert!(Shift::new(now, now).is_err());
+    assert!(Shift::new(now, now - Duration::from_secs(1)).is_err());
+}
+
+#[test]
+fn contains_times_between_start_and_end() {
+    let now = SystemTime::now();
+    let shift = Shift::new(now, now + Duration::from_secs(3600)).unwrap();
+
+    assert!(!shift.contains(now - Duration::from_secs(1)));
+    assert!(shift.contains(now));
+    assert!(shift.contains(now + Duration::from_secs(1800)));
+    assert!(!shift.contains(now + Duration::from_secs(3600)));
+    assert!(shift.has_ended(now + Duration::from_secs(3600)));
+}
+
+#[test]
+fn converts_status_to_and_from_string() {
+    for status in [
+        CourierStatus::OffDuty,
+        CourierStatus::Available,
+        CourierStatus::Busy,
+        CourierStatus::OnBreak,
+        CourierStatus::Deactivated,
+    ] {
+        let raw = String::from(status);
+        assert_eq!(CourierStatus::try_from(raw.as_str()), Ok(status));
+    }
+    assert!(CourierStatus::try_from("sleeping").is_err());
+}
@@ -0,0 +1,165 @@
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::kernel::great_circle;
+use crate::model::kernel::grid::GridBounds;
+
+/// How locations coming from the geo service are interpreted.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CoordinateSystem {
+    /// Integer cells within the bounds, Manhattan distance, speed in cells
+    /// per tick.
+    Grid(GridBounds),
+    /// WGS84 latitude and longitude, distance in meters, speed in km/h.
+    Geographic(GeoSettings),
+}
+
+impl CoordinateSystem {
+    /// Settings geographic locations are measured with; the defaults while
+    /// running on the grid.
+    pub fn geo_settings(&self) -> GeoSettings {
+        match self {
+            CoordinateSystem::Grid(_) => GeoSettings::DEFAULT,
+            CoordinateSystem::Geographic(settings) => *settings,
+        }
+    }
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        CoordinateSystem::Grid(GridBounds::DEFAULT)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DistanceFormula {
+    Haversine,
+    Equirectangular,
+}
+
+impl DistanceFormula {
+    pub fn distance(&self, from: (f64, f64), to: (f64, f64)) -> f64 {
+        match self {
+            DistanceFormula::Haversine => great_circle::haversine(from, to),
+            DistanceFormula::Equirectangular => great_circle::equirectangular(from, to),
+        }
+    }
+}
+
+/// Rectangle geographic locations have to lie in, in degrees.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GeoArea {
+    south: f64,
+    west: f64,
+    north: f64,
+    east: f64,
+}
+
+impl GeoArea {
+    pub const WORLD: GeoArea = GeoArea {
+        south: -90.0,
+        west: -180.0,
+        north: 90.0,
+        east: 180.0,
+    };
+
+    pub fn new(south: f64, west: f64, north: f64, east: f64) -> Result<Self, DomainModelError> {
+        let latitudes = -90.0..=90.0;
+        let longitudes = -180.0..=180.0;
+        if !latitudes.contains(&south)
+            || !latitudes.contains(&north)
+            || !longitudes.contains(&west)
+            || !longitudes.contains(&east)
+            || south >= north
+            || west >= east
+        {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "invalid geo area. south: {}, west: {}, north: {}, east: {}",
+                south, west, north, east
+            )));
+        }
+
+        Ok(Self {
+            south,
+            west,
+            north,
+            east,
+        })
+    }
+
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        (self.south..=self.north).contains(&latitude)
+            && (self.west..=self.east).contains(&longitude)
+    }
+
+    pub fn south(&self) -> f64 {
+        self.south
+    }
+
+    pub fn west(&self) -> f64 {
+        self.west
+    }
+
+    pub fn north(&self) -> f64 {
+        self.north
+    }
+
+    pub fn east(&self) -> f64 {
+        self.east
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GeoSettings {
+    area: GeoArea,
+    formula: DistanceFormula,
+    tick_seconds: u32,
+}
+
+impl GeoSettings {
+    pub const DEFAULT: GeoSettings = GeoSettings {
+        area: GeoArea::WORLD,
+        formula: DistanceFormula::Haversine,
+        tick_seconds: 60,
+    };
+
+    /// `tick_seconds` is how much time a courier covers on every move.
+    pub fn new(
+        area: GeoArea,
+        formula: DistanceFormula,
+        tick_seconds: u32,
+    ) -> Result<Self, DomainModelError> {
+        if tick_seconds == 0 {
+            return Err(DomainModelError::ArgumentCannotBeZero(
+                "tick seconds".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            area,
+            formula,
+            tick_seconds,
+        })
+    }
+
+    pub fn area(&self) -> &GeoArea {
+        &self.area
+    }
+
+    pub fn formula(&self) -> DistanceFormula {
+        self.formula
+    }
+
+    pub fn tick_seconds(&self) -> u32 {
+        self.tick_seconds
+    }
+
+    /// Meters covered in one tick at `speed_kmh`.
+    pub fn step_meters(&self, speed_kmh: u8) -> f64 {
+        f64::from(speed_kmh) * 1000.0 / 3600.0 * f64::from(self.tick_seconds)
+    }
+}
+
+impl Default for GeoSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
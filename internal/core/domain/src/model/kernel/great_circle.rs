@@ -0,0 +1,83 @@
+//! Distances and paths between WGS84 points given as `(latitude, longitude)`
+//! in degrees. Distances are in meters.
+
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+pub fn haversine(from: (f64, f64), to: (f64, f64)) -> f64 {
+    EARTH_RADIUS_METERS * central_angle(from, to)
+}
+
+/// Flat earth approximation; cheaper than haversine and accurate enough
+/// within a city.
+pub fn equirectangular(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+
+    let mut delta_lon = lon2 - lon1;
+    if delta_lon > std::f64::consts::PI {
+        delta_lon -= 2.0 * std::f64::consts::PI;
+    } else if delta_lon < -std::f64::consts::PI {
+        delta_lon += 2.0 * std::f64::consts::PI;
+    }
+
+    let x = delta_lon * ((lat1 + lat2) / 2.0).cos();
+    let y = lat2 - lat1;
+    EARTH_RADIUS_METERS * (x * x + y * y).sqrt()
+}
+
+/// Below this `sin` of the central angle the two points are treated as the
+/// same or antipodal; dividing by it would give NaN or noise.
+const DEGENERATE_SINE: f64 = 1e-9;
+
+/// Point `fraction` of the way from `from` to `to` along the great circle
+/// through both. Identical points stay at `from`; antipodal ones, joined by
+/// every great circle through them, go north along the meridian of `from`.
+pub fn interpolate(from: (f64, f64), to: (f64, f64), fraction: f64) -> (f64, f64) {
+    let angle = central_angle(from, to);
+    if angle.sin() < DEGENERATE_SINE {
+        return if angle < std::f64::consts::FRAC_PI_2 {
+            from
+        } else {
+            along_meridian(from, fraction * 180.0)
+        };
+    }
+
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let a = ((1.0 - fraction) * angle).sin() / angle.sin();
+    let b = (fraction * angle).sin() / angle.sin();
+
+    let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+    let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+    let z = a * lat1.sin() + b * lat2.sin();
+
+    (
+        z.atan2((x * x + y * y).sqrt()).to_degrees(),
+        y.atan2(x).to_degrees(),
+    )
+}
+
+/// Point `degrees` north of `from` along its meridian, over the pole and down
+/// the opposite meridian when it goes past it.
+fn along_meridian(from: (f64, f64), degrees: f64) -> (f64, f64) {
+    let latitude = from.0 + degrees;
+    if latitude <= 90.0 {
+        return (latitude, from.1);
+    }
+
+    let longitude = if from.1 > 0.0 {
+        from.1 - 180.0
+    } else {
+        from.1 + 180.0
+    };
+    (180.0 - latitude, longitude)
+}
+
+fn central_angle(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * h.sqrt().min(1.0).asin()
+}
@@ -0,0 +1,62 @@
+use super::great_circle::equirectangular;
+use super::great_circle::haversine;
+use super::great_circle::interpolate;
+
+const MOSCOW: (f64, f64) = (55.7558, 37.6173);
+const SAINT_PETERSBURG: (f64, f64) = (59.9343, 30.3351);
+
+#[test]
+fn measures_haversine_distance() {
+    let distance = haversine(MOSCOW, SAINT_PETERSBURG);
+
+    assert!((distance - 634_000.0).abs() < 2_000.0, "{}", distance);
+    assert_eq!(haversine(MOSCOW, MOSCOW), 0.0);
+}
+
+#[test]
+fn equirectangular_is_close_to_haversine_within_a_city() {
+    let from = (55.7558, 37.6173);
+    let to = (55.8000, 37.7000);
+
+    let exact = haversine(from, to);
+    let approximate = equirectangular(from, to);
+
+    assert!(
+        (exact - approximate).abs() < 1.0,
+        "{} vs {}",
+        exact,
+        approximate
+    );
+}
+
+#[test]
+fn interpolates_along_great_circle() {
+    let halfway = interpolate(MOSCOW, SAINT_PETERSBURG, 0.5);
+
+    let total = haversine(MOSCOW, SAINT_PETERSBURG);
+    assert!((haversine(MOSCOW, halfway) - total / 2.0).abs() < 1.0);
+    assert!((haversine(halfway, SAINT_PETERSBURG) - total / 2.0).abs() < 1.0);
+}
+
+#[test]
+fn stays_put_between_identical_points() {
+    assert_eq!(interpolate(MOSCOW, MOSCOW, 0.5), MOSCOW);
+    assert_eq!(interpolate(MOSCOW, MOSCOW, 1.0), MOSCOW);
+}
+
+#[test]
+fn crosses_the_north_pole_between_antipodal_points() {
+    let from = (-30.0, 40.0);
+    let antipode = (30.0, -140.0);
+
+    let quarter = interpolate(from, antipode, 0.25);
+    let past_pole = interpolate(from, antipode, 0.75);
+    let end = interpolate(from, antipode, 1.0);
+
+    assert_eq!(quarter, (15.0, 40.0));
+    assert_eq!(past_pole, (75.0, -140.0));
+    assert_eq!(end, antipode);
+    let total = haversine(from, antipode);
+    assert!((haversine(from, quarter) - total / 4.0).abs() < 1.0);
+    assert!((haversine(quarter, antipode) - total * 3.0 / 4.0).abs() < 1.0);
+}
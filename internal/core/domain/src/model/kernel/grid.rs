@@ -0,0 +1,51 @@
+use crate::errors::domain_model_errors::DomainModelError;
+
+/// Size of the city grid. Coordinates run from 1 up to and including the
+/// width and height.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct GridBounds {
+    width: u16,
+    height: u16,
+}
+
+impl GridBounds {
+    pub const DEFAULT: GridBounds = GridBounds {
+        width: 10,
+        height: 10,
+    };
+
+    /// Every cell a coordinate can name.
+    pub const MAX: GridBounds = GridBounds {
+        width: u16::MAX,
+        height: u16::MAX,
+    };
+
+    pub fn new(width: u16, height: u16) -> Result<Self, DomainModelError> {
+        if width == 0 || height == 0 {
+            return Err(DomainModelError::ArgumentCannotBeZero(format!(
+                "grid bounds. width: {}, height: {}",
+                width, height
+            )));
+        }
+
+        Ok(Self { width, height })
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (1..=self.width).contains(&x) && (1..=self.height).contains(&y)
+    }
+}
+
+impl Default for GridBounds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,49 @@
+use super::grid::GridBounds;
+use super::location::Location;
+
+#[test]
+fn defaults_to_ten_by_ten() {
+    let bounds = GridBounds::default();
+
+    assert_eq!(bounds.width(), 10);
+    assert_eq!(bounds.height(), 10);
+    assert!(bounds.contains(10, 10));
+    assert!(!bounds.contains(11, 1));
+}
+
+#[test]
+fn rejects_zero_size() {
+    assert!(GridBounds::new(0, 10).is_err());
+    assert!(GridBounds::new(10, 0).is_err());
+}
+
+#[test]
+fn accepts_locations_within_larger_grid() {
+    let bounds = GridBounds::new(1000, 500).unwrap();
+
+    let location = Location::new_within(1000, 500, &bounds).unwrap();
+
+    assert_eq!(location.x(), 1000);
+    assert_eq!(location.y(), 500);
+    assert!(Location::new_within(1001, 1, &bounds).is_err());
+    assert!(Location::new_within(1, 501, &bounds).is_err());
+}
+
+#[test]
+fn rejects_raw_coordinates_that_do_not_fit() {
+    assert!(Location::try_from_raw(-1i32, 1, &GridBounds::MAX).is_err());
+    assert!(Location::try_from_raw(65_537i32, 1, &GridBounds::MAX).is_err());
+    assert_eq!(
+        Location::try_from_raw(3i32, 4, &GridBounds::MAX).unwrap(),
+        Location::new(3, 4).unwrap()
+    );
+}
+
+#[test]
+fn checks_raw_coordinates_against_given_bounds() {
+    let bounds = GridBounds::new(1000, 500).unwrap();
+
+    assert!(Location::try_from_raw(1000i32, 500, &bounds).is_ok());
+    assert!(Location::try_from_raw(1000i32, 501, &bounds).is_err());
+    assert!(Location::try_from_raw(11i32, 1, &GridBounds::DEFAULT).is_err());
+}
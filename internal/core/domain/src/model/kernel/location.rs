@@ -1,51 +1,158 @@
 use rand::Rng;
 use rand::rng;
+use std::fmt::Display;
 
 use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::kernel::coordinate_system::CoordinateSystem;
+use crate::model::kernel::coordinate_system::DistanceFormula;
+use crate::model::kernel::coordinate_system::GeoArea;
+use crate::model::kernel::grid::GridBounds;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Coordinates {
+    Grid { x: u16, y: u16 },
+    Geographic { latitude: f64, longitude: f64 },
+}
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Location {
-    x: u8,
-    y: u8,
+    coordinates: Coordinates,
 }
 
 impl Location {
-    pub fn new(x: u8, y: u8) -> Result<Self, DomainModelError> {
-        if x * y == 0 {
+    /// Cell on the default grid; `new_within` checks configured bounds.
+    pub fn new(x: u16, y: u16) -> Result<Self, DomainModelError> {
+        Self::new_within(x, y, &GridBounds::DEFAULT)
+    }
+
+    pub fn new_within(x: u16, y: u16, bounds: &GridBounds) -> Result<Self, DomainModelError> {
+        if x == 0 || y == 0 {
             return Err(DomainModelError::ArgumentCannotBeZero(format!(
                 "coordinates cannot be less than 1. x: {}, y: {}",
                 x, y
             )));
         }
 
-        if x > 10 || y > 10 {
+        if !bounds.contains(x, y) {
             return Err(DomainModelError::UnmetRequirement(format!(
-                "coordinates cannot be more than 10. x: {}, y: {}",
-                x, y
+                "coordinates cannot be more than {}x{}. x: {}, y: {}",
+                bounds.width(),
+                bounds.height(),
+                x,
+                y
+            )));
+        }
+
+        Ok(Self {
+            coordinates: Coordinates::Grid { x, y },
+        })
+    }
+
+    /// Point anywhere on earth; `new_geographic_within` checks a configured
+    /// area.
+    pub fn new_geographic(latitude: f64, longitude: f64) -> Result<Self, DomainModelError> {
+        Self::new_geographic_within(latitude, longitude, &GeoArea::WORLD)
+    }
+
+    pub fn new_geographic_within(
+        latitude: f64,
+        longitude: f64,
+        area: &GeoArea,
+    ) -> Result<Self, DomainModelError> {
+        if !area.contains(latitude, longitude) {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "coordinates are outside of the area. latitude: {}, longitude: {}",
+                latitude, longitude
             )));
         }
 
-        Ok(Self { x, y })
+        Ok(Self {
+            coordinates: Coordinates::Geographic {
+                latitude,
+                longitude,
+            },
+        })
+    }
+
+    /// Builds a location from coordinates stored or sent in a wider or signed
+    /// type, rejecting values that do not fit instead of truncating them.
+    pub fn try_from_raw<T>(x: T, y: T, bounds: &GridBounds) -> Result<Self, DomainModelError>
+    where
+        T: TryInto<u16> + Copy + Display,
+    {
+        match (x.try_into(), y.try_into()) {
+            (Ok(cx), Ok(cy)) => Self::new_within(cx, cy, bounds),
+            _ => Err(DomainModelError::UnmetRequirement(format!(
+                "coordinates are out of range. x: {}, y: {}",
+                x, y
+            ))),
+        }
     }
 
-    pub fn new_random() -> Location {
+    pub fn new_random(system: &CoordinateSystem) -> Location {
         let mut rand = rng();
 
-        let x = rand.random_range(1..=10);
-        let y = rand.random_range(1..=10);
+        let coordinates = match system {
+            CoordinateSystem::Grid(bounds) => Coordinates::Grid {
+                x: rand.random_range(1..=bounds.width()),
+                y: rand.random_range(1..=bounds.height()),
+            },
+            CoordinateSystem::Geographic(settings) => {
+                let area = settings.area();
+                Coordinates::Geographic {
+                    latitude: rand.random_range(area.south()..=area.north()),
+                    longitude: rand.random_range(area.west()..=area.east()),
+                }
+            }
+        };
+
+        Self { coordinates }
+    }
+
+    pub fn coordinates(&self) -> Coordinates {
+        self.coordinates
+    }
 
-        Self { x, y }
+    /// Grid column; zero for geographic locations.
+    pub fn x(&self) -> u16 {
+        match self.coordinates {
+            Coordinates::Grid { x, .. } => x,
+            Coordinates::Geographic { .. } => 0,
+        }
     }
 
-    pub fn x(&self) -> u8 {
-        self.x
+    /// Grid row; zero for geographic locations.
+    pub fn y(&self) -> u16 {
+        match self.coordinates {
+            Coordinates::Grid { y, .. } => y,
+            Coordinates::Geographic { .. } => 0,
+        }
     }
 
-    pub fn y(&self) -> u8 {
-        self.y
+    /// Manhattan distance in cells on the grid, great-circle meters between
+    /// geographic locations.
+    pub fn get_distance(&self, other: &Location) -> u32 {
+        self.get_distance_by(other, DistanceFormula::Haversine)
     }
 
-    pub fn get_distance(&self, other: &Location) -> u8 {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    /// Like `get_distance`, with geographic distance measured by `formula`.
+    /// Grid and geographic locations are never close to each other.
+    pub fn get_distance_by(&self, other: &Location, formula: DistanceFormula) -> u32 {
+        match (self.coordinates, other.coordinates) {
+            (Coordinates::Grid { x: x1, y: y1 }, Coordinates::Grid { x: x2, y: y2 }) => {
+                u32::from(x1.abs_diff(x2)) + u32::from(y1.abs_diff(y2))
+            }
+            (
+                Coordinates::Geographic {
+                    latitude: lat1,
+                    longitude: lon1,
+                },
+                Coordinates::Geographic {
+                    latitude: lat2,
+                    longitude: lon2,
+                },
+            ) => formula.distance((lat1, lon1), (lat2, lon2)).round() as u32,
+            _ => u32::MAX,
+        }
     }
 }
@@ -1,4 +1,7 @@
+use super::coordinate_system::GeoArea;
+use super::location::Coordinates;
 use super::location::Location;
+use crate::model::kernel::coordinate_system::CoordinateSystem;
 
 #[test]
 fn gets_distance() {
@@ -11,7 +14,7 @@ fn gets_distance() {
 
 #[test]
 fn creates_random() {
-    let a = Location::new_random();
+    let a = Location::new_random(&CoordinateSystem::default());
 
     assert!(a.x() >= 1 && a.x() <= 10);
     assert!(a.y() >= 1 && a.y() <= 10);
@@ -27,3 +30,38 @@ fn compares() {
     assert_eq!(a, c);
     assert_ne!(b, c);
 }
+
+#[test]
+fn rejects_coordinates_outside_default_grid() {
+    assert!(Location::new(0, 1).is_err());
+    assert!(Location::new(11, 1).is_err());
+    assert!(Location::new(1, 11).is_err());
+}
+
+#[test]
+fn creates_geographic_location() {
+    let area = GeoArea::new(55.5, 37.3, 56.0, 37.9).unwrap();
+
+    let location = Location::new_geographic_within(55.7558, 37.6173, &area).unwrap();
+
+    assert_eq!(
+        location.coordinates(),
+        Coordinates::Geographic {
+            latitude: 55.7558,
+            longitude: 37.6173
+        }
+    );
+    assert!(Location::new_geographic_within(59.9343, 30.3351, &area).is_err());
+    assert!(Location::new_geographic(91.0, 0.0).is_err());
+    assert!(Location::new_geographic(f64::NAN, 0.0).is_err());
+}
+
+#[test]
+fn gets_geographic_distance_in_meters() {
+    let moscow = Location::new_geographic(55.7558, 37.6173).unwrap();
+    let saint_petersburg = Location::new_geographic(59.9343, 30.3351).unwrap();
+
+    let distance = moscow.get_distance(&saint_petersburg);
+
+    assert!((632_000..636_000).contains(&distance), "{}", distance);
+}
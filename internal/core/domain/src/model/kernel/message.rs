@@ -9,6 +9,13 @@ pub struct Message {
     pub payload: String,
     pub occured_at: SystemTime,
     pub processed_at: Option<SystemTime>,
+    /// Failed delivery attempts so far.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Earliest moment the relay may retry the message.
+    pub next_attempt_at: Option<SystemTime>,
+    /// Set once the message gave up on delivery; it is never picked up again.
+    pub dead_lettered_at: Option<SystemTime>,
 }
 
 impl Message {
@@ -19,6 +26,38 @@ impl Message {
             payload,
             occured_at: SystemTime::now(),
             processed_at: None,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: None,
+            dead_lettered_at: None,
         }
     }
+
+    pub fn mark_published(&mut self) {
+        self.processed_at = Some(SystemTime::now());
+        self.next_attempt_at = None;
+    }
+
+    pub fn record_failure(&mut self, error: String, retry_at: SystemTime) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.next_attempt_at = Some(retry_at);
+    }
+
+    /// Puts the message off until `retry_at` without counting an attempt,
+    /// e.g. while an earlier message it must follow waits for its retry.
+    pub fn defer(&mut self, retry_at: SystemTime) {
+        self.next_attempt_at = Some(retry_at);
+    }
+
+    pub fn dead_letter(&mut self, error: String) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.next_attempt_at = None;
+        self.dead_lettered_at = Some(SystemTime::now());
+    }
+
+    pub fn is_dead_lettered(&self) -> bool {
+        self.dead_lettered_at.is_some()
+    }
 }
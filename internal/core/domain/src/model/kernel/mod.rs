@@ -1,4 +1,11 @@
+pub mod coordinate_system;
 pub mod event;
+pub mod great_circle;
+#[cfg(test)]
+pub mod great_circle_test;
+pub mod grid;
+#[cfg(test)]
+pub mod grid_test;
 pub mod location;
 #[cfg(test)]
 pub mod location_test;
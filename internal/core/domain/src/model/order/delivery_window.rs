@@ -0,0 +1,71 @@
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::errors::domain_model_errors::DomainModelError;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DeliveryWindow {
+    start: SystemTime,
+    end: SystemTime,
+}
+
+impl DeliveryWindow {
+    pub fn new(start: SystemTime, end: SystemTime) -> Result<Self, DomainModelError> {
+        if end <= start {
+            return Err(DomainModelError::UnmetRequirement(
+                "delivery window must end after it starts".into(),
+            ));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    /// Builds the nearest window from UTC hours of day `from..to`: today's
+    /// window, or tomorrow's when today's one has already closed.
+    pub fn from_hours(now: SystemTime, from: u8, to: u8) -> Result<Self, DomainModelError> {
+        if to > 24 {
+            return Err(DomainModelError::UnmetRequirement(
+                "delivery period cannot end after 24h".into(),
+            ));
+        }
+        if from >= to {
+            return Err(DomainModelError::UnmetRequirement(
+                "delivery period must end after it starts".into(),
+            ));
+        }
+
+        let since_epoch = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| DomainModelError::UnmetRequirement("now is before unix epoch".into()))?
+            .as_secs();
+        let mut midnight = UNIX_EPOCH + Duration::from_secs(since_epoch - since_epoch % DAY);
+        if midnight + Duration::from_secs(to as u64 * HOUR) <= now {
+            midnight += Duration::from_secs(DAY);
+        }
+
+        Self::new(
+            midnight + Duration::from_secs(from as u64 * HOUR),
+            midnight + Duration::from_secs(to as u64 * HOUR),
+        )
+    }
+
+    pub fn start(&self) -> SystemTime {
+        self.start
+    }
+
+    pub fn end(&self) -> SystemTime {
+        self.end
+    }
+
+    pub fn has_opened(&self, now: SystemTime) -> bool {
+        self.start <= now
+    }
+
+    pub fn is_missed(&self, now: SystemTime) -> bool {
+        self.end < now
+    }
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::order::delivery_window::DeliveryWindow;
+
+const HOUR: u64 = 60 * 60;
+
+fn at(day: u64, hour: u64, minute: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(day * 24 * HOUR + hour * HOUR + minute * 60)
+}
+
+#[test]
+fn rejects_window_ending_before_start() {
+    let now = SystemTime::now();
+
+    let result = DeliveryWindow::new(now, now);
+    assert!(matches!(result, Err(DomainModelError::UnmetRequirement(_))));
+}
+
+#[test]
+fn builds_todays_window_from_hours() {
+    let window = DeliveryWindow::from_hours(at(100, 8, 30), 9, 12).unwrap();
+
+    assert_eq!(window.start(), at(100, 9, 0));
+    assert_eq!(window.end(), at(100, 12, 0));
+    assert!(!window.has_opened(at(100, 8, 30)));
+    assert!(window.has_opened(at(100, 9, 0)));
+}
+
+#[test]
+fn builds_tomorrows_window_when_today_is_over() {
+    let window = DeliveryWindow::from_hours(at(100, 13, 0), 9, 12).unwrap();
+
+    assert_eq!(window.start(), at(101, 9, 0));
+    assert_eq!(window.end(), at(101, 12, 0));
+}
+
+#[test]
+fn rejects_invalid_hours() {
+    let now = at(100, 0, 0);
+
+    assert!(DeliveryWindow::from_hours(now, 12, 9).is_err());
+    assert!(DeliveryWindow::from_hours(now, 9, 25).is_err());
+}
+
+#[test]
+fn is_missed_only_after_end() {
+    let window = DeliveryWindow::new(at(100, 9, 0), at(100, 12, 0)).unwrap();
+
+    assert!(!window.is_missed(at(100, 12, 0)));
+    assert!(window.is_missed(at(100, 12, 1)));
+}
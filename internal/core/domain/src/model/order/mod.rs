@@ -1,4 +1,9 @@
+pub mod delivery_window;
+#[cfg(test)]
+pub mod delivery_window_test;
 pub mod order_aggregate;
 #[cfg(test)]
 pub mod order_aggregate_test;
 pub mod order_events;
+#[cfg(test)]
+pub mod order_events_test;
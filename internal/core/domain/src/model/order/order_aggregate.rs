@@ -1,19 +1,23 @@
 use serde::Deserialize;
 use serde::Serialize;
 use std::fmt::Display;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 use crate::errors::domain_model_errors::DomainModelError;
 use crate::model::courier::courier_aggregate::CourierId;
 use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
+use crate::model::order::delivery_window::DeliveryWindow;
 use crate::model::order::order_events::OrderEvent;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum OrderStatus {
     Created,
     Assigned,
+    PickedUp,
     Completed,
+    Cancelled,
 }
 
 impl From<OrderStatus> for String {
@@ -21,7 +25,9 @@ impl From<OrderStatus> for String {
         match value {
             OrderStatus::Created => "created".into(),
             OrderStatus::Assigned => "assigned".into(),
+            OrderStatus::PickedUp => "picked_up".into(),
             OrderStatus::Completed => "completed".into(),
+            OrderStatus::Cancelled => "cancelled".into(),
         }
     }
 }
@@ -31,7 +37,24 @@ impl From<&OrderStatus> for String {
         match value {
             OrderStatus::Created => "created".into(),
             OrderStatus::Assigned => "assigned".into(),
+            OrderStatus::PickedUp => "picked_up".into(),
             OrderStatus::Completed => "completed".into(),
+            OrderStatus::Cancelled => "cancelled".into(),
+        }
+    }
+}
+
+impl TryFrom<&str> for OrderStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "created" => Ok(OrderStatus::Created),
+            "assigned" => Ok(OrderStatus::Assigned),
+            "picked_up" => Ok(OrderStatus::PickedUp),
+            "completed" => Ok(OrderStatus::Completed),
+            "cancelled" => Ok(OrderStatus::Cancelled),
+            other => Err(format!("invalid order status: {}", other)),
         }
     }
 }
@@ -60,9 +83,14 @@ impl OrderId {
 pub struct Order {
     id: OrderId,
     courier_id: Option<CourierId>,
+    pickup_location: Option<Location>,
     location: Location,
     volume: Volume,
     status: OrderStatus,
+    delivery_window: Option<DeliveryWindow>,
+    delivery_window_missed: bool,
+    created_at: SystemTime,
+    version: u64,
 
     domain_events: Vec<OrderEvent>,
 }
@@ -89,33 +117,91 @@ impl Order {
     pub fn new(id: OrderId, location: Location, volume: Volume) -> Result<Self, DomainModelError> {
         let mut order = Self {
             id,
+            pickup_location: None,
             location,
             volume,
             status: OrderStatus::Created,
             courier_id: None,
+            delivery_window: None,
+            delivery_window_missed: false,
+            created_at: SystemTime::now(),
+            version: 0,
             domain_events: Vec::new(),
         };
         order.raise_domain_event(OrderEvent::created(id));
         Ok(order)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn restore(
         id: OrderId,
         courier_id: Option<CourierId>,
+        pickup_location: Option<Location>,
         location: Location,
         volume: Volume,
         status: OrderStatus,
+        delivery_window: Option<DeliveryWindow>,
+        delivery_window_missed: bool,
+        created_at: SystemTime,
+        version: u64,
     ) -> Self {
         Self {
             id,
+            pickup_location,
             location,
             volume,
             status,
             courier_id,
+            delivery_window,
+            delivery_window_missed,
+            created_at,
+            version,
             domain_events: Vec::new(),
         }
     }
 
+    pub fn set_pickup_location(&mut self, location: Location) -> Result<(), DomainModelError> {
+        if self.status != OrderStatus::Created {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "cannot change pickup location, status is already {}",
+                self.status
+            )));
+        }
+        self.pickup_location = Some(location);
+        Ok(())
+    }
+
+    pub fn set_delivery_window(&mut self, window: DeliveryWindow) -> Result<(), DomainModelError> {
+        if self.status != OrderStatus::Created {
+            return Err(DomainModelError::UnmetRequirement(format!(
+                "cannot change delivery window, status is already {}",
+                self.status
+            )));
+        }
+        self.delivery_window = Some(window);
+        self.delivery_window_missed = false;
+        Ok(())
+    }
+
+    /// Flags the order once its window has closed while it is still
+    /// undelivered. Returns whether the flag was raised by this call.
+    pub fn check_delivery_window(&mut self, now: SystemTime) -> bool {
+        let missed = match (&self.status, self.delivery_window) {
+            (
+                OrderStatus::Created | OrderStatus::Assigned | OrderStatus::PickedUp,
+                Some(window),
+            ) => window.is_missed(now),
+            _ => false,
+        };
+        if !missed || self.delivery_window_missed {
+            return false;
+        }
+
+        self.delivery_window_missed = true;
+        self.raise_domain_event(OrderEvent::delivery_window_missed(self.id));
+        true
+    }
+
     pub fn assign(&mut self, courier_id: &CourierId) -> Result<(), DomainModelError> {
         if self.courier_id.is_some() {
             return Err(DomainModelError::ArgumentAlreadyExists(
@@ -123,9 +209,13 @@ impl Order {
             ));
         }
         match self.status {
-            OrderStatus::Assigned | OrderStatus::Completed => Err(
-                DomainModelError::UnmetRequirement(format!("status is already {}", self.status)),
-            ),
+            OrderStatus::Assigned
+            | OrderStatus::PickedUp
+            | OrderStatus::Completed
+            | OrderStatus::Cancelled => Err(DomainModelError::UnmetRequirement(format!(
+                "status is already {}",
+                self.status
+            ))),
             OrderStatus::Created => {
                 self.courier_id = Some(*courier_id);
                 self.status = OrderStatus::Assigned;
@@ -134,6 +224,30 @@ impl Order {
         }
     }
 
+    pub fn pick_up(&mut self) -> Result<(), DomainModelError> {
+        let Some(courier_id) = self.courier_id else {
+            return Err(DomainModelError::UnmetRequirement(
+                "courier_id is not present".to_owned(),
+            ));
+        };
+        if self.pickup_location.is_none() {
+            return Err(DomainModelError::UnmetRequirement(
+                "order has no pickup location".to_owned(),
+            ));
+        }
+        match self.status {
+            OrderStatus::Assigned => {
+                self.status = OrderStatus::PickedUp;
+                self.raise_domain_event(OrderEvent::picked_up(self.id, courier_id));
+                Ok(())
+            }
+            _ => Err(DomainModelError::UnmetRequirement(format!(
+                "cannot pick up order, status is {}",
+                self.status
+            ))),
+        }
+    }
+
     pub fn complete(&mut self) -> Result<(), DomainModelError> {
         match self.courier_id {
             None => Err(DomainModelError::UnmetRequirement(
@@ -143,6 +257,14 @@ impl Order {
                 OrderStatus::Completed => Err(DomainModelError::UnmetRequirement(
                     "status is already set as Completed".to_string(),
                 )),
+                OrderStatus::Cancelled => Err(DomainModelError::UnmetRequirement(
+                    "order is cancelled".to_string(),
+                )),
+                OrderStatus::Created | OrderStatus::Assigned if self.pickup_location.is_some() => {
+                    Err(DomainModelError::UnmetRequirement(
+                        "order has not been picked up".to_string(),
+                    ))
+                }
                 _ => {
                     self.status = OrderStatus::Completed;
                     self.raise_domain_event(OrderEvent::completed(self.id, courier_id));
@@ -152,6 +274,19 @@ impl Order {
         }
     }
 
+    pub fn cancel(&mut self) -> Result<(), DomainModelError> {
+        match self.status {
+            OrderStatus::PickedUp | OrderStatus::Completed | OrderStatus::Cancelled => Err(
+                DomainModelError::UnmetRequirement(format!("status is already {}", self.status)),
+            ),
+            OrderStatus::Created | OrderStatus::Assigned => {
+                self.status = OrderStatus::Cancelled;
+                self.raise_domain_event(OrderEvent::cancelled(self.id, self.courier_id));
+                Ok(())
+            }
+        }
+    }
+
     pub fn id(&self) -> OrderId {
         self.id
     }
@@ -160,6 +295,18 @@ impl Order {
         &self.courier_id
     }
 
+    pub fn pickup_location(&self) -> Option<&Location> {
+        self.pickup_location.as_ref()
+    }
+
+    /// Pickup location the courier still has to visit, if any.
+    pub fn pending_pickup(&self) -> Option<&Location> {
+        match self.status {
+            OrderStatus::Created | OrderStatus::Assigned => self.pickup_location.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn location(&self) -> &Location {
         &self.location
     }
@@ -172,6 +319,23 @@ impl Order {
         &self.status
     }
 
+    pub fn delivery_window(&self) -> Option<DeliveryWindow> {
+        self.delivery_window
+    }
+
+    pub fn is_delivery_window_missed(&self) -> bool {
+        self.delivery_window_missed
+    }
+
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Version the order was loaded at; updates only apply on top of it.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
     pub fn raise_domain_event(&mut self, event: OrderEvent) {
         self.domain_events.push(event);
     }
@@ -1,10 +1,16 @@
+use std::time::Duration;
+use std::time::SystemTime;
 use uuid::Uuid;
 
+use crate::model::courier::courier_aggregate::CourierId;
 use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
+use crate::model::order::delivery_window::DeliveryWindow;
+use crate::model::order::order_events::OrderEvent;
 
 use super::order_aggregate::Order;
 use super::order_aggregate::OrderId;
+use super::order_aggregate::OrderStatus;
 
 #[test]
 fn should_create_order() {
@@ -80,3 +86,126 @@ mod tests {
         order.complete().unwrap();
     }
 }
+
+#[test]
+fn should_cancel_assigned_order() {
+    let location = Location::new(1, 1).unwrap();
+    let volume: Volume = Volume::new(10).unwrap();
+    let mut order = Order::new(OrderId::new(Uuid::new_v4()), location, volume).unwrap();
+    let courier_id = CourierId(Uuid::new_v4());
+    order.assign(&courier_id).unwrap();
+    order.clear_domain_events();
+
+    order.cancel().unwrap();
+
+    assert_eq!(order.status(), &OrderStatus::Cancelled);
+    assert!(matches!(
+        order.get_domain_events().as_slice(),
+        [OrderEvent::Cancelled(e)] if e.courier_id == Some(courier_id)
+    ));
+}
+
+#[test]
+#[should_panic = "status is already completed"]
+fn should_not_cancel_completed_order() {
+    let location = Location::new(1, 1).unwrap();
+    let volume: Volume = Volume::new(10).unwrap();
+    let mut order = Order::new(OrderId::new(Uuid::new_v4()), location, volume).unwrap();
+    order.assign(&CourierId(Uuid::new_v4())).unwrap();
+    order.complete().unwrap();
+
+    order.cancel().unwrap();
+}
+
+#[test]
+#[should_panic = "status is already cancelled"]
+fn should_not_assign_cancelled_order() {
+    let location = Location::new(1, 1).unwrap();
+    let volume: Volume = Volume::new(10).unwrap();
+    let mut order = Order::new(OrderId::new(Uuid::new_v4()), location, volume).unwrap();
+    order.cancel().unwrap();
+
+    order.assign(&CourierId(Uuid::new_v4())).unwrap();
+}
+
+#[test]
+fn should_flag_missed_delivery_window_once() {
+    let location = Location::new(1, 1).unwrap();
+    let volume: Volume = Volume::new(10).unwrap();
+    let mut order = Order::new(OrderId::new(Uuid::new_v4()), location, volume).unwrap();
+    let now = SystemTime::now();
+    let window = DeliveryWindow::new(
+        now - Duration::from_secs(7200),
+        now - Duration::from_secs(60),
+    )
+    .unwrap();
+    order.set_delivery_window(window).unwrap();
+    order.clear_domain_events();
+
+    assert!(order.check_delivery_window(now));
+    assert!(!order.check_delivery_window(now));
+
+    assert!(order.is_delivery_window_missed());
+    assert!(matches!(
+        order.get_domain_events().as_slice(),
+        [OrderEvent::DeliveryWindowMissed(e)] if e.order_id == order.id()
+    ));
+}
+
+#[test]
+fn should_not_flag_open_delivery_window() {
+    let location = Location::new(1, 1).unwrap();
+    let volume: Volume = Volume::new(10).unwrap();
+    let mut order = Order::new(OrderId::new(Uuid::new_v4()), location, volume).unwrap();
+    let now = SystemTime::now();
+    let window = DeliveryWindow::new(now, now + Duration::from_secs(3600)).unwrap();
+    order.set_delivery_window(window).unwrap();
+
+    assert!(!order.check_delivery_window(now));
+    assert!(!order.is_delivery_window_missed());
+}
+
+#[test]
+fn should_pick_up_before_completing() {
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(5, 5).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap();
+    order
+        .set_pickup_location(Location::new(1, 1).unwrap())
+        .unwrap();
+    let courier_id = CourierId(Uuid::new_v4());
+    order.assign(&courier_id).unwrap();
+    order.clear_domain_events();
+
+    assert!(order.complete().is_err());
+    assert_eq!(order.pending_pickup(), Some(&Location::new(1, 1).unwrap()));
+
+    order.pick_up().unwrap();
+    assert_eq!(order.status(), &OrderStatus::PickedUp);
+    assert_eq!(order.pending_pickup(), None);
+    assert!(matches!(
+        order.pop_domain_events().as_slice(),
+        [OrderEvent::PickedUp(e)] if e.courier_id == courier_id
+    ));
+    assert!(order.cancel().is_err());
+
+    order.complete().unwrap();
+    assert_eq!(order.status(), &OrderStatus::Completed);
+}
+
+#[test]
+fn should_not_pick_up_order_without_pickup_location() {
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(5, 5).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap();
+    order.assign(&CourierId(Uuid::new_v4())).unwrap();
+
+    assert!(order.pick_up().is_err());
+    assert!(order.complete().is_ok());
+}
@@ -4,12 +4,16 @@ use serde::Serialize;
 use crate::model::courier::courier_aggregate::CourierId;
 use crate::model::kernel::event::DomainEvent;
 use crate::model::kernel::event::EventId;
+use crate::model::kernel::message::Message;
 use crate::model::order::order_aggregate::OrderId;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum OrderEvent {
     Created(OrderCreatedEvent),
+    PickedUp(OrderPickedUpEvent),
     Completed(OrderCompletedEvent),
+    Cancelled(OrderCancelledEvent),
+    DeliveryWindowMissed(OrderDeliveryWindowMissedEvent),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -19,6 +23,14 @@ pub struct OrderCreatedEvent {
     pub order_id: OrderId,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OrderPickedUpEvent {
+    pub id: EventId,
+    pub name: String,
+    pub order_id: OrderId,
+    pub courier_id: CourierId,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct OrderCompletedEvent {
     pub id: EventId,
@@ -27,23 +39,64 @@ pub struct OrderCompletedEvent {
     pub courier_id: CourierId,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OrderCancelledEvent {
+    pub id: EventId,
+    pub name: String,
+    pub order_id: OrderId,
+    pub courier_id: Option<CourierId>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OrderDeliveryWindowMissedEvent {
+    pub id: EventId,
+    pub name: String,
+    pub order_id: OrderId,
+}
+
 impl DomainEvent for OrderEvent {
     fn id(&self) -> String {
         match self {
             Self::Created(e) => e.id.0.to_string(),
+            Self::PickedUp(e) => e.id.0.to_string(),
             Self::Completed(e) => e.id.0.to_string(),
+            Self::Cancelled(e) => e.id.0.to_string(),
+            Self::DeliveryWindowMissed(e) => e.id.0.to_string(),
         }
     }
 
     fn name(&self) -> String {
         match self {
             Self::Created(e) => e.name.clone(),
+            Self::PickedUp(e) => e.name.clone(),
             Self::Completed(e) => e.name.clone(),
+            Self::Cancelled(e) => e.name.clone(),
+            Self::DeliveryWindowMissed(e) => e.name.clone(),
         }
     }
 }
 
 impl OrderEvent {
+    pub fn event_id(&self) -> EventId {
+        match self {
+            Self::Created(e) => e.id,
+            Self::PickedUp(e) => e.id,
+            Self::Completed(e) => e.id,
+            Self::Cancelled(e) => e.id,
+            Self::DeliveryWindowMissed(e) => e.id,
+        }
+    }
+
+    pub fn order_id(&self) -> OrderId {
+        match self {
+            Self::Created(e) => e.order_id,
+            Self::PickedUp(e) => e.order_id,
+            Self::Completed(e) => e.order_id,
+            Self::Cancelled(e) => e.order_id,
+            Self::DeliveryWindowMissed(e) => e.order_id,
+        }
+    }
+
     pub fn created(order_id: OrderId) -> Self {
         Self::Created(OrderCreatedEvent {
             id: EventId::default(),
@@ -52,6 +105,15 @@ impl OrderEvent {
         })
     }
 
+    pub fn picked_up(order_id: OrderId, courier_id: CourierId) -> Self {
+        Self::PickedUp(OrderPickedUpEvent {
+            id: EventId::default(),
+            name: "picked_up".to_string(),
+            order_id,
+            courier_id,
+        })
+    }
+
     pub fn completed(order_id: OrderId, courier_id: CourierId) -> Self {
         Self::Completed(OrderCompletedEvent {
             id: EventId::default(),
@@ -60,4 +122,35 @@ impl OrderEvent {
             courier_id,
         })
     }
+
+    pub fn cancelled(order_id: OrderId, courier_id: Option<CourierId>) -> Self {
+        Self::Cancelled(OrderCancelledEvent {
+            id: EventId::default(),
+            name: "cancelled".to_string(),
+            order_id,
+            courier_id,
+        })
+    }
+
+    pub fn delivery_window_missed(order_id: OrderId) -> Self {
+        Self::DeliveryWindowMissed(OrderDeliveryWindowMissedEvent {
+            id: EventId::default(),
+            name: "delivery_window_missed".to_string(),
+            order_id,
+        })
+    }
+}
+
+/// Outbox messages reuse the event id, so consumers can dedupe republished events.
+impl TryFrom<&OrderEvent> for Message {
+    type Error = String;
+
+    fn try_from(event: &OrderEvent) -> Result<Self, Self::Error> {
+        let payload = serde_json::to_string(event)
+            .map_err(|err| format!("could not serialize event: {}", err))?;
+        let mut message = Message::new(event.name(), payload);
+        message.id = event.event_id().0;
+
+        Ok(message)
+    }
 }
@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::model::courier::courier_aggregate::CourierId;
+use crate::model::kernel::message::Message;
+use crate::model::order::order_aggregate::OrderId;
+use crate::model::order::order_events::OrderEvent;
+
+#[test]
+fn outbox_message_keeps_event_id_and_name() {
+    let event = OrderEvent::completed(OrderId::new(Uuid::new_v4()), CourierId(Uuid::new_v4()));
+
+    let message = Message::try_from(&event).unwrap();
+
+    assert_eq!(message.id, event.event_id().0);
+    assert_eq!(message.name, "completed");
+    assert!(message.processed_at.is_none());
+}
+
+#[test]
+fn outbox_message_payload_restores_event() {
+    let order_id = OrderId::new(Uuid::new_v4());
+    let event = OrderEvent::cancelled(order_id, None);
+
+    let message = Message::try_from(&event).unwrap();
+    let restored: OrderEvent = serde_json::from_str(&message.payload).unwrap();
+
+    match restored {
+        OrderEvent::Cancelled(e) => {
+            assert_eq!(e.id, event.event_id());
+            assert_eq!(e.order_id, order_id);
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+}
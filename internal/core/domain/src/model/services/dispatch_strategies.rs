@@ -0,0 +1,130 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::model::courier::city_map::CityMap;
+use crate::model::courier::courier_aggregate::Courier;
+use crate::model::kernel::volume::Volume;
+use crate::model::order::order_aggregate::Order;
+use crate::model::services::order_dispatcher::OrderDispatcher;
+use crate::model::services::order_dispatcher::detour_time;
+
+/// Courier with the fewest occupied storage places.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LeastLoadedDispatcher;
+
+impl OrderDispatcher for LeastLoadedDispatcher {
+    fn select_courier(
+        &self,
+        order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        map: &CityMap,
+    ) -> Option<usize> {
+        candidates.iter().copied().min_by_key(|&idx| {
+            let courier = &couriers[idx];
+            (courier.occupied_places(), detour_time(courier, order, map))
+        })
+    }
+}
+
+/// Takes turns among the couriers able to take the order, in courier id
+/// order so the rotation does not depend on how they were loaded.
+#[derive(Debug, Default)]
+pub struct RoundRobinDispatcher {
+    next: AtomicUsize,
+}
+
+impl RoundRobinDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OrderDispatcher for RoundRobinDispatcher {
+    fn select_courier(
+        &self,
+        _order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        _map: &CityMap,
+    ) -> Option<usize> {
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let mut sorted = candidates.to_vec();
+        sorted.sort_by_key(|&idx| couriers[idx].id().0);
+        let turn = self.next.fetch_add(1, Ordering::Relaxed);
+
+        Some(sorted[turn % sorted.len()])
+    }
+}
+
+/// Courier whose storage place fits the order with the least volume to spare.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BestVolumeFitDispatcher;
+
+impl OrderDispatcher for BestVolumeFitDispatcher {
+    fn select_courier(
+        &self,
+        order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        map: &CityMap,
+    ) -> Option<usize> {
+        let volume = Volume::new(order.volume()).ok()?;
+
+        candidates.iter().copied().min_by_key(|&idx| {
+            let courier = &couriers[idx];
+            (
+                courier.spare_volume_for(&volume).unwrap_or(u16::MAX),
+                detour_time(courier, order, map),
+            )
+        })
+    }
+}
+
+/// Lowest weighted sum of detour time, occupied storage places and spare
+/// volume.
+#[derive(Clone, Copy, Debug)]
+pub struct WeightedScoreDispatcher {
+    distance_weight: f64,
+    load_weight: f64,
+    fit_weight: f64,
+}
+
+impl WeightedScoreDispatcher {
+    pub fn new(distance_weight: f64, load_weight: f64, fit_weight: f64) -> Self {
+        Self {
+            distance_weight,
+            load_weight,
+            fit_weight,
+        }
+    }
+
+    fn score(&self, courier: &Courier, order: &Order, volume: &Volume, map: &CityMap) -> f64 {
+        let spare = courier.spare_volume_for(volume).unwrap_or(u16::MAX);
+
+        self.distance_weight * detour_time(courier, order, map) as f64
+            + self.load_weight * courier.occupied_places() as f64
+            + self.fit_weight * spare as f64
+    }
+}
+
+impl OrderDispatcher for WeightedScoreDispatcher {
+    fn select_courier(
+        &self,
+        order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        map: &CityMap,
+    ) -> Option<usize> {
+        let volume = Volume::new(order.volume()).ok()?;
+
+        candidates
+            .iter()
+            .map(|&idx| (idx, self.score(&couriers[idx], order, &volume, map)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(idx, _)| idx)
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::SystemTime;
+use uuid::Uuid;
+
+use crate::model::courier::city_map::CityMap;
+use crate::model::courier::courier_aggregate::Courier;
+use crate::model::courier::courier_aggregate::CourierName;
+use crate::model::courier::courier_aggregate::CourierSpeed;
+use crate::model::kernel::location::Location;
+use crate::model::kernel::volume::Volume;
+use crate::model::order::order_aggregate::Order;
+use crate::model::order::order_aggregate::OrderId;
+use crate::model::services::dispatch_strategies::BestVolumeFitDispatcher;
+use crate::model::services::dispatch_strategies::LeastLoadedDispatcher;
+use crate::model::services::dispatch_strategies::RoundRobinDispatcher;
+use crate::model::services::dispatch_strategies::WeightedScoreDispatcher;
+use crate::model::services::order_dispatcher::OrderDispatcher;
+
+fn order_at(x: u16, y: u16, volume: u16) -> Order {
+    Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(x, y).unwrap(),
+        Volume::new(volume).unwrap(),
+    )
+    .unwrap()
+}
+
+fn courier(name: &str, x: u16, y: u16) -> Courier {
+    Courier::new(
+        CourierName(name.into()),
+        CourierSpeed(1),
+        Location::new(x, y).unwrap(),
+    )
+    .unwrap()
+}
+
+/// A courier with a spare bag, already carrying one order next to it.
+fn busy_courier(name: &str, x: u16, y: u16) -> Courier {
+    let mut courier = courier(name, x, y);
+    courier
+        .add_storage_place("trunk".into(), Volume::new(50).unwrap())
+        .unwrap();
+    courier
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(x, y).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    courier
+}
+
+fn dispatched_to(dispatcher: &impl OrderDispatcher, couriers: &mut [Courier]) -> String {
+    let mut orders = vec![order_at(1, 1, 5)];
+    let (_, courier) = dispatcher
+        .dispatch(
+            &mut orders,
+            couriers,
+            SystemTime::now(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    courier.name().clone()
+}
+
+#[test]
+fn least_loaded_prefers_idle_courier() {
+    let mut couriers = vec![busy_courier("Bob", 1, 1), courier("Rick", 3, 3)];
+
+    assert_eq!(dispatched_to(&LeastLoadedDispatcher, &mut couriers), "Rick");
+}
+
+#[test]
+fn round_robin_takes_turns() {
+    let dispatcher = RoundRobinDispatcher::new();
+    let mut couriers = vec![courier("Bob", 9, 9), courier("Rick", 1, 1)];
+
+    let first = dispatched_to(&dispatcher, &mut couriers.clone());
+    let second = dispatched_to(&dispatcher, &mut couriers);
+
+    assert_ne!(first, second);
+}
+
+#[test]
+fn best_volume_fit_prefers_tightest_storage_place() {
+    let mut roomy = courier("Bob", 1, 1);
+    roomy
+        .add_storage_place("trunk".into(), Volume::new(40).unwrap())
+        .unwrap();
+    let mut tight = courier("Rick", 9, 9);
+    tight
+        .add_storage_place("box".into(), Volume::new(6).unwrap())
+        .unwrap();
+    let mut couriers = vec![roomy, tight];
+
+    assert_eq!(
+        dispatched_to(&BestVolumeFitDispatcher, &mut couriers),
+        "Rick"
+    );
+}
+
+#[test]
+fn weighted_score_follows_weights() {
+    let mut couriers = vec![busy_courier("Bob", 1, 1), courier("Rick", 5, 5)];
+    assert_eq!(
+        dispatched_to(&WeightedScoreDispatcher::new(1.0, 0.0, 0.0), &mut couriers),
+        "Bob"
+    );
+
+    let mut couriers = vec![busy_courier("Bob", 1, 1), courier("Rick", 5, 5)];
+    assert_eq!(
+        dispatched_to(
+            &WeightedScoreDispatcher::new(1.0, 100.0, 0.0),
+            &mut couriers
+        ),
+        "Rick"
+    );
+}
@@ -0,0 +1,69 @@
+/// Minimum-cost assignment for a square `cost` matrix (Hungarian algorithm,
+/// O(n³)). Returns the column assigned to every row.
+pub fn solve(cost: &[Vec<i64>]) -> Vec<usize> {
+    let n = cost.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Potentials and matching are 1-based; index 0 is the virtual root.
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; n + 1];
+    let mut row_of = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        row_of[0] = row;
+        let mut col = 0;
+        let mut min_to = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+
+        loop {
+            used[col] = true;
+            let current = row_of[col];
+            let mut delta = i64::MAX;
+            let mut next = 0;
+
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost[current - 1][j - 1] - u[current] - v[j];
+                if reduced < min_to[j] {
+                    min_to[j] = reduced;
+                    way[j] = col;
+                }
+                if min_to[j] < delta {
+                    delta = min_to[j];
+                    next = j;
+                }
+            }
+
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_to[j] -= delta;
+                }
+            }
+
+            col = next;
+            if row_of[col] == 0 {
+                break;
+            }
+        }
+
+        while col != 0 {
+            let previous = way[col];
+            row_of[col] = row_of[previous];
+            col = previous;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for col in 1..=n {
+        assignment[row_of[col] - 1] = col - 1;
+    }
+    assignment
+}
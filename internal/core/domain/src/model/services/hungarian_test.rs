@@ -0,0 +1,21 @@
+use crate::model::services::hungarian::solve;
+
+#[test]
+fn finds_minimum_cost_assignment() {
+    let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+
+    let assignment = solve(&cost);
+
+    assert_eq!(assignment, vec![1, 0, 2]);
+    let total: i64 = assignment
+        .iter()
+        .enumerate()
+        .map(|(row, &col)| cost[row][col])
+        .sum();
+    assert_eq!(total, 5);
+}
+
+#[test]
+fn handles_empty_matrix() {
+    assert!(solve(&[]).is_empty());
+}
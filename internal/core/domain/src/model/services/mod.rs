@@ -1,3 +1,12 @@
+pub mod dispatch_strategies;
+#[cfg(test)]
+mod dispatch_strategies_test;
+mod hungarian;
+#[cfg(test)]
+mod hungarian_test;
+pub mod optimal_assignment;
+#[cfg(test)]
+mod optimal_assignment_test;
 pub mod order_dispatcher;
 #[cfg(test)]
 mod order_dispatcher_test;
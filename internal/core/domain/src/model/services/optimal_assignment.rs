@@ -0,0 +1,109 @@
+use std::time::SystemTime;
+
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::city_map::CityMap;
+use crate::model::courier::courier_aggregate::Courier;
+use crate::model::kernel::volume::Volume;
+use crate::model::order::order_aggregate::Order;
+use crate::model::services::hungarian;
+use crate::model::services::order_dispatcher::max_route_detour;
+use crate::model::services::order_dispatcher::prioritize;
+
+/// Cost of a pairing that must not be picked, far above any real detour.
+const UNASSIGNABLE: i64 = 1 << 40;
+
+pub struct OptimalAssignmentService;
+
+impl OptimalAssignmentService {
+    /// Assigns as many ready orders as there are free storage places, keeping
+    /// the total extra distance travelled by couriers minimal. When orders
+    /// outnumber places the most urgent ones are matched. Returns the
+    /// `(order, courier)` index pairs that were assigned.
+    pub fn assign(
+        orders: &mut [Order],
+        couriers: &mut [Courier],
+        now: SystemTime,
+        map: &CityMap,
+    ) -> Result<Vec<(usize, usize)>, DomainModelError> {
+        let slots: Vec<(usize, u16)> = couriers
+            .iter()
+            .enumerate()
+            .filter(|(_, courier)| courier.is_dispatchable(now))
+            .flat_map(|(courier_idx, courier)| {
+                courier
+                    .storage_places()
+                    .iter()
+                    .filter(|sp| sp.order_id().is_none())
+                    .map(move |sp| (courier_idx, sp.total_volume()))
+            })
+            .collect();
+
+        let mut ready = prioritize(orders, now);
+        ready.truncate(slots.len());
+        if ready.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let size = slots.len();
+        let cost: Vec<Vec<i64>> = (0..size)
+            .map(|row| match ready.get(row) {
+                Some(&order_idx) => {
+                    let order = &orders[order_idx];
+                    // Routes are searched once per courier, not for each of its places.
+                    let mut detours: Vec<Option<i64>> = vec![None; couriers.len()];
+                    slots
+                        .iter()
+                        .map(|&(courier_idx, capacity)| {
+                            if order.volume() > capacity {
+                                return UNASSIGNABLE;
+                            }
+                            *detours[courier_idx].get_or_insert_with(|| {
+                                Self::detour_cost(order, &couriers[courier_idx], map)
+                            })
+                        })
+                        .collect()
+                }
+                None => vec![0; size],
+            })
+            .collect();
+
+        let mut pairs: Vec<(usize, usize)> = hungarian::solve(&cost)
+            .into_iter()
+            .enumerate()
+            .take(ready.len())
+            .filter(|&(row, col)| cost[row][col] < UNASSIGNABLE)
+            .map(|(row, col)| (ready[row], slots[col].0))
+            .collect();
+
+        // Largest orders first, so each lands in the smallest place that still
+        // fits it and no place the matching counted on is taken by a smaller one.
+        pairs.sort_by_key(|&(order_idx, _)| std::cmp::Reverse(orders[order_idx].volume()));
+
+        for &(order_idx, courier_idx) in &pairs {
+            let order = &mut orders[order_idx];
+            let courier = &mut couriers[courier_idx];
+
+            courier.take_order(
+                order.id(),
+                Volume::new(order.volume())?,
+                order.pending_pickup(),
+                order.location(),
+                map,
+            )?;
+            order.assign(courier.id())?;
+        }
+
+        Ok(pairs)
+    }
+
+    fn detour_cost(order: &Order, courier: &Courier, map: &CityMap) -> i64 {
+        let detour = courier.route_detour(order.pending_pickup(), order.location(), map);
+        if detour == u32::MAX
+            || courier.is_en_route() && detour > max_route_detour(order.location())
+        {
+            return UNASSIGNABLE;
+        }
+
+        detour as i64
+    }
+}
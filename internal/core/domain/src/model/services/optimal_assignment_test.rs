@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::city_map::CityMap;
+use crate::model::courier::courier_aggregate::Courier;
+use crate::model::courier::courier_aggregate::CourierName;
+use crate::model::courier::courier_aggregate::CourierSpeed;
+use crate::model::courier::movement::AxisStepping;
+use crate::model::courier::movement::MovementModel;
+use crate::model::kernel::coordinate_system::CoordinateSystem;
+use crate::model::kernel::location::Location;
+use crate::model::kernel::volume::Volume;
+use crate::model::order::order_aggregate::Order;
+use crate::model::order::order_aggregate::OrderId;
+use crate::model::order::order_aggregate::OrderStatus;
+use crate::model::services::optimal_assignment::OptimalAssignmentService;
+use crate::model::services::order_dispatcher::OrderDispatcher;
+use crate::model::services::order_dispatcher::OrderDispatcherService;
+
+fn order_at(x: u16, y: u16, volume: u16) -> Order {
+    Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(x, y).unwrap(),
+        Volume::new(volume).unwrap(),
+    )
+    .unwrap()
+}
+
+fn courier_at(name: &str, x: u16, y: u16) -> Courier {
+    Courier::new(
+        CourierName(name.into()),
+        CourierSpeed(1),
+        Location::new(x, y).unwrap(),
+    )
+    .unwrap()
+}
+
+/// Axis stepping that counts how many distances were asked for.
+#[derive(Default)]
+struct CountingMovement {
+    distances: AtomicUsize,
+}
+
+impl MovementModel for CountingMovement {
+    fn step(
+        &self,
+        from: &Location,
+        to: &Location,
+        speed: u8,
+    ) -> Result<Location, DomainModelError> {
+        AxisStepping.step(from, to, speed)
+    }
+
+    fn distance(&self, from: &Location, to: &Location) -> u32 {
+        self.distances.fetch_add(1, Ordering::SeqCst);
+        AxisStepping.distance(from, to)
+    }
+}
+
+/// Distances computed to assign one order to a busy courier with `places`
+/// free storage places.
+fn distances_for_free_places(places: usize) -> usize {
+    let movement = Arc::new(CountingMovement::default());
+    let map = CityMap::new(CoordinateSystem::default(), movement.clone());
+    let mut courier = courier_at("Bob", 1, 1);
+    for place in 1..places {
+        courier
+            .add_storage_place(format!("bag {}", place), Volume::new(10).unwrap())
+            .unwrap();
+    }
+    courier
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(4, 1).unwrap(),
+            &map,
+        )
+        .unwrap();
+    let mut orders = vec![order_at(2, 1, 1)];
+    let mut couriers = vec![courier];
+    movement.distances.store(0, Ordering::SeqCst);
+
+    OptimalAssignmentService::assign(&mut orders, &mut couriers, SystemTime::now(), &map).unwrap();
+
+    movement.distances.load(Ordering::SeqCst)
+}
+
+fn total_distance(orders: &[Order], couriers: &[Courier], pairs: &[(usize, usize)]) -> u32 {
+    pairs
+        .iter()
+        .map(|&(o, c)| couriers[c].location().get_distance(orders[o].location()))
+        .sum()
+}
+
+#[test]
+fn beats_greedy_total_travel() {
+    let mut orders = vec![order_at(3, 1, 1), order_at(1, 1, 1)];
+    let mut couriers = vec![courier_at("Bob", 1, 1), courier_at("Rick", 5, 1)];
+
+    let pairs = OptimalAssignmentService::assign(
+        &mut orders,
+        &mut couriers,
+        SystemTime::now(),
+        &CityMap::default(),
+    )
+    .unwrap();
+
+    assert_eq!(pairs.len(), 2);
+    assert!(orders.iter().all(|o| o.status() == &OrderStatus::Assigned));
+    let initial = vec![courier_at("Bob", 1, 1), courier_at("Rick", 5, 1)];
+    assert_eq!(total_distance(&orders, &initial, &pairs), 2);
+
+    let mut greedy_orders = vec![order_at(3, 1, 1), order_at(1, 1, 1)];
+    let mut greedy_couriers = initial.clone();
+    let mut greedy_pairs = Vec::new();
+    for order_idx in 0..greedy_orders.len() {
+        let (order, courier) = OrderDispatcherService
+            .dispatch(
+                &mut greedy_orders[order_idx..=order_idx],
+                &mut greedy_couriers,
+                SystemTime::now(),
+                &CityMap::default(),
+            )
+            .unwrap();
+        let courier_idx = initial.iter().position(|c| c.id() == courier.id()).unwrap();
+        greedy_pairs.push((order_idx, courier_idx));
+        assert_eq!(order.status(), &OrderStatus::Assigned);
+    }
+    assert!(total_distance(&greedy_orders, &initial, &greedy_pairs) > 2);
+}
+
+#[test]
+fn respects_storage_place_volume() {
+    let mut orders = vec![order_at(1, 1, 30), order_at(1, 1, 5)];
+    let mut small = courier_at("Bob", 1, 1);
+    small
+        .add_storage_place("box".into(), Volume::new(5).unwrap())
+        .unwrap();
+    let mut couriers = vec![small, courier_at("Rick", 9, 9)];
+
+    let pairs = OptimalAssignmentService::assign(
+        &mut orders,
+        &mut couriers,
+        SystemTime::now(),
+        &CityMap::default(),
+    )
+    .unwrap();
+
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(couriers[0].occupied_places(), 2);
+    assert_eq!(orders[0].courier_id(), &Some(*couriers[0].id()));
+    assert_eq!(orders[1].courier_id(), &Some(*couriers[0].id()));
+}
+
+#[test]
+fn leaves_orders_without_free_place_unassigned() {
+    let mut orders = vec![order_at(1, 1, 1), order_at(2, 2, 1), order_at(3, 3, 1)];
+    let mut couriers = vec![courier_at("Bob", 1, 1)];
+
+    let pairs = OptimalAssignmentService::assign(
+        &mut orders,
+        &mut couriers,
+        SystemTime::now(),
+        &CityMap::default(),
+    )
+    .unwrap();
+
+    assert_eq!(pairs, vec![(0, 0)]);
+    assert_eq!(orders[1].status(), &OrderStatus::Created);
+    assert_eq!(orders[2].status(), &OrderStatus::Created);
+}
+
+#[test]
+fn searches_routes_once_per_courier_not_per_place() {
+    assert_eq!(distances_for_free_places(4), distances_for_free_places(2));
+}
@@ -1,46 +1,142 @@
+use std::time::SystemTime;
+
 use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::city_map::CityMap;
 use crate::model::courier::courier_aggregate::Courier;
+use crate::model::kernel::location::Coordinates;
+use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
 use crate::model::order::order_aggregate::Order;
 use crate::model::order::order_aggregate::OrderStatus;
 
 pub trait OrderDispatcher {
-    fn dispatch<'a>(
-        order: &mut Order,
-        couriers: &'a mut Vec<Courier>,
-    ) -> Result<&'a mut Courier, DomainModelError>;
-}
-
-pub struct OrderDispatcherService;
+    /// Picks the courier for `order` among `candidates`, indexes of couriers
+    /// able to take it. Returns an index into `couriers`.
+    fn select_courier(
+        &self,
+        order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        map: &CityMap,
+    ) -> Option<usize>;
 
-impl OrderDispatcher for OrderDispatcherService {
-    fn dispatch<'a>(
-        order: &mut Order,
-        couriers: &'a mut Vec<Courier>,
-    ) -> Result<&'a mut Courier, DomainModelError> {
-        if order.status() != &OrderStatus::Created {
+    fn dispatch<'o, 'c>(
+        &self,
+        orders: &'o mut [Order],
+        couriers: &'c mut [Courier],
+        now: SystemTime,
+        map: &CityMap,
+    ) -> Result<(&'o mut Order, &'c mut Courier), DomainModelError> {
+        let candidates = prioritize(orders, now);
+        if candidates.is_empty() {
             return Err(DomainModelError::UnmetRequirement(
-                "status it not 'created'".into(),
+                "no order with status 'created' is ready for dispatch".into(),
             ));
         }
 
-        let order_volume = Volume::new(order.volume())
-            .map_err(|_| DomainModelError::UnmetRequirement("invalid order volume".into()))?;
+        let (order_idx, courier_idx, order_volume) = candidates
+            .into_iter()
+            .find_map(|order_idx| {
+                let order = &orders[order_idx];
+                let order_volume = Volume::new(order.volume()).ok()?;
+                let eligible = eligible_couriers(order, &order_volume, couriers, now, map);
 
-        let (idx, _) = couriers
-            .iter_mut()
-            .enumerate()
-            .filter(|(_, c)| c.can_take_order(&order_volume).is_some())
-            .min_by_key(|(_, c)| c.get_traverse_length(order.location()))
+                self.select_courier(order, couriers, &eligible, map)
+                    .map(|courier_idx| (order_idx, courier_idx, order_volume))
+            })
             .ok_or(DomainModelError::UnmetRequirement(
                 "no available courier found".into(),
             ))?;
 
-        let courier = &mut couriers[idx];
+        let order = &mut orders[order_idx];
+        let courier = &mut couriers[courier_idx];
 
-        courier.take_order(order.id(), order_volume)?;
+        courier.take_order(
+            order.id(),
+            order_volume,
+            order.pending_pickup(),
+            order.location(),
+            map,
+        )?;
         order.assign(courier.id())?;
 
-        Ok(courier)
+        Ok((order, courier))
+    }
+}
+
+/// Largest extra distance an en-route courier's route may grow by to pick up
+/// one more order.
+pub const MAX_ROUTE_DETOUR: u32 = 4;
+/// Same allowance between geographic locations.
+pub const MAX_ROUTE_DETOUR_METERS: u32 = 2_000;
+
+pub fn max_route_detour(destination: &Location) -> u32 {
+    match destination.coordinates() {
+        Coordinates::Grid { .. } => MAX_ROUTE_DETOUR,
+        Coordinates::Geographic { .. } => MAX_ROUTE_DETOUR_METERS,
+    }
+}
+
+/// Orders ready for dispatch, soonest closing delivery window first.
+/// Orders without a window keep their relative order after windowed ones.
+pub(crate) fn prioritize(orders: &[Order], now: SystemTime) -> Vec<usize> {
+    let mut candidates: Vec<usize> = orders
+        .iter()
+        .enumerate()
+        .filter(|(_, o)| o.status() == &OrderStatus::Created)
+        .filter(|(_, o)| o.delivery_window().is_none_or(|w| w.has_opened(now)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    candidates.sort_by_key(|&idx| {
+        let window = orders[idx].delivery_window();
+        (window.is_none(), window.map(|w| w.end()))
+    });
+
+    candidates
+}
+
+pub(crate) fn eligible_couriers(
+    order: &Order,
+    order_volume: &Volume,
+    couriers: &[Courier],
+    now: SystemTime,
+    map: &CityMap,
+) -> Vec<usize> {
+    couriers
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_dispatchable(now))
+        .filter(|(_, c)| c.can_take_order(order_volume).is_some())
+        .filter(|(_, c)| {
+            !c.is_en_route()
+                || c.route_detour(order.pending_pickup(), order.location(), map)
+                    <= max_route_detour(order.location())
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Time the courier needs to take `order` on, used to break ties.
+pub fn detour_time(courier: &Courier, order: &Order, map: &CityMap) -> u32 {
+    courier.get_detour_length(order.pending_pickup(), order.location(), map)
+}
+
+/// Nearest courier: the one reaching the order soonest.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderDispatcherService;
+
+impl OrderDispatcher for OrderDispatcherService {
+    fn select_courier(
+        &self,
+        order: &Order,
+        couriers: &[Courier],
+        candidates: &[usize],
+        map: &CityMap,
+    ) -> Option<usize> {
+        candidates
+            .iter()
+            .copied()
+            .min_by_key(|&idx| detour_time(&couriers[idx], order, map))
     }
 }
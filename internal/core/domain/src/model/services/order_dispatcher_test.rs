@@ -1,12 +1,18 @@
+use std::slice::from_mut;
+use std::time::Duration;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 use crate::errors::domain_model_errors::DomainModelError;
+use crate::model::courier::city_map::CityMap;
 use crate::model::courier::courier_aggregate::Courier;
 use crate::model::courier::courier_aggregate::CourierId;
 use crate::model::courier::courier_aggregate::CourierName;
 use crate::model::courier::courier_aggregate::CourierSpeed;
+use crate::model::courier::shift::Shift;
 use crate::model::kernel::location::Location;
 use crate::model::kernel::volume::Volume;
+use crate::model::order::delivery_window::DeliveryWindow;
 use crate::model::order::order_aggregate::Order;
 use crate::model::order::order_aggregate::OrderId;
 use crate::model::services::order_dispatcher::OrderDispatcher;
@@ -32,7 +38,12 @@ fn fails_when_order_not_created() {
     let _ = order.assign(&CourierId(Uuid::new_v4()));
     let _ = order.complete();
 
-    let result = OrderDispatcherService::dispatch(&mut order, &mut couriers);
+    let result = OrderDispatcherService.dispatch(
+        from_mut(&mut order),
+        &mut couriers,
+        SystemTime::now(),
+        &CityMap::default(),
+    );
     assert!(matches!(result, Err(DomainModelError::UnmetRequirement(_))));
 }
 
@@ -51,7 +62,13 @@ fn fails_when_no_available_couriers() {
         Location::new(9, 9).unwrap(),
     )
     .unwrap();
-    let _ = courier_bob.take_order(order.id(), Volume::new(2).unwrap());
+    let _ = courier_bob.take_order(
+        order.id(),
+        Volume::new(2).unwrap(),
+        None,
+        order.location(),
+        &CityMap::default(),
+    );
 
     let mut courier_rick = Courier::new(
         CourierName("Rick".into()),
@@ -59,11 +76,22 @@ fn fails_when_no_available_couriers() {
         Location::new(5, 5).unwrap(),
     )
     .unwrap();
-    let _ = courier_rick.take_order(order.id(), Volume::new(2).unwrap());
+    let _ = courier_rick.take_order(
+        order.id(),
+        Volume::new(2).unwrap(),
+        None,
+        order.location(),
+        &CityMap::default(),
+    );
 
     let mut couriers = vec![];
 
-    let result = OrderDispatcherService::dispatch(&mut order, &mut couriers);
+    let result = OrderDispatcherService.dispatch(
+        from_mut(&mut order),
+        &mut couriers,
+        SystemTime::now(),
+        &CityMap::default(),
+    );
     assert!(matches!(result, Err(DomainModelError::UnmetRequirement(_))));
 }
 
@@ -95,6 +123,227 @@ fn picks_fastest_available_courier() {
     .unwrap();
     let mut couriers = vec![courier_bob, courier_rick, courier_zack];
 
-    let result = OrderDispatcherService::dispatch(&mut order, &mut couriers).unwrap();
-    assert_eq!(result.name(), "Zack");
+    let result = OrderDispatcherService
+        .dispatch(
+            from_mut(&mut order),
+            &mut couriers,
+            SystemTime::now(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert_eq!(result.1.name(), "Zack");
+}
+
+fn windowed_order(start: SystemTime, end: SystemTime) -> Order {
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 1).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap();
+    order
+        .set_delivery_window(DeliveryWindow::new(start, end).unwrap())
+        .unwrap();
+    order
+}
+
+fn single_courier() -> Vec<Courier> {
+    vec![
+        Courier::new(
+            CourierName("Bob".into()),
+            CourierSpeed(1),
+            Location::new(9, 9).unwrap(),
+        )
+        .unwrap(),
+    ]
+}
+
+#[test]
+fn prioritizes_closing_delivery_window() {
+    let now = SystemTime::now();
+    let no_window = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 1).unwrap(),
+        Volume::new(1).unwrap(),
+    )
+    .unwrap();
+    let closes_later = windowed_order(
+        now - Duration::from_secs(3600),
+        now + Duration::from_secs(7200),
+    );
+    let closes_soon = windowed_order(
+        now - Duration::from_secs(3600),
+        now + Duration::from_secs(600),
+    );
+    let soon_id = closes_soon.id();
+    let mut orders = vec![no_window, closes_later, closes_soon];
+    let mut couriers = single_courier();
+
+    let (order, _) = OrderDispatcherService
+        .dispatch(&mut orders, &mut couriers, now, &CityMap::default())
+        .unwrap();
+    assert_eq!(order.id(), soon_id);
+}
+
+#[test]
+fn skips_delivery_window_not_yet_open() {
+    let now = SystemTime::now();
+    let not_open = windowed_order(
+        now + Duration::from_secs(3600),
+        now + Duration::from_secs(7200),
+    );
+    let mut orders = vec![not_open];
+    let mut couriers = single_courier();
+
+    let result =
+        OrderDispatcherService.dispatch(&mut orders, &mut couriers, now, &CityMap::default());
+    assert!(matches!(result, Err(DomainModelError::UnmetRequirement(_))));
+
+    let open = windowed_order(
+        now - Duration::from_secs(60),
+        now + Duration::from_secs(7200),
+    );
+    let open_id = open.id();
+    orders.push(open);
+
+    let (order, _) = OrderDispatcherService
+        .dispatch(&mut orders, &mut couriers, now, &CityMap::default())
+        .unwrap();
+    assert_eq!(order.id(), open_id);
+}
+
+#[test]
+fn adds_order_to_en_route_courier_on_small_detour() {
+    let now = SystemTime::now();
+    let mut en_route = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(1),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    en_route
+        .add_storage_place("trunk".into(), Volume::new(10).unwrap())
+        .unwrap();
+    en_route
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    let idle = Courier::new(
+        CourierName("Rick".into()),
+        CourierSpeed(1),
+        Location::new(5, 5).unwrap(),
+    )
+    .unwrap();
+    let mut couriers = vec![en_route, idle];
+
+    let mut orders = vec![
+        Order::new(
+            OrderId::new(Uuid::new_v4()),
+            Location::new(2, 5).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap(),
+    ];
+
+    let (_, courier) = OrderDispatcherService
+        .dispatch(&mut orders, &mut couriers, now, &CityMap::default())
+        .unwrap();
+    assert_eq!(courier.name(), "Bob");
+    assert_eq!(courier.route().len(), 2);
+    assert_eq!(courier.next_stop().unwrap().order_id(), orders[0].id());
+}
+
+#[test]
+fn skips_en_route_courier_on_large_detour() {
+    let now = SystemTime::now();
+    let mut en_route = Courier::new(
+        CourierName("Bob".into()),
+        CourierSpeed(3),
+        Location::new(1, 1).unwrap(),
+    )
+    .unwrap();
+    en_route
+        .add_storage_place("trunk".into(), Volume::new(10).unwrap())
+        .unwrap();
+    en_route
+        .take_order(
+            OrderId::new(Uuid::new_v4()),
+            Volume::new(1).unwrap(),
+            None,
+            &Location::new(1, 9).unwrap(),
+            &CityMap::default(),
+        )
+        .unwrap();
+    let idle = Courier::new(
+        CourierName("Rick".into()),
+        CourierSpeed(1),
+        Location::new(10, 10).unwrap(),
+    )
+    .unwrap();
+    let mut couriers = vec![en_route, idle];
+
+    let mut orders = vec![
+        Order::new(
+            OrderId::new(Uuid::new_v4()),
+            Location::new(9, 1).unwrap(),
+            Volume::new(1).unwrap(),
+        )
+        .unwrap(),
+    ];
+
+    let (_, courier) = OrderDispatcherService
+        .dispatch(&mut orders, &mut couriers, now, &CityMap::default())
+        .unwrap();
+    assert_eq!(courier.name(), "Rick");
+}
+
+#[test]
+fn skips_couriers_off_duty_on_break_or_out_of_shift() {
+    let now = SystemTime::now();
+    let make_courier = |name: &str, speed: u8| {
+        Courier::new(
+            CourierName(name.into()),
+            CourierSpeed(speed),
+            Location::new(9, 9).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let mut off_duty = make_courier("Bob", 9);
+    off_duty.end_shift().unwrap();
+    let mut on_break = make_courier("Rick", 8);
+    on_break.start_break().unwrap();
+    let mut out_of_shift = make_courier("Zack", 7);
+    out_of_shift
+        .schedule_shift(
+            Shift::new(
+                now + Duration::from_secs(3600),
+                now + Duration::from_secs(7200),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    let mut couriers = vec![off_duty, on_break, out_of_shift, make_courier("Ann", 1)];
+
+    let mut order = Order::new(
+        OrderId::new(Uuid::new_v4()),
+        Location::new(1, 1).unwrap(),
+        Volume::new(10).unwrap(),
+    )
+    .unwrap();
+
+    let result = OrderDispatcherService
+        .dispatch(
+            from_mut(&mut order),
+            &mut couriers,
+            now,
+            &CityMap::default(),
+        )
+        .unwrap();
+    assert_eq!(result.1.name(), "Ann");
 }
@@ -0,0 +1,6 @@
+use crate::errors::RepositoryError;
+
+pub trait BlockedCellRepositoryPort {
+    /// Grid cells couriers cannot pass through, as `(x, y)`.
+    fn get_all(&mut self) -> Result<Vec<(u16, u16)>, RepositoryError>;
+}
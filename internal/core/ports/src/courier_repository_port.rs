@@ -13,8 +13,15 @@ pub struct GetAllCouriersResponse {
 
 pub trait CourierRepositoryPort {
     fn add(&mut self, courier: Courier) -> Result<(), RepositoryError>;
+    /// Fails with `Conflict` when the stored courier moved past the loaded
+    /// version, so a courier can be saved once per load.
     fn update(&mut self, courier: Courier) -> Result<(), RepositoryError>;
     fn get_by_id(&mut self, id: CourierId) -> Result<Courier, RepositoryError>;
+    /// Couriers on duty, not winding down a shift, with a free storage place.
+    /// Inside a unit of work they stay locked until it ends, and couriers
+    /// locked by another unit of work are left out.
     fn get_all_free(&mut self) -> Result<Vec<Courier>, RepositoryError>;
+    /// Couriers with a shift scheduled, with all their storage places.
+    fn get_all_scheduled(&mut self) -> Result<Vec<Courier>, RepositoryError>;
     fn get_all_couriers(&mut self) -> Result<Vec<GetAllCouriersResponse>, RepositoryError>;
 }
@@ -10,6 +10,10 @@ pub enum RepositoryError {
     DatabaseError(String),
     MapError(String),
     NotFound(String),
+    /// The aggregate is already stored under the same id.
+    AlreadyExists(String),
+    /// The stored aggregate changed since it was loaded.
+    Conflict(String),
 }
 
 impl Error for RepositoryError {}
@@ -26,6 +30,12 @@ impl Display for RepositoryError {
             RepositoryError::NotFound(msg) => {
                 write!(f, "Could not find: {}", msg)
             }
+            RepositoryError::AlreadyExists(msg) => {
+                write!(f, "Already exists: {}", msg)
+            }
+            RepositoryError::Conflict(msg) => {
+                write!(f, "Version conflict: {}", msg)
+            }
         }
     }
 }
@@ -62,3 +72,26 @@ impl From<DomainModelError> for GeoClientError {
         Self::ExecutionError(v.to_string())
     }
 }
+
+#[derive(Debug)]
+pub enum ProducerError {
+    /// The event could not be turned into a broker record; retrying will not help.
+    EncodeError(String),
+    /// The broker did not acknowledge the record.
+    DeliveryError(String),
+}
+
+impl Error for ProducerError {}
+
+impl Display for ProducerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::EncodeError(msg) => {
+                write!(f, "Producer encode error: {}", msg)
+            }
+            Self::DeliveryError(msg) => {
+                write!(f, "Producer delivery error: {}", msg)
+            }
+        }
+    }
+}
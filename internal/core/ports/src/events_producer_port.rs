@@ -1,9 +1,13 @@
 use std::error::Error;
 use std::fmt;
+use std::time::SystemTime;
 
+use async_trait::async_trait;
 use domain::model::kernel::message::Message;
 use domain::model::order::order_events::OrderEvent;
 
+use crate::errors::ProducerError;
+
 #[derive(Debug)]
 pub enum Events {
     Order(OrderEvent),
@@ -28,7 +32,10 @@ impl TryFrom<&Message> for Events {
     fn try_from(v: &Message) -> Result<Self, Self::Error> {
         let event = match v.name.as_str() {
             "created" => serde_json::from_str(&v.payload)?,
+            "picked_up" => serde_json::from_str(&v.payload)?,
             "completed" => serde_json::from_str(&v.payload)?,
+            "cancelled" => serde_json::from_str(&v.payload)?,
+            "delivery_window_missed" => serde_json::from_str(&v.payload)?,
             _ => {
                 return Err(Box::new(UnsupportedEventName(v.name.clone())));
             }
@@ -49,6 +56,9 @@ impl fmt::Display for UnsupportedEventName {
 
 impl Error for UnsupportedEventName {}
 
-pub trait EventsProducerPort {
-    fn publish(&self, e: Events);
+#[async_trait]
+pub trait EventsProducerPort: Send + Sync {
+    /// Resolves once the broker acknowledged the event. `occurred_at` is when
+    /// the event was stored in the outbox, so a replayed event keeps it.
+    async fn publish(&self, e: Events, occurred_at: SystemTime) -> Result<(), ProducerError>;
 }
@@ -0,0 +1,9 @@
+use domain::model::kernel::event::EventId;
+
+use crate::errors::RepositoryError;
+
+pub trait InboxRepositoryPort {
+    /// Records an inbound event as processed. Returns `false` when it was
+    /// already recorded, so the caller can skip a redelivered event.
+    fn try_record(&mut self, event_id: EventId) -> Result<bool, RepositoryError>;
+}
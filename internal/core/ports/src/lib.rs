@@ -1,7 +1,9 @@
+pub mod blocked_cell_repository_port;
 pub mod courier_repository_port;
 pub mod errors;
 pub mod events_producer_port;
 pub mod geo_service_port;
+pub mod inbox_repository_port;
 pub mod order_repository_port;
 pub mod outbox_repository;
 pub mod unit_of_work_port;
@@ -1,13 +1,112 @@
+use std::cmp::Reverse;
+use std::time::SystemTime;
+
+use domain::model::courier::courier_aggregate::CourierId;
 use domain::model::order::order_aggregate::Order;
 use domain::model::order::order_aggregate::OrderId;
+use domain::model::order::order_aggregate::OrderStatus;
 
 use crate::errors::RepositoryError;
 
+/// Conditions an order must meet; empty fields match every order.
+#[derive(Debug, Clone, Default)]
+pub struct OrderFilter {
+    pub statuses: Vec<OrderStatus>,
+    pub courier_id: Option<CourierId>,
+    pub created_from: Option<SystemTime>,
+    pub created_before: Option<SystemTime>,
+}
+
+impl OrderFilter {
+    pub fn with_statuses(statuses: impl IntoIterator<Item = OrderStatus>) -> Self {
+        Self {
+            statuses: statuses.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn matches(&self, order: &Order) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(order.status()))
+            && self
+                .courier_id
+                .is_none_or(|courier_id| *order.courier_id() == Some(courier_id))
+            && self
+                .created_from
+                .is_none_or(|from| order.created_at() >= from)
+            && self
+                .created_before
+                .is_none_or(|before| order.created_at() < before)
+    }
+}
+
+/// Orders with the same creation time are ordered by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderSort {
+    #[default]
+    OldestFirst,
+    NewestFirst,
+}
+
+/// A filtered, sorted and paged selection of orders.
+#[derive(Debug, Clone, Default)]
+pub struct OrderCriteria {
+    pub filter: OrderFilter,
+    pub sort: OrderSort,
+    pub limit: Option<u32>,
+    pub offset: u32,
+}
+
+impl OrderCriteria {
+    pub fn matching(filter: OrderFilter) -> Self {
+        Self {
+            filter,
+            ..Self::default()
+        }
+    }
+
+    /// Evaluates the criteria in memory, the way a repository would.
+    pub fn apply(&self, orders: impl IntoIterator<Item = Order>) -> Vec<Order> {
+        let mut selected: Vec<Order> = orders
+            .into_iter()
+            .filter(|order| self.filter.matches(order))
+            .collect();
+        match self.sort {
+            OrderSort::OldestFirst => {
+                selected.sort_by_key(|order| (order.created_at(), order.id().value()))
+            }
+            OrderSort::NewestFirst => {
+                selected.sort_by_key(|order| (Reverse(order.created_at()), order.id().value()))
+            }
+        }
+
+        selected
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+}
+
+/// A status an order went through, recorded on every transition.
+#[derive(Debug, Clone)]
+pub struct OrderHistoryEntry {
+    pub status: OrderStatus,
+    pub courier_id: Option<CourierId>,
+    pub occurred_at: SystemTime,
+}
+
 pub trait OrderRepositoryPort {
     fn add(&mut self, order: &Order) -> Result<(), RepositoryError>;
+    /// Fails with `Conflict` when the stored order moved past the loaded
+    /// version, so an order can be saved once per load.
     fn update(&mut self, order: &Order) -> Result<(), RepositoryError>;
     fn get_by_id(&mut self, id: OrderId) -> Result<Order, RepositoryError>;
     fn get_any_new(&mut self) -> Result<Order, RepositoryError>;
     fn get_all_assigned(&mut self) -> Result<Vec<Order>, RepositoryError>;
-    fn raw(&mut self, query: String) -> Result<Vec<Order>, RepositoryError>;
+    /// Inside a unit of work the orders stay locked until it ends, and orders
+    /// locked by another unit of work are left out.
+    fn find(&mut self, criteria: &OrderCriteria) -> Result<Vec<Order>, RepositoryError>;
+    fn count(&mut self, filter: &OrderFilter) -> Result<u64, RepositoryError>;
+    /// Oldest entries first.
+    fn get_history(&mut self, id: OrderId) -> Result<Vec<OrderHistoryEntry>, RepositoryError>;
 }
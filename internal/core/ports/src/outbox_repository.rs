@@ -1,3 +1,6 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
 use domain::model::kernel::message::Message;
 
 use crate::errors::RepositoryError;
@@ -5,5 +8,28 @@ use crate::errors::RepositoryError;
 pub trait OutboxRepositoryPort {
     fn add(&mut self, message: &Message) -> Result<(), RepositoryError>;
     fn update(&mut self, message: &Message) -> Result<(), RepositoryError>;
-    fn get_not_published_messages(&mut self) -> Result<Vec<Message>, RepositoryError>;
+    /// Takes up to `limit` due messages, oldest first, and hides them from
+    /// other relays for `lease`, so concurrent relays never share a message.
+    fn claim_not_published_messages(
+        &mut self,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<Message>, RepositoryError>;
+    /// Deletes up to `limit` messages published before `cutoff` and returns how
+    /// many were removed. Dead-lettered messages are kept for inspection.
+    fn delete_published_before(
+        &mut self,
+        cutoff: SystemTime,
+        limit: usize,
+    ) -> Result<u64, RepositoryError>;
+    /// Queues published and dead-lettered messages that occurred in `[from, to)`
+    /// for another delivery. Rows keep their id, which is the event id consumers
+    /// dedupe on.
+    fn replay(&mut self, from: SystemTime, to: SystemTime) -> Result<u64, RepositoryError>;
+}
+
+pub trait OutboxListenerPort {
+    /// Blocks until new messages are signalled, returning `false` once
+    /// `timeout` elapses without a signal.
+    fn wait_for_messages(&mut self, timeout: Duration) -> Result<bool, RepositoryError>;
 }
@@ -1,11 +1,15 @@
 use crate::courier_repository_port::CourierRepositoryPort;
 use crate::errors::RepositoryError;
+use crate::inbox_repository_port::InboxRepositoryPort;
 use crate::order_repository_port::OrderRepositoryPort;
+use crate::outbox_repository::OutboxRepositoryPort;
 
 pub trait UnitOfWorkPort {
     type Uow: UnitOfWorkPort;
     type CourierRepo: CourierRepositoryPort;
     type OrderRepo: OrderRepositoryPort;
+    type OutboxRepo: OutboxRepositoryPort;
+    type InboxRepo: InboxRepositoryPort;
 
     fn transaction<F, T>(&mut self, f: F) -> Result<T, RepositoryError>
     where
@@ -14,4 +18,12 @@ pub trait UnitOfWorkPort {
     fn courier_repo(&mut self) -> Self::CourierRepo;
 
     fn order_repo(&mut self) -> Self::OrderRepo;
+
+    /// Inside a transaction, messages added here commit or roll back together
+    /// with the aggregate changes made through the other repositories.
+    fn outbox_repo(&mut self) -> Self::OutboxRepo;
+
+    /// Inside a transaction, a recorded event id rolls back with the changes
+    /// it guards, so a failed attempt can be redelivered.
+    fn inbox_repo(&mut self) -> Self::InboxRepo;
 }
@@ -7,6 +7,163 @@ use serde::{Deserialize, Serialize};
 
 use crate::{models, types::*};
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum AddStoragePlaceResponse {
+    /// Успешный ответ
+    Status201
+    (models::StoragePlace)
+    ,
+    /// Ошибка валидации
+    Status400
+    (models::Error)
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка выполнения бизнес логики
+    Status409
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum DeactivateCourierResponse {
+    /// Успешный ответ
+    Status204
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка выполнения бизнес логики
+    Status409
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum GetCourierResponse {
+    /// Успешный ответ
+    Status200
+    (models::CourierDetails)
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum RemoveStoragePlaceResponse {
+    /// Успешный ответ
+    Status204
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка выполнения бизнес логики
+    Status409
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum UpdateCourierResponse {
+    /// Успешный ответ
+    Status204
+    ,
+    /// Ошибка валидации
+    Status400
+    (models::Error)
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка выполнения бизнес логики
+    Status409
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum GetOrderResponse {
+    /// Успешный ответ
+    Status200
+    (models::OrderDetails)
+    ,
+    /// Не найдено
+    Status404
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ListOrdersResponse {
+    /// Успешный ответ
+    Status200
+    (models::OrdersPage)
+    ,
+    /// Ошибка валидации
+    Status400
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum ReplayOutboxResponse {
+    /// Успешный ответ
+    Status200
+    (models::OutboxReplayResult)
+    ,
+    /// Ошибка валидации
+    Status400
+    (models::Error)
+    ,
+    /// Ошибка
+    Status0
+    (models::Error)
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[must_use]
 #[allow(clippy::large_enum_variant)]
@@ -116,4 +273,102 @@ pub trait Default<E: std::fmt::Debug + Send + Sync + 'static = ()>: super::Error
     host: &Host,
     cookies: &CookieJar,
     ) -> Result<GetOrdersResponse, E>;
+
+    /// Добавить место хранения курьеру.
+    ///
+    /// AddStoragePlace - POST /api/v1/couriers/{courier_id}/storage-places
+    async fn add_storage_place(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::AddStoragePlacePathParams,
+            body: &Option<models::NewStoragePlace>,
+    ) -> Result<AddStoragePlaceResponse, E>;
+
+    /// Вывести курьера из работы.
+    ///
+    /// DeactivateCourier - POST /api/v1/couriers/{courier_id}/deactivate
+    async fn deactivate_courier(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::DeactivateCourierPathParams,
+    ) -> Result<DeactivateCourierResponse, E>;
+
+    /// Получить курьера с местами хранения и текущими заказами.
+    ///
+    /// GetCourier - GET /api/v1/couriers/{courier_id}
+    async fn get_courier(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::GetCourierPathParams,
+    ) -> Result<GetCourierResponse, E>;
+
+    /// Удалить свободное место хранения.
+    ///
+    /// RemoveStoragePlace - DELETE /api/v1/couriers/{courier_id}/storage-places/{storage_place_id}
+    async fn remove_storage_place(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::RemoveStoragePlacePathParams,
+    ) -> Result<RemoveStoragePlaceResponse, E>;
+
+    /// Изменить имя или скорость курьера.
+    ///
+    /// UpdateCourier - PATCH /api/v1/couriers/{courier_id}
+    async fn update_courier(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::UpdateCourierPathParams,
+            body: &Option<models::UpdateCourier>,
+    ) -> Result<UpdateCourierResponse, E>;
+
+    /// Получить заказ с историей статусов.
+    ///
+    /// GetOrder - GET /api/v1/orders/{order_id}
+    async fn get_order(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      path_params: &models::GetOrderPathParams,
+    ) -> Result<GetOrderResponse, E>;
+
+    /// Получить страницу заказов с фильтрами по статусу, курьеру и дате создания.
+    ///
+    /// ListOrders - GET /api/v1/orders
+    async fn list_orders(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+      query_params: &models::ListOrdersQueryParams,
+    ) -> Result<ListOrdersResponse, E>;
+
+    /// Повторно опубликовать события из outbox за интервал времени.
+    ///
+    /// ReplayOutbox - POST /api/v1/admin/outbox/replay
+    async fn replay_outbox(
+    &self,
+    
+    method: &Method,
+    host: &Host,
+    cookies: &CookieJar,
+            body: &models::OutboxReplayRange,
+    ) -> Result<ReplayOutboxResponse, E>;
 }
@@ -60,6 +60,89 @@ pub fn check_xss_map<T>(v: &std::collections::HashMap<String, T>) -> std::result
 }
 
 
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct AddStoragePlacePathParams {
+    /// Идентификатор курьера
+    pub courier_id: uuid::Uuid,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct DeactivateCourierPathParams {
+    /// Идентификатор курьера
+    pub courier_id: uuid::Uuid,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetCourierPathParams {
+    /// Идентификатор курьера
+    pub courier_id: uuid::Uuid,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetOrderPathParams {
+    /// Идентификатор заказа
+    pub order_id: uuid::Uuid,
+}
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ListOrdersQueryParams {
+    /// Статусы заказов: created, assigned, picked_up, completed или cancelled
+    #[serde(rename = "status")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub status: Option<Vec<String>>,
+    /// Идентификатор курьера
+    #[serde(rename = "courier_id")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub courier_id: Option<uuid::Uuid>,
+    /// Заказы, созданные не раньше этого момента
+    #[serde(rename = "created_from")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub created_from: Option<chrono::DateTime::<chrono::Utc>>,
+    /// Заказы, созданные раньше этого момента
+    #[serde(rename = "created_to")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub created_to: Option<chrono::DateTime::<chrono::Utc>>,
+    /// Размер страницы
+    #[serde(rename = "limit")]
+    #[validate(
+            range(min = 1u32, max = 100u32),
+    )]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub limit: Option<u32>,
+    /// Смещение от начала выборки
+    #[serde(rename = "offset")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct RemoveStoragePlacePathParams {
+    /// Идентификатор курьера
+    pub courier_id: uuid::Uuid,
+    /// Идентификатор места хранения
+    pub storage_place_id: uuid::Uuid,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct UpdateCourierPathParams {
+    /// Идентификатор курьера
+    pub courier_id: uuid::Uuid,
+}
+
+
 
 
 
@@ -207,6 +290,79 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Courier> {
 
 
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct CourierDetails {
+    /// Идентификатор
+    #[serde(rename = "id")]
+    pub id: uuid::Uuid,
+
+    /// Имя
+    #[serde(rename = "name")]
+    #[validate(
+            length(min = 1),
+          custom(function = "check_xss_string"),
+    )]
+    pub name: String,
+
+    /// Скорость
+    #[serde(rename = "speed")]
+    #[validate(
+            range(min = 1u32),
+    )]
+    pub speed: u32,
+
+    /// Статус: off_duty, available, busy, on_break или deactivated
+    #[serde(rename = "status")]
+    #[validate(
+          custom(function = "check_xss_string"),
+    )]
+    pub status: String,
+
+    #[serde(rename = "location")]
+    #[validate(
+            nested,
+    )]
+    pub location: models::Location,
+
+    /// Места хранения
+    #[serde(rename = "storage_places")]
+    #[validate(
+            nested,
+    )]
+    pub storage_places: Vec<models::StoragePlace>,
+
+    /// Заказы, которые везет курьер
+    #[serde(rename = "orders")]
+    #[validate(
+            nested,
+    )]
+    pub orders: Vec<models::Order>,
+
+}
+
+
+
+
+
+impl CourierDetails {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: uuid::Uuid, name: String, speed: u32, status: String, location: models::Location, storage_places: Vec<models::StoragePlace>, orders: Vec<models::Order>, ) -> CourierDetails {
+        CourierDetails {
+            id,
+            name,
+            speed,
+            status,
+            location,
+            storage_places,
+            orders,
+        }
+    }
+}
+
+
+
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct Error {
@@ -357,6 +513,16 @@ pub struct Location {
     )]
     pub y: u32,
 
+    /// Latitude
+    #[serde(rename = "latitude")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub latitude: Option<f64>,
+
+    /// Longitude
+    #[serde(rename = "longitude")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub longitude: Option<f64>,
+
 }
 
 
@@ -369,6 +535,8 @@ impl Location {
         Location {
             x,
             y,
+            latitude: None,
+            longitude: None,
         }
     }
 }
@@ -387,6 +555,22 @@ impl std::fmt::Display for Location {
             Some("y".to_string()),
             Some(self.y.to_string()),
 
+
+            self.latitude.as_ref().map(|latitude| {
+                [
+                    "latitude".to_string(),
+                    latitude.to_string(),
+                ].join(",")
+            }),
+
+
+            self.longitude.as_ref().map(|longitude| {
+                [
+                    "longitude".to_string(),
+                    longitude.to_string(),
+                ].join(",")
+            }),
+
         ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
@@ -406,6 +590,8 @@ impl std::str::FromStr for Location {
         struct IntermediateRep {
             pub x: Vec<u32>,
             pub y: Vec<u32>,
+            pub latitude: Vec<f64>,
+            pub longitude: Vec<f64>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -427,6 +613,10 @@ impl std::str::FromStr for Location {
                     "x" => intermediate_rep.x.push(<u32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
                     "y" => intermediate_rep.y.push(<u32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "latitude" => intermediate_rep.latitude.push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "longitude" => intermediate_rep.longitude.push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     _ => return std::result::Result::Err("Unexpected key while parsing Location".to_string())
                 }
             }
@@ -439,6 +629,8 @@ impl std::str::FromStr for Location {
         std::result::Result::Ok(Location {
             x: intermediate_rep.x.into_iter().next().ok_or_else(|| "x missing in Location".to_string())?,
             y: intermediate_rep.y.into_iter().next().ok_or_else(|| "y missing in Location".to_string())?,
+            latitude: intermediate_rep.latitude.into_iter().next(),
+            longitude: intermediate_rep.longitude.into_iter().next(),
         })
     }
 }
@@ -617,6 +809,43 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<NewCourier>
 
 
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct NewStoragePlace {
+    /// Название
+    #[serde(rename = "name")]
+    #[validate(
+            length(min = 1),
+          custom(function = "check_xss_string"),
+    )]
+    pub name: String,
+
+    /// Объем
+    #[serde(rename = "total_volume")]
+    #[validate(
+            range(min = 1u32, max = 65535u32),
+    )]
+    pub total_volume: u32,
+
+}
+
+
+
+
+
+impl NewStoragePlace {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(name: String, total_volume: u32, ) -> NewStoragePlace {
+        NewStoragePlace {
+            name,
+            total_volume,
+        }
+    }
+}
+
+
+
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct Order {
@@ -744,3 +973,332 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<Order> {
 
 
 
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OrderDetails {
+    /// Идентификатор
+    #[serde(rename = "id")]
+    pub id: uuid::Uuid,
+
+    /// Статус: created, assigned, picked_up, completed или cancelled
+    #[serde(rename = "status")]
+    pub status: String,
+
+    /// Идентификатор назначенного курьера
+    #[serde(rename = "courier_id")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub courier_id: Option<uuid::Uuid>,
+
+    /// Объем
+    #[serde(rename = "volume")]
+    pub volume: u32,
+
+    /// Адрес доставки
+    #[serde(rename = "location")]
+          #[validate(nested)]
+    pub location: models::Location,
+
+    /// Адрес забора
+    #[serde(rename = "pickup_location")]
+          #[validate(nested)]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub pickup_location: Option<models::Location>,
+
+    /// Момент создания
+    #[serde(rename = "created_at")]
+    pub created_at: chrono::DateTime::<chrono::Utc>,
+
+    /// История статусов, от старых к новым
+    #[serde(rename = "history")]
+          #[validate(nested)]
+    pub history: Vec<models::OrderHistoryEntry>,
+
+}
+
+
+
+
+
+impl OrderDetails {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: uuid::Uuid, status: String, volume: u32, location: models::Location, created_at: chrono::DateTime::<chrono::Utc>, history: Vec<models::OrderHistoryEntry>, ) -> OrderDetails {
+        OrderDetails {
+            id,
+            status,
+            courier_id: None,
+            volume,
+            location,
+            pickup_location: None,
+            created_at,
+            history,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OrderHistoryEntry {
+    /// Статус: created, assigned, picked_up, completed или cancelled
+    #[serde(rename = "status")]
+    pub status: String,
+
+    /// Идентификатор курьера
+    #[serde(rename = "courier_id")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub courier_id: Option<uuid::Uuid>,
+
+    /// Момент перехода
+    #[serde(rename = "occurred_at")]
+    pub occurred_at: chrono::DateTime::<chrono::Utc>,
+
+}
+
+
+
+
+
+impl OrderHistoryEntry {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(status: String, occurred_at: chrono::DateTime::<chrono::Utc>, ) -> OrderHistoryEntry {
+        OrderHistoryEntry {
+            status,
+            courier_id: None,
+            occurred_at,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OrderSummary {
+    /// Идентификатор
+    #[serde(rename = "id")]
+    pub id: uuid::Uuid,
+
+    /// Статус: created, assigned, picked_up, completed или cancelled
+    #[serde(rename = "status")]
+    pub status: String,
+
+    /// Идентификатор назначенного курьера
+    #[serde(rename = "courier_id")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub courier_id: Option<uuid::Uuid>,
+
+    /// Объем
+    #[serde(rename = "volume")]
+    pub volume: u32,
+
+    /// Адрес доставки
+    #[serde(rename = "location")]
+          #[validate(nested)]
+    pub location: models::Location,
+
+    /// Момент создания
+    #[serde(rename = "created_at")]
+    pub created_at: chrono::DateTime::<chrono::Utc>,
+
+}
+
+
+
+
+
+impl OrderSummary {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: uuid::Uuid, status: String, volume: u32, location: models::Location, created_at: chrono::DateTime::<chrono::Utc>, ) -> OrderSummary {
+        OrderSummary {
+            id,
+            status,
+            courier_id: None,
+            volume,
+            location,
+            created_at,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OrdersPage {
+    /// Заказы, от новых к старым
+    #[serde(rename = "items")]
+          #[validate(nested)]
+    pub items: Vec<models::OrderSummary>,
+
+    /// Общее число заказов, подходящих под фильтр
+    #[serde(rename = "total")]
+    pub total: u64,
+
+    /// Размер страницы
+    #[serde(rename = "limit")]
+    pub limit: u32,
+
+    /// Смещение от начала выборки
+    #[serde(rename = "offset")]
+    pub offset: u32,
+
+}
+
+
+
+
+
+impl OrdersPage {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(items: Vec<models::OrderSummary>, total: u64, limit: u32, offset: u32, ) -> OrdersPage {
+        OrdersPage {
+            items,
+            total,
+            limit,
+            offset,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OutboxReplayRange {
+    /// События, произошедшие не раньше этого момента
+    #[serde(rename = "from")]
+    pub from: chrono::DateTime::<chrono::Utc>,
+
+    /// События, произошедшие раньше этого момента
+    #[serde(rename = "to")]
+    pub to: chrono::DateTime::<chrono::Utc>,
+
+}
+
+
+
+
+
+impl OutboxReplayRange {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(from: chrono::DateTime::<chrono::Utc>, to: chrono::DateTime::<chrono::Utc>, ) -> OutboxReplayRange {
+        OutboxReplayRange {
+            from,
+            to,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct OutboxReplayResult {
+    /// Число событий, поставленных в очередь на повторную публикацию
+    #[serde(rename = "replayed")]
+    pub replayed: u64,
+
+}
+
+
+
+
+
+impl OutboxReplayResult {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(replayed: u64, ) -> OutboxReplayResult {
+        OutboxReplayResult {
+            replayed,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct StoragePlace {
+    /// Идентификатор
+    #[serde(rename = "id")]
+    pub id: uuid::Uuid,
+
+    /// Название
+    #[serde(rename = "name")]
+    #[validate(
+          custom(function = "check_xss_string"),
+    )]
+    pub name: String,
+
+    /// Объем
+    #[serde(rename = "total_volume")]
+    pub total_volume: u32,
+
+    /// Заказ, который лежит в месте хранения
+    #[serde(rename = "order_id")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub order_id: Option<uuid::Uuid>,
+
+}
+
+
+
+
+
+impl StoragePlace {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new(id: uuid::Uuid, name: String, total_volume: u32, ) -> StoragePlace {
+        StoragePlace {
+            id,
+            name,
+            total_volume,
+            order_id: None,
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct UpdateCourier {
+    /// Имя
+    #[serde(rename = "name")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    #[validate(
+            length(min = 1),
+          custom(function = "check_xss_string"),
+    )]
+    pub name: Option<String>,
+
+    /// Скорость
+    #[serde(rename = "speed")]
+    #[serde(skip_serializing_if="Option::is_none")]
+    #[validate(
+            range(min = 1u32, max = 255u32),
+    )]
+    pub speed: Option<u32>,
+
+}
+
+
+
+
+
+impl UpdateCourier {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> UpdateCourier {
+        UpdateCourier {
+            name: None,
+            speed: None,
+        }
+    }
+}